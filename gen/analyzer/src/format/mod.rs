@@ -0,0 +1,336 @@
+//! # Formatter
+//! canonical formatter of `.gen` file (`gen fmt`)
+//!
+//! `Model` is lossy (props are stored in HashMap, style is flattened, comments in style are dropped),
+//! so the formatter checks the file with [`Model`] first, then re-emits it from a lossless tree of the source,
+//! which keeps all comments and the raw text of every value.
+mod style;
+mod template;
+
+use gen_utils::error::{Error, ParseError, ParseType};
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_until},
+    character::complete::{char, multispace0},
+    multi::many0,
+    sequence::{delimited, preceded},
+    IResult,
+};
+
+use crate::{parse::remain_err, Comment, Model};
+
+/// # Formatter
+/// format `.gen` file with consistent indentation and prop ordering
+/// - `<template>`: one tag per line, builtin props come first (in the order of [`crate::BUILTIN_PROPS`]),
+///   then normal props, `:bind` props and `@event` props (each group is sorted by name).
+///   If a tag is longer than `max_width`, each prop will be placed in a single line
+/// - `<script>`: formatted by the `script` callback of [`Formatter::format_with`] (such as rustfmt), or just re-indented
+/// - `<style>`: one property per line, nested styles are indented
+/// ## Example
+/// ```rust
+/// use gen_analyzer::Formatter;
+///
+/// let input = "<template>\n<component name=\"Hello\"><label text=\"'Hi'\"></label></component>\n</template>\n";
+/// let formatter = Formatter::default();
+/// let formatted = formatter.format(input).unwrap();
+/// assert!(formatted.contains("\n    <component name=\"Hello\">\n"));
+/// assert!(formatter.check(&formatted).unwrap());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Formatter {
+    /// spaces of each indent level
+    pub indent: usize,
+    /// max width of a tag line
+    pub max_width: usize,
+}
+
+impl Default for Formatter {
+    fn default() -> Self {
+        Self {
+            indent: 4,
+            max_width: 100,
+        }
+    }
+}
+
+impl Formatter {
+    /// format `.gen` file, `<script>` is only re-indented
+    pub fn format(&self, input: &str) -> Result<String, Error> {
+        self.format_with(input, |_| None)
+    }
+    /// ## format `.gen` file
+    /// `script` is used to format the code in `<script>`, return None to keep the script (re-indented)
+    ///
+    /// invalid file will not be formatted, the error of [`Model::parse`] is returned
+    pub fn format_with<F>(&self, input: &str, script: F) -> Result<String, Error>
+    where
+        F: FnOnce(&str) -> Option<String>,
+    {
+        let _ = input.parse::<Model>()?;
+        let (remain, blocks) = many0(block)(input)
+            .map_err(|_| Error::from(ParseError::template("can not format the file")))?;
+        if !remain.trim().is_empty() {
+            return Err(remain_err(input, remain, ParseType::Template));
+        }
+
+        let mut script = Some(script);
+        let mut lines = vec![];
+        let mut after_section = false;
+        for block in blocks {
+            if after_section {
+                lines.push(String::new());
+            }
+            match block {
+                Block::Comment(comment) => {
+                    after_section = false;
+                    lines.push(comment.to_string());
+                }
+                Block::Section(name, content) => {
+                    after_section = true;
+                    lines.push(format!("<{}>", name));
+                    match name {
+                        "template" => self.template(content, &mut lines)?,
+                        "style" => self.style(content, &mut lines)?,
+                        _ => {
+                            let content = dedent(content);
+                            let formatted = script
+                                .take()
+                                .and_then(|f| f(&content))
+                                .unwrap_or(content);
+                            lines.extend(
+                                formatted
+                                    .trim_end()
+                                    .lines()
+                                    .map(|line| line.trim_end().to_string()),
+                            );
+                        }
+                    }
+                    lines.push(format!("</{}>", name));
+                }
+            }
+        }
+
+        let mut res = lines.join("\n");
+        res.push('\n');
+        Ok(res)
+    }
+    /// check the input is formatted or not, `<script>` is only re-indented, See [`Formatter::check_with`]
+    pub fn check(&self, input: &str) -> Result<bool, Error> {
+        self.check_with(input, |_| None)
+    }
+    /// check the input is formatted or not, `script` is the same as [`Formatter::format_with`]
+    pub fn check_with<F>(&self, input: &str, script: F) -> Result<bool, Error>
+    where
+        F: FnOnce(&str) -> Option<String>,
+    {
+        self.format_with(input, script).map(|formatted| formatted == input)
+    }
+    fn pad(&self, level: usize) -> String {
+        " ".repeat(self.indent * level)
+    }
+    /// push comment as a new line, if the comment is trailing, push it to the end of last line
+    fn comment(&self, comment: &Comment, trailing: bool, level: usize, lines: &mut Vec<String>) {
+        match lines.last_mut() {
+            Some(last) if trailing => {
+                last.push(' ');
+                last.push_str(&comment.to_string());
+            }
+            _ => lines.push(format!("{}{}", self.pad(level), comment)),
+        }
+    }
+}
+
+/// top level block of `.gen` file
+enum Block<'a> {
+    Comment(Comment),
+    /// (section name, raw content)
+    Section(&'a str, &'a str),
+}
+
+fn block(input: &str) -> IResult<&str, Block<'_>> {
+    alt((
+        |input| comment(input).map(|(input, (c, _))| (input, Block::Comment(c))),
+        section,
+    ))(input)
+}
+
+fn section(input: &str) -> IResult<&str, Block<'_>> {
+    // do not trim the content, keep the indent of the first line
+    let (input, name) = preceded(
+        multispace0,
+        delimited(
+            char('<'),
+            alt((tag("template"), tag("script"), tag("style"))),
+            char('>'),
+        ),
+    )(input)?;
+    let end = format!("</{}>", name);
+    let (input, content) = take_until(end.as_str())(input)?;
+    let (input, _) = tag(end.as_str())(input)?;
+    Ok((input, Block::Section(name, content)))
+}
+
+/// ## parse comment with trailing flag
+/// trailing means the comment is in the same line with the previous code
+pub(crate) fn comment(input: &str) -> IResult<&str, (Comment, bool)> {
+    let (remain, space) = multispace0(input)?;
+    let (remain, comment) = Comment::parse(remain)?;
+    let comment = match comment {
+        Comment::Normal(c) => Comment::Normal(c.trim_end().to_string()),
+        Comment::Document(c) => Comment::Document(c.trim_end().to_string()),
+        Comment::File(c) => Comment::File(c.trim_end().to_string()),
+    };
+    Ok((remain, (comment, !space.contains('\n'))))
+}
+
+/// remove the common indent of all lines and the blank lines at start and end
+fn dedent(input: &str) -> String {
+    let indent = input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or_default();
+
+    input
+        .lines()
+        .map(|line| line.get(indent..).unwrap_or_default().trim_end())
+        .skip_while(|line| line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end()
+        .to_string()
+}
+
+#[cfg(test)]
+mod test_format {
+    use super::Formatter;
+
+    const INPUT: &str = r#"//! hello component
+<template>
+  <component name="Hello"  >
+       // title
+    <label @clicked="click()" :text="name" id="title" font_size="16.0"/> // trailing
+    <view class="box"><button text="'ok'" :if="show"></button></view>
+  </component>
+</template>
+
+<script>
+    #[component]
+    pub struct Hello {
+        name: String,
+    }
+</script>
+<style>
+.box{ height: Fit;
+  // nested
+  .inner {width : Fill;}
+}
+#title{ color: #FFF; }
+</style>
+"#;
+
+    const OUTPUT: &str = r#"//! hello component
+<template>
+    <component name="Hello">
+        // title
+        <label id="title" font_size="16.0" :text="name" @clicked="click()" /> // trailing
+        <view class="box">
+            <button :if="show" text="'ok'"></button>
+        </view>
+    </component>
+</template>
+
+<script>
+#[component]
+pub struct Hello {
+    name: String,
+}
+</script>
+
+<style>
+    .box {
+        height: Fit;
+        // nested
+        .inner {
+            width: Fill;
+        }
+    }
+
+    #title {
+        color: #FFF;
+    }
+</style>
+"#;
+
+    #[test]
+    fn format() {
+        let formatter = Formatter::default();
+        let formatted = formatter.format(INPUT).unwrap();
+        assert_eq!(formatted, OUTPUT);
+        // formatted file should not change any more
+        assert!(formatter.check(&formatted).unwrap());
+        assert!(!formatter.check(INPUT).unwrap());
+    }
+
+    #[test]
+    fn long_tag() {
+        let input = "<template><component name=\"Hello\"><label text=\"'a very very long text which is longer than max width'\" font_size=\"16.0\"></label></component></template>";
+        let formatter = Formatter {
+            max_width: 60,
+            ..Default::default()
+        };
+        let formatted = formatter.format(input).unwrap();
+        assert_eq!(
+            formatted,
+            "<template>\n    <component name=\"Hello\">\n        <label\n            font_size=\"16.0\"\n            text=\"'a very very long text which is longer than max width'\"\n        ></label>\n    </component>\n</template>\n"
+        );
+    }
+
+    #[test]
+    fn text() {
+        let input = "<template><component name=\"Hello\"><label   id=\"a\">\n  Hello {{ name }}  </label></component></template>";
+        let formatted = Formatter::default().format(input).unwrap();
+        assert_eq!(
+            formatted,
+            "<template>\n    <component name=\"Hello\">\n        <label id=\"a\">Hello {{ name }}</label>\n    </component>\n</template>\n"
+        );
+    }
+
+    #[test]
+    fn scss_style() {
+        let input = "<style>\n@import   \"theme.gen\" ;\n$main:#FFF;\n@mixin size($w,$h:$w / 2){width:$w;height:$h;}\n.btn{ @include size(100,20);\n&_primary{color:$main;}\n&::hover{ @include size(10) ;}}\n</style>\n";
+        let formatted = Formatter::default().format(input).unwrap();
+        assert_eq!(
+            formatted,
+            "<style>\n    @import \"theme.gen\";\n    $main: #FFF;\n    @mixin size($w, $h: $w / 2) {\n        width: $w;\n        height: $h;\n    }\n\n    .btn {\n        @include size(100, 20);\n        &_primary {\n            color: $main;\n        }\n        &::hover {\n            @include size(10);\n        }\n    }\n</style>\n"
+        );
+    }
+
+    #[test]
+    fn keyframes() {
+        let input = "<style>\n@keyframes fade{from{opacity:0;}\n50%{opacity:0.5;} to{opacity:1;}}\n.a{transition:opacity 0.2s ease_out;}\n</style>\n";
+        let formatted = Formatter::default().format(input).unwrap();
+        assert_eq!(
+            formatted,
+            "<style>\n    @keyframes fade {\n        from {\n            opacity: 0;\n        }\n        50% {\n            opacity: 0.5;\n        }\n        to {\n            opacity: 1;\n        }\n    }\n\n    .a {\n        transition: opacity 0.2s ease_out;\n    }\n</style>\n"
+        );
+    }
+
+    #[test]
+    fn conditions() {
+        let input = "<style>\n@media   (max_width: 600)  and (min_width: 300){.a{height:10;}}\n.b{@platform(android|ios){height:20;}}\n</style>\n";
+        let formatted = Formatter::default().format(input).unwrap();
+        assert_eq!(
+            formatted,
+            "<style>\n    @media (max_width: 600) and (min_width: 300) {\n        .a {\n            height: 10;\n        }\n    }\n\n    .b {\n        @platform(android|ios) {\n            height: 20;\n        }\n    }\n</style>\n"
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let input = "<template>\n  <component name=\"Hello\">\n    <label text=hello />\n  </component>\n</template>\n";
+        let err = Formatter::default().format(input).unwrap_err();
+        assert_eq!(err.span().map(|span| span.line), Some(3));
+    }
+}
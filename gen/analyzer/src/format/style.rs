@@ -0,0 +1,231 @@
+use gen_utils::{
+    common::tokenizer::{
+        FUNCTION_SIGN, HOLDER_END, HOLDER_START, IMPORT, STYLE_CLASS, STYLE_ID, STYLE_PESUDO,
+    },
+    error::{Error, ParseType},
+    parser::{parse_value, trim},
+};
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_until, take_until1, take_while1},
+    character::complete::{multispace0, multispace1},
+    combinator::{opt, recognize},
+    multi::many0,
+    sequence::{delimited, pair, preceded, terminated},
+    IResult,
+};
+
+use crate::{
+    parse::{
+        located_nom_err, remain_err,
+        style::{
+            parse_paren, parse_parent_ident, split_args, STYLE_IMPORT, STYLE_INCLUDE,
+            STYLE_KEYFRAMES, STYLE_MEDIA, STYLE_MIXIN, STYLE_PLATFORM, STYLE_VAR,
+        },
+    },
+    Comment,
+};
+
+use super::{comment, Formatter};
+
+/// node in `<style>`, unlike [`crate::Style`], it keeps the nesting, the order and the comments
+enum Node<'a> {
+    Comment(Comment, bool),
+    /// (key, raw value), key of variable starts with `$`
+    Prop(&'a str, &'a str),
+    /// (name, args) of `@include`
+    Include(&'a str, Option<Vec<&'a str>>),
+    /// path of `@import`
+    Import(&'a str),
+    /// (ident, children)
+    Rule(String, Vec<Node<'a>>),
+}
+
+fn node(input: &str) -> IResult<&str, Node<'_>> {
+    alt((
+        |input| comment(input).map(|(input, (c, trailing))| (input, Node::Comment(c, trailing))),
+        prop,
+        include,
+        import,
+        rule,
+    ))(input)
+}
+
+/// `@import "path/to/theme.gen";`
+fn import(input: &str) -> IResult<&str, Node<'_>> {
+    let (input, path) = preceded(
+        preceded(multispace0, pair(tag(STYLE_IMPORT), multispace0)),
+        delimited(tag("\""), take_until("\""), tag("\"")),
+    )(input)?;
+    let (input, _) = trim(tag(";"))(input)?;
+    Ok((input, Node::Import(path)))
+}
+
+/// `key: value;` | `$var: value;`
+fn prop(input: &str) -> IResult<&str, Node<'_>> {
+    let (input, key) = preceded(
+        multispace0,
+        recognize(pair(opt(tag(STYLE_VAR)), parse_value)),
+    )(input)?;
+    let (input, value) = preceded(trim(tag(":")), terminated(take_until1(";"), tag(";")))(input)?;
+    Ok((input, Node::Prop(key, value.trim())))
+}
+
+/// `@include name;` | `@include name(a, b);`
+fn include(input: &str) -> IResult<&str, Node<'_>> {
+    let (input, name) = preceded(
+        preceded(multispace0, pair(tag(STYLE_INCLUDE), multispace1)),
+        parse_value,
+    )(input)?;
+    let (input, args) = opt(preceded(multispace0, parse_paren))(input)?;
+    let (input, _) = trim(tag(";"))(input)?;
+    Ok((input, Node::Include(name, args.map(split_args))))
+}
+
+/// `.class {...}` | `#id {...}` | `&::pseudo {...}` | `&_suffix {...}` | `@mixin name($a) {...}`
+/// | `@keyframes name {...}` | `from {...}` | `50% {...}` | `@media (...) {...}` | `@platform(...) {...}`
+fn rule(input: &str) -> IResult<&str, Node<'_>> {
+    let (input, ident) = alt((
+        mixin,
+        keyframes,
+        condition,
+        selector,
+        parse_parent_ident,
+        frame,
+    ))(input)?;
+    let (input, _) = trim(tag(HOLDER_START))(input)?;
+    let (input, children) = many0(node)(input)?;
+    let (input, _) = trim(tag(HOLDER_END))(input)?;
+    Ok((input, Node::Rule(ident, children)))
+}
+
+fn selector(input: &str) -> IResult<&str, String> {
+    let (input, (sign, name)) = pair(
+        trim(alt((
+            tag(STYLE_CLASS),
+            tag(STYLE_ID),
+            tag(STYLE_PESUDO),
+            tag(IMPORT),
+            tag(FUNCTION_SIGN),
+        ))),
+        parse_value,
+    )(input)?;
+    Ok((input, format!("{}{}", sign, name)))
+}
+
+/// `@keyframes name`
+fn keyframes(input: &str) -> IResult<&str, String> {
+    let (input, name) = preceded(
+        preceded(multispace0, pair(tag(STYLE_KEYFRAMES), multispace1)),
+        parse_value,
+    )(input)?;
+    Ok((input, format!("{} {}", STYLE_KEYFRAMES, name)))
+}
+
+/// `@media  (max_width:600)` => `@media (max_width:600)`, whitespaces in the query are collapsed
+fn condition(input: &str) -> IResult<&str, String> {
+    let (input, (kind, query)) = preceded(
+        multispace0,
+        pair(
+            alt((tag(STYLE_MEDIA), tag(STYLE_PLATFORM))),
+            take_until1(HOLDER_START),
+        ),
+    )(input)?;
+    let query = query.split_whitespace().collect::<Vec<_>>().join(" ");
+    Ok((
+        input,
+        if kind == STYLE_MEDIA {
+            format!("{} {}", kind, query)
+        } else {
+            format!("{}{}", kind, query)
+        },
+    ))
+}
+
+/// keyframe in `@keyframes`: `from` | `to` | `50%`
+fn frame(input: &str) -> IResult<&str, String> {
+    let (input, frame) = trim(take_while1(|c: char| {
+        c.is_alphanumeric() || c == '%' || c == '.'
+    }))(input)?;
+    Ok((input, frame.to_string()))
+}
+
+/// `@mixin name($a, $b:1)` => `@mixin name($a, $b: 1)`
+fn mixin(input: &str) -> IResult<&str, String> {
+    let (input, name) = preceded(
+        preceded(multispace0, pair(tag(STYLE_MIXIN), multispace1)),
+        parse_value,
+    )(input)?;
+    let (input, params) = opt(preceded(multispace0, parse_paren))(input)?;
+    let params = params.map(|params| {
+        split_args(params)
+            .into_iter()
+            .map(|param| match param.split_once(':') {
+                Some((name, default)) => format!("{}: {}", name.trim(), default.trim()),
+                None => param.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    });
+    Ok((
+        input,
+        match params {
+            Some(params) => format!("{} {}({})", STYLE_MIXIN, name, params),
+            None => format!("{} {}", STYLE_MIXIN, name),
+        },
+    ))
+}
+
+impl Formatter {
+    /// format the content of `<style>`, top level styles are separated by a blank line
+    pub(crate) fn style(&self, input: &str, lines: &mut Vec<String>) -> Result<(), Error> {
+        let (remain, nodes) =
+            many0(node)(input).map_err(|e| located_nom_err(input, e, ParseType::Style))?;
+        if !remain.trim().is_empty() {
+            return Err(remain_err(input, remain, ParseType::Style));
+        }
+        self.style_nodes(&nodes, 1, lines);
+        Ok(())
+    }
+
+    fn style_nodes(&self, nodes: &[Node], level: usize, lines: &mut Vec<String>) {
+        let pad = self.pad(level);
+        let mut after_rule = false;
+        for node in nodes {
+            match node {
+                Node::Comment(comment, trailing) => {
+                    if after_rule && !trailing && level == 1 {
+                        lines.push(String::new());
+                    }
+                    after_rule = false;
+                    self.comment(comment, *trailing, level, lines);
+                }
+                Node::Prop(key, value) => {
+                    after_rule = false;
+                    lines.push(format!("{}{}: {};", pad, key, value));
+                }
+                Node::Import(path) => {
+                    after_rule = false;
+                    lines.push(format!("{}{} \"{}\";", pad, STYLE_IMPORT, path.trim()));
+                }
+                Node::Include(name, args) => {
+                    after_rule = false;
+                    let args = args
+                        .as_ref()
+                        .map(|args| format!("({})", args.join(", ")))
+                        .unwrap_or_default();
+                    lines.push(format!("{}{} {}{};", pad, STYLE_INCLUDE, name, args));
+                }
+                Node::Rule(ident, children) => {
+                    if after_rule && level == 1 {
+                        lines.push(String::new());
+                    }
+                    after_rule = true;
+                    lines.push(format!("{}{} {}", pad, ident, HOLDER_START));
+                    self.style_nodes(children, level + 1, lines);
+                    lines.push(format!("{}{}", pad, HOLDER_END));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use gen_utils::{
+    common::tokenizer::{END_SIGN, END_START_SIGN, EQUAL_SIGN, SELF_END_SIGN},
+    error::{Error, ParseType},
+    parser::{parse_string, parse_value, trim},
+};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, multispace0},
+    combinator::opt,
+    multi::many0,
+    sequence::{delimited, preceded},
+    IResult,
+};
+
+use crate::{
+    parse::{located_nom_err, remain_err, template::parse_text},
+    Comment, BUILTIN_PROPS,
+};
+
+use super::{comment, Formatter};
+
+/// node in `<template>`
+enum Node<'a> {
+    Comment(Comment, bool),
+    Tag(Tag<'a>),
+    /// text child (trimmed), such as `Hello {{ name }}`
+    Text(&'a str),
+}
+
+struct Tag<'a> {
+    name: &'a str,
+    props: Vec<Attr<'a>>,
+    children: Vec<Node<'a>>,
+    self_closed: bool,
+}
+
+/// prop of tag, keep the raw value
+struct Attr<'a> {
+    /// `""` | `":"` | `"@"`
+    sign: &'a str,
+    key: &'a str,
+    value: Option<&'a str>,
+}
+
+impl Attr<'_> {
+    /// builtin props first (in the order of [`BUILTIN_PROPS`]), then normal, bind and event props
+    fn order(&self) -> usize {
+        BUILTIN_PROPS
+            .iter()
+            .position(|prop| *prop == self.key)
+            .unwrap_or_else(|| {
+                BUILTIN_PROPS.len()
+                    + match self.sign {
+                        ":" => 1,
+                        "@" => 2,
+                        _ => 0,
+                    }
+            })
+    }
+}
+
+impl std::fmt::Display for Attr<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.value {
+            Some(value) => write!(f, "{}{}{}\"{}\"", self.sign, self.key, EQUAL_SIGN, value),
+            None => write!(f, "{}{}", self.sign, self.key),
+        }
+    }
+}
+
+fn node(input: &str) -> IResult<&str, Node<'_>> {
+    alt((
+        |input| comment(input).map(|(input, (c, trailing))| (input, Node::Comment(c, trailing))),
+        |input| tag_node(input).map(|(input, t)| (input, Node::Tag(t))),
+        |input| parse_text(input).map(|(input, text)| (input, Node::Text(text))),
+    ))(input)
+}
+
+fn attr(input: &str) -> IResult<&str, Attr<'_>> {
+    let (input, sign) = opt(alt((tag(":"), tag("@"))))(input)?;
+    let (input, key) = parse_value(input)?;
+    let (input, value) = opt(preceded(trim(tag(EQUAL_SIGN)), parse_string))(input)?;
+    Ok((
+        input,
+        Attr {
+            sign: sign.unwrap_or_default(),
+            key,
+            value,
+        },
+    ))
+}
+
+fn tag_node(input: &str) -> IResult<&str, Tag<'_>> {
+    let (input, name) = preceded(multispace0, preceded(char('<'), parse_value))(input)?;
+    let (input, props) = many0(trim(attr))(input)?;
+    let (input, close) = alt((tag(SELF_END_SIGN), tag(END_SIGN)))(input)?;
+    let self_closed = close == SELF_END_SIGN;
+    let (input, children) = if self_closed {
+        (input, vec![])
+    } else {
+        let (input, children) = many0(node)(input)?;
+        let (input, _) = trim(delimited(
+            tag(END_START_SIGN),
+            trim(tag(name)),
+            tag(END_SIGN),
+        ))(input)?;
+        (input, children)
+    };
+
+    Ok((
+        input,
+        Tag {
+            name,
+            props,
+            children,
+            self_closed,
+        },
+    ))
+}
+
+impl Formatter {
+    /// format the content of `<template>`
+    pub(crate) fn template(&self, input: &str, lines: &mut Vec<String>) -> Result<(), Error> {
+        let (remain, nodes) =
+            many0(node)(input).map_err(|e| located_nom_err(input, e, ParseType::Template))?;
+        if !remain.trim().is_empty() {
+            return Err(remain_err(input, remain, ParseType::Template));
+        }
+        for node in nodes.iter() {
+            self.node(node, 1, lines);
+        }
+        Ok(())
+    }
+
+    fn node(&self, node: &Node, level: usize, lines: &mut Vec<String>) {
+        match node {
+            Node::Comment(comment, trailing) => self.comment(comment, *trailing, level, lines),
+            Node::Tag(t) => self.tag(t, level, lines),
+            Node::Text(text) => lines.push(format!("{}{}", self.pad(level), text)),
+        }
+    }
+
+    fn tag(&self, t: &Tag, level: usize, lines: &mut Vec<String>) {
+        let pad = self.pad(level);
+        let mut props = t.props.iter().collect::<Vec<_>>();
+        props.sort_by(|a, b| a.order().cmp(&b.order()).then(a.key.cmp(b.key)));
+        // single text child is kept in the same line: `<label>Hello {{ name }}</label>`
+        let text = match t.children.as_slice() {
+            [Node::Text(text)] => Some(*text),
+            _ => None,
+        };
+        let close = if t.self_closed {
+            format!(" {}", SELF_END_SIGN)
+        } else if t.children.is_empty() || text.is_some() {
+            format!(
+                "{}{}{}{}{}",
+                END_SIGN,
+                text.unwrap_or_default(),
+                END_START_SIGN,
+                t.name,
+                END_SIGN
+            )
+        } else {
+            END_SIGN.to_string()
+        };
+
+        let line = props
+            .iter()
+            .fold(format!("{}<{}", pad, t.name), |line, prop| {
+                format!("{} {}", line, prop)
+            });
+
+        if props.len() > 1 && line.len() + close.len() > self.max_width {
+            // one prop per line
+            lines.push(format!("{}<{}", pad, t.name));
+            let prop_pad = self.pad(level + 1);
+            lines.extend(props.iter().map(|prop| format!("{}{}", prop_pad, prop)));
+            lines.push(format!("{}{}", pad, close.trim_start()));
+        } else {
+            lines.push(format!("{}{}", line, close));
+        }
+
+        if !t.children.is_empty() && text.is_none() {
+            for child in t.children.iter() {
+                self.node(child, level + 1, lines);
+            }
+            lines.push(format!("{}{}{}{}", pad, END_START_SIGN, t.name, END_SIGN));
+        }
+    }
+}
@@ -1,6 +1,8 @@
+mod format;
 mod macros;
 mod model;
 mod parse;
 
+pub use format::*;
 pub use model::*;
 pub use parse::*;
\ No newline at end of file
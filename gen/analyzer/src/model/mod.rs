@@ -13,7 +13,7 @@ use nom::{
 pub use poll::*;
 pub use script::*;
 use std::{
-    str::FromStr, sync::{
+    path::Path, str::FromStr, sync::{
         mpsc::{self},
         Arc, RwLock,
     }, thread
@@ -22,9 +22,11 @@ pub use strategy::*;
 pub use style::*;
 use gen_utils::{
     common::{fs, Source},
-    error::{Error, ParseError},
+    error::{Error, ParseError, ParseType, Span},
     parser::trim,
 };
+
+use crate::parse::{located_nom_err, remain_err};
 pub use template::*;
 
 /// # GenUI文件模型
@@ -62,6 +64,9 @@ pub struct Model {
     /// 也可以认为是模型的属性部分，在GenUI中并没有属性与样式的区别
     /// Style实际上是被平展的样式列表
     pub style: Option<Style>,
+    /// 样式中通过`@import`引入的文件(相对于源码目录), 例如: `components/theme.gen`
+    /// 通过[`Model::new`]创建时, 会包含间接引入的文件, 并且引入的样式已经合并到style中
+    pub style_imports: Vec<String>,
     /// 模型是否需要被编译
     /// 在项目中可能存在一个文件被编写，但没有在项目中使用到
     /// 表现为这个文件没有使用Rust的use语句进行引入
@@ -88,12 +93,53 @@ impl Model {
     pub fn new(source: Source, is_entry: bool) -> Result<Self, gen_utils::error::Error> {
         let content = fs::read(source.from_path())?;
         let mut model = Model::default();
-        model.parse(&content)?;
+        model.parse(&content).map_err(|mut e| {
+            if let Some(span) = e.span_mut() {
+                span.set_file(source.from_path());
+            }
+            e
+        })?;
+        model.import_styles(&source, &content)?;
         model.special = source;
         model.is_entry = is_entry;
         Ok(model)
     }
 
+    /// ## merge styles imported by `@import`
+    /// paths are relative to the source dir (such as `hello` of `hello/views/home.gen`),
+    /// imported rules are merged before the rules of the file, so the rules of the file win when the specificity is the same.
+    /// files imported by the imported files are merged too, each file is merged once (cycles are ignored)
+    fn import_styles(&mut self, source: &Source, content: &str) -> Result<(), Error> {
+        if self.style_imports.is_empty() {
+            return Ok(());
+        }
+        let mut components = source.from.components();
+        let dir = source
+            .path
+            .join(components.next().map_or("".as_ref(), |c| c.as_os_str()));
+        let mut imported = vec![fs::path_to_str(components.as_path())];
+        let mut styles = vec![];
+        let imports = std::mem::take(&mut self.style_imports);
+        import_styles(
+            &dir,
+            (source.from_path().as_path(), content),
+            &imports,
+            &mut imported,
+            &mut styles,
+        )?;
+
+        let mut merged = Style::new();
+        for style in styles.into_iter().chain(self.style.take()) {
+            for (k, v) in style {
+                merged.entry(k).or_default().extend(v);
+            }
+        }
+        self.style.replace(merged);
+        imported.remove(0);
+        self.style_imports = imported;
+        Ok(())
+    }
+
     pub fn is_empty(&self) -> bool {
         self.template.is_none() && self.script.is_none() && self.style.is_none()
     }
@@ -130,12 +176,13 @@ impl Model {
     //     }
     // }
 
-    /// parse gen file
-    /// try parse `<template>...</template>`, `<style>...</style>`, `<script>...</script>`
-    /// use nom take till
-    pub fn parse(&mut self, input: &str) -> Result<(), Error> {
-        fn parse_tag<'a>(name: &'a str) -> impl Fn(&'a str) -> IResult<&'a str, Option<String>> {
-            move |input: &str| {
+    /// ## split gen file into sections
+    /// try parse `<template>...</template>`, `<script>...</script>`, `<style>...</style>`
+    /// ### return
+    /// (template, style, script), each section is (offset of the content in input, content)
+    fn sections(input: &str) -> Result<(Section, Section, Section), Error> {
+        fn parse_tag<'a>(name: &'a str) -> impl Fn(&'a str) -> IResult<&'a str, Option<&'a str>> {
+            move |input: &'a str| {
                 let (input, _) = many0(Comment::parse)(input)?;
                 let (input, _) = trim(tag(format!("<{}>", name).as_str()))(input)?;
                 let (input, template_str) =
@@ -144,28 +191,42 @@ impl Model {
                 if template_str.is_empty() {
                     Ok((input, None))
                 } else {
-                    Ok((input, Some(template_str.to_string())))
+                    Ok((input, Some(template_str)))
                 }
             }
         }
+        let section = |s: Option<Option<&str>>| {
+            s.flatten()
+                .map(|s| (Span::offset_in(input, s), s.to_string()))
+        };
+        let origin_err = locate(0, input);
 
-        let (input, template) =
-            opt(parse_tag("template"))(input).map_err(|e| Error::from(e.to_string()))?;
-        let (input, script) =
-            opt(parse_tag("script"))(input).map_err(|e| Error::from(e.to_string()))?;
-        let (input, style) =
-            opt(parse_tag("style"))(input).map_err(|e| Error::from(e.to_string()))?;
-        if !input.trim().is_empty() {
-            return Err(
-                ParseError::template(&format!("parse error! Still remain: {}", input)).into(),
-            );
+        let (remain, template) = opt(parse_tag("template"))(input)
+            .map_err(|e| origin_err(located_nom_err(input, e, ParseType::Template)))?;
+        let (remain, script) = opt(parse_tag("script"))(remain)
+            .map_err(|e| origin_err(located_nom_err(input, e, ParseType::Script)))?;
+        let (remain, style) = opt(parse_tag("style"))(remain)
+            .map_err(|e| origin_err(located_nom_err(input, e, ParseType::Style)))?;
+        if !remain.trim().is_empty() {
+            return Err(origin_err(remain_err(input, remain, ParseType::Template)));
         }
 
-        let template = template.flatten();
-        let style = style.flatten();
-        let script = script.flatten();
+        Ok((section(template), section(style), section(script)))
+    }
+
+    /// parse gen file
+    /// try parse `<template>...</template>`, `<style>...</style>`, `<script>...</script>`
+    /// use nom take till
+    ///
+    /// all errors have span with line and column in the whole `input`
+    pub fn parse(&mut self, input: &str) -> Result<(), Error> {
+        let (template, style, script) = Self::sections(input)?;
+        let script = script.map(|(_, sc)| sc);
+        // section parser only know the offset in the section, shift it to the whole input
+        let locate = |base: usize| locate(base, input);
+
         match (template, style, script) {
-            (Some(t), Some(s), Some(sc)) => {
+            (Some((t_base, t)), Some((s_base, s)), Some(sc)) => {
                 self.strategy = Strategy::All;
                 let (template_sender, template_receiver) = mpsc::channel();
                 let (style_sender, style_receiver) = mpsc::channel();
@@ -177,7 +238,7 @@ impl Model {
                 });
 
                 let _ = thread::spawn(move || -> Result<(), Error> {
-                    let res = crate::parse::style::parse(&s);
+                    let res = crate::parse::style::parse_with_imports(&s);
                     style_sender.send(res).expect("send style error");
                     Ok(())
                 });
@@ -185,8 +246,10 @@ impl Model {
                 // wait for parse result
                 match (template_receiver.recv(), style_receiver.recv()) {
                     (Ok(template), Ok(style)) => {
-                        self.template.replace(template?);
-                        self.style.replace(style?);
+                        let mut template = template.map_err(locate(t_base))?;
+                        template.locate(t_base, input);
+                        self.template.replace(template);
+                        self.set_style(style.map_err(locate(s_base))?);
                         self.script.replace(sc);
                     }
                     (Ok(_), Err(e)) => {
@@ -200,7 +263,7 @@ impl Model {
                     }
                 }
             }
-            (Some(t), Some(s), None) => {
+            (Some((t_base, t)), Some((s_base, s)), None) => {
                 self.strategy = Strategy::TemplateStyle;
                 let poll = Arc::clone(&self.polls);
                 let (sender, receiver) = mpsc::channel();
@@ -212,14 +275,18 @@ impl Model {
                 let _ = receiver.recv().map_or_else(
                     |e| Err(Error::from(format!("receive template error: {}", e))),
                     |t| {
-                        self.template.replace(t?);
+                        let mut template = t.map_err(locate(t_base))?;
+                        template.locate(t_base, input);
+                        self.template.replace(template);
                         Ok(())
                     },
                 )?;
 
-                self.style.replace(crate::parse::style::parse(&s)?);
+                self.set_style(
+                    crate::parse::style::parse_with_imports(&s).map_err(locate(s_base))?,
+                );
             }
-            (Some(t), None, Some(sc)) => {
+            (Some((t_base, t)), None, Some(sc)) => {
                 self.strategy = Strategy::TemplateScript;
                 let poll = Arc::clone(&self.polls);
                 let (sender, receiver) = mpsc::channel();
@@ -231,20 +298,26 @@ impl Model {
                 let _ = receiver.recv().map_or_else(
                     |e| Err(Error::from(format!("receive template error: {}", e))),
                     |t| {
-                        self.template.replace(t?);
+                        let mut template = t.map_err(locate(t_base))?;
+                        template.locate(t_base, input);
+                        self.template.replace(template);
                         Ok(())
                     },
                 )?;
                 self.script.replace(sc);
             }
-            (Some(t), None, None) => {
+            (Some((t_base, t)), None, None) => {
                 self.strategy = Strategy::SingleTemplate;
-                self.template
-                    .replace(Template::parse(&t, Arc::clone(&self.polls))?);
+                let mut template =
+                    Template::parse(&t, Arc::clone(&self.polls)).map_err(locate(t_base))?;
+                template.locate(t_base, input);
+                self.template.replace(template);
             }
-            (None, Some(s), None) => {
+            (None, Some((s_base, s)), None) => {
                 self.strategy = Strategy::SingleStyle;
-                self.style.replace(crate::parse::style::parse(&s)?);
+                self.set_style(
+                    crate::parse::style::parse_with_imports(&s).map_err(locate(s_base))?,
+                );
             }
             (None, None, Some(sc)) => {
                 self.strategy = Strategy::SingleScript;
@@ -260,4 +333,222 @@ impl Model {
 
         Ok(())
     }
+
+    fn set_style(&mut self, (style, imports): (Style, Vec<String>)) {
+        self.style.replace(style);
+        self.style_imports = imports;
+    }
+
+    /// ## parse gen file with error recovery
+    /// unlike [`Model::parse`], it does not stop at the first error,
+    /// the parts which can be parsed are kept in the model and all errors are returned (with span in the whole `input`)
+    ///
+    /// use for editor tooling, such as language server
+    pub fn parse_recover(&mut self, input: &str) -> Vec<Error> {
+        let (template, style, script) = match Self::sections(input) {
+            Ok(sections) => sections,
+            Err(e) => return vec![e],
+        };
+        let mut errors = vec![];
+
+        self.strategy = match (template.is_some(), style.is_some(), script.is_some()) {
+            (true, true, true) => Strategy::All,
+            (true, true, false) => Strategy::TemplateStyle,
+            (true, false, true) => Strategy::TemplateScript,
+            (true, false, false) => Strategy::SingleTemplate,
+            (false, true, false) => Strategy::SingleStyle,
+            (false, false, true) => Strategy::SingleScript,
+            (false, false, false) => Strategy::None,
+            _ => {
+                let msg = "the parse strategy is invalid!";
+                errors.push(ParseError::template(msg).into());
+                Strategy::Error(msg.to_string())
+            }
+        };
+
+        if let Some((base, t)) = template {
+            let (template, template_errors) = Template::parse_recover(&t, Arc::clone(&self.polls));
+            errors.extend(template_errors.into_iter().map(locate(base, input)));
+            if let Some(mut template) = template {
+                template.locate(base, input);
+                self.template.replace(template);
+            }
+        }
+        if let Some((base, s)) = style {
+            match crate::parse::style::parse_with_imports(&s) {
+                Ok(style) => {
+                    self.set_style(style);
+                }
+                Err(e) => errors.push(locate(base, input)(e)),
+            }
+        }
+        if let Some((_, sc)) = script {
+            self.script.replace(sc);
+        }
+
+        errors
+    }
+}
+
+/// import styles depth first, styles of the imported files are pushed before the styles which import them
+/// - `dir`: source dir
+/// - `from`: (path, content) of the file which imports, used to locate the error
+/// - `imported`: files have been imported
+fn import_styles(
+    dir: &Path,
+    from: (&Path, &str),
+    imports: &[String],
+    imported: &mut Vec<String>,
+    styles: &mut Vec<Style>,
+) -> Result<(), Error> {
+    for import in imports {
+        if imported.contains(import) {
+            continue;
+        }
+        imported.push(import.to_string());
+        let path = dir.join(import);
+        if !path.is_file() {
+            let (file, content) = from;
+            let mut span = Span::new(content.find(&format!("\"{}\"", import)).unwrap_or_default());
+            span.resolve(content).set_file(file);
+            let mut err = ParseError::other(
+                &format!(
+                    "style import `{}` is not found in `{}`",
+                    import,
+                    dir.display()
+                ),
+                "DSL Style",
+            );
+            err.set_span(span);
+            return Err(err.into());
+        }
+        let content = fs::read(path.as_path())?;
+        let model = content.parse::<Model>().map_err(|mut e| {
+            if let Some(span) = e.span_mut() {
+                span.set_file(path.as_path());
+            }
+            e
+        })?;
+        import_styles(
+            dir,
+            (path.as_path(), &content),
+            &model.style_imports,
+            imported,
+            styles,
+        )?;
+        if let Some(style) = model.style {
+            styles.push(style);
+        }
+    }
+    Ok(())
+}
+
+/// section of gen file: (offset of the content in the whole file, content)
+type Section = Option<(usize, String)>;
+
+/// section parser only know the offset in the section, shift the span to the whole `source`
+fn locate(base: usize, source: &str) -> impl Fn(Error) -> Error + '_ {
+    move |mut e: Error| {
+        if let Some(span) = e.span_mut() {
+            span.shift(base).resolve(source);
+        }
+        e
+    }
+}
+
+#[cfg(test)]
+mod test_model {
+    use super::Model;
+
+    #[test]
+    fn template_error_span() {
+        let input = "<template>\n  <component name=\"Hello\">\n    <view>\n      <label text=\"'a'\" />\n    </view\n  </component>\n</template>\n";
+        let err = Model::default().parse(input).unwrap_err();
+        let span = err.span().expect("template error should have span");
+        assert!(span.is_resolved());
+        assert!(span.line > 1);
+    }
+
+    #[test]
+    fn style_error_kind() {
+        let input = "<template>\n  <component name=\"Hello\"></component>\n</template>\n\n<style>\n.card {\n    width: 40;\n}\n}\n</style>\n";
+        let err = Model::default().parse(input).unwrap_err();
+        assert!(err.to_string().starts_with("GenUI Parse DSL Style Error"));
+        assert_eq!(err.span().map(|span| span.line), Some(9));
+    }
+
+    #[test]
+    fn parse_recover() {
+        let input = "<template>\n  <component name=\"Hello\">\n    <label text=hello />\n    <view>\n  </component>\n</template>\n";
+        let mut model = Model::default();
+        let errors = model.parse_recover(input);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].span().map(|span| span.line), Some(3));
+        assert!(model.template.is_some());
+    }
+
+    #[test]
+    fn remain_error_span() {
+        let input = "<template>\n  <component name=\"Hello\"></component>\n</template>\n\nhello\n";
+        let err = Model::default().parse(input).unwrap_err();
+        let span = err.span().expect("remain error should have span");
+        assert_eq!((span.line, span.column), (5, 1));
+    }
+
+    #[test]
+    fn style_imports() {
+        use gen_utils::common::Source;
+
+        let root = std::env::temp_dir().join(format!("gen_style_imports_{}", std::process::id()));
+        let files = [
+            (
+                "hello/components/base.gen",
+                "<style>\n.btn {\n    height: 10;\n    width: 10;\n}\n</style>\n",
+            ),
+            (
+                "hello/components/theme.gen",
+                "<style>\n@import \"components/base.gen\";\n.btn {\n    width: 20;\n}\n.card {\n    width: 30;\n}\n</style>\n",
+            ),
+            (
+                "hello/views/home.gen",
+                "<style>\n@import \"components/theme.gen\";\n.card {\n    width: 40;\n}\n</style>\n",
+            ),
+            (
+                "hello/views/broken.gen",
+                "<style>\n.card {\n    width: 40;\n}\n@import \"components/unknown.gen\";\n</style>\n",
+            ),
+        ];
+        for (path, content) in files {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+        let source = |file: &str| {
+            Source::new(
+                root.as_path(),
+                root.join(file).as_path(),
+                root.join("src_gen/src/views/home.rs").as_path(),
+            )
+        };
+        let width = |model: &Model, key: &str| {
+            model.style.as_ref().unwrap()[key]
+                .iter()
+                .find(|(k, _)| k.name == "width")
+                .map(|(_, v)| v.to_string())
+        };
+
+        let model = Model::new(source("hello/views/home.gen"), false).unwrap();
+        assert_eq!(
+            model.style_imports,
+            vec!["components/theme.gen", "components/base.gen"]
+        );
+        assert_eq!(width(&model, ".btn"), Some("20".to_string()));
+        assert_eq!(width(&model, ".card"), Some("40".to_string()));
+
+        let err = Model::new(source("hello/views/broken.gen"), false).unwrap_err();
+        assert!(err.to_string().contains("components/unknown.gen"));
+        assert_eq!(err.span().map(|span| span.line), Some(5));
+
+        let _ = std::fs::remove_dir_all(root);
+    }
 }
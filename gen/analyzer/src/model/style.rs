@@ -1,22 +1,30 @@
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::Display,
+};
 
-use crate::{value::Value, PropKey};
-use gen_utils::{common::string::FixedString, err_from_to, error::Error};
+use crate::{parse::style::StyleCondition, value::Value, PropKey, Props};
+use gen_utils::{
+    common::{string::FixedString, tokenizer::STYLE_PESUDO},
+    err_from_to,
+    error::Error,
+};
+use indexmap::IndexMap;
 
 /// ### Style Value
 pub type StyleVal = HashMap<PropKey, Value>;
 /// ## Style
 /// in gen-ui no difference between style and props,
 /// so we use the same struct to represent them
-/// `<id|class, HashMap<prop, value>>`
-pub type Style = HashMap<String, StyleVal>;
+/// `<id|class, HashMap<prop, value>>`, rules are kept in source order, See [`StyleVisitor::visit`]
+pub type Style = IndexMap<String, StyleVal>;
 
 pub struct StyleVisitor;
 
 impl StyleVisitor {
-    /// 根据层级找出所有的样式
+    /// 根据层级找出所有的样式, 并按照优先级(specificity)层叠为一个属性表
     /// 例如:
-    /// ```
+    /// ```txt
     /// <view class="a">
     ///     <view class="b"></view>
     ///     <view id="d">
@@ -39,116 +47,287 @@ impl StyleVisitor {
     ///
     /// #c {
     ///  width: 200px;
+    ///  height: 300px;
     /// }
     /// ```
     /// 当访问id = c的时候，我们需要找出所有的样式，也就是`.a-#c`和`#c`的样式
-    /// 这个方法就是用来找出所有的样式的
-    /// 参数中styles存储了展开后的所有样式，例如:
-    /// ```
+    /// 参数中styles存储了展开后的所有样式(按照源码顺序)，例如:
+    /// ```txt
     /// k: .a        v: {height: 100px}
     /// k: .a-.b     v: {width: 100px}
     /// k: .a-.b-#c  v: {font-size: 100px}
     /// k: .a-#c     v: {height: 200px}
-    /// k: #c        v: {width: 200px}
-    ///
+    /// k: #c        v: {width: 200px, height: 300px}
+    /// ```
     /// 当id = c时, chain = [IdClass{id: None, class: Some("a")}, IdClass{id: Some("d"), class: None}]
     /// 在template结构中，c的父级是d，但是在style中是`.a#c`，d没有样式所以不写，所以我们需要找出所有包含c的样式
     /// 然后根据chain进行正确的过滤, 因为从存储的样式中单纯靠c会找到`#c`,`.a-#c`,`.a-.b-#c`，但是我们需要的是`.a-#c`和`#c`
-    /// ```
+    ///
+    /// 匹配到的样式按照[`Specificity`]从低到高合并, 相同时后定义的样式优先, 所以结果为:
+    /// `{width: 200px, height: 200px}` (`.a-#c`比`#c`多一个class, 所以`.a-#c`的height生效)
+    ///
+    /// 伪类状态(`.a-&::hover`)匹配`.a`, 层叠后放入[`Cascade::states`]而不是props
+    ///
+    /// 响应式规则(`@media(max_width:600.0) .a`)去掉条件后匹配, 按优先级顺序放入[`Cascade::conditions`]
     pub fn visit(
-        styles: &HashMap<String, HashMap<PropKey, Value>>,
+        styles: &Style,
         id: Option<&String>,
         class: Option<&Value>,
-        chain: &Vec<IdClass>,
-    ) -> Result<Vec<HashMap<PropKey, Value>>, Error> {
-        let mut res = vec![];
-        // [id] ------------------------------------------------------------------------------------
-        if let Some(id) = id {
-            //对所有包含id的styles进行过滤
-            for (k, c_styles) in styles.iter() {
-                let id = format!("#{}", id);
-                if matcher(k, &id, chain)? {
-                    res.push(c_styles.clone());
+        chain: &[IdClass],
+    ) -> Result<Cascade, Error> {
+        let node = IdClass {
+            id: id.cloned(),
+            class: class.cloned(),
+        };
+        let node = Segment::node(&node)?;
+        let ancestors = chain
+            .iter()
+            .map(Segment::node)
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let mut rules = styles
+            .iter()
+            .enumerate()
+            .filter_map(|(order, (k, v))| {
+                let (conditions, selector) = StyleCondition::split(k);
+                let (selector, state) = split_state(selector);
+                matcher(selector, &node, &ancestors)
+                    .then(|| (Specificity::of(selector), order, k, conditions, state, v))
+            })
+            .collect::<Vec<_>>();
+        rules.sort_by_key(|(specificity, order, ..)| (*specificity, *order));
+
+        let mut cascade = Cascade::default();
+        // prop -> (selector, specificity) of the rule which the value comes from
+        let mut from: HashMap<&PropKey, (&String, Specificity)> = HashMap::new();
+        for (specificity, _, selector, conditions, state, props) in rules {
+            if !conditions.is_empty() {
+                cascade.conditions.push((conditions, props.clone()));
+                continue;
+            }
+            if let Some(state) = state {
+                cascade
+                    .states
+                    .entry(state.to_string())
+                    .or_default()
+                    .extend(props.iter().map(|(k, v)| (k.clone(), v.clone())));
+                continue;
+            }
+            let mut props = props.iter().collect::<Vec<_>>();
+            props.sort_by(|(a, _), (b, _)| a.name.cmp(&b.name));
+            for (k, v) in props {
+                if let Some((before, before_specificity)) = from.insert(k, (selector, specificity))
+                {
+                    if before_specificity < specificity {
+                        cascade.overridden.push(Overridden {
+                            prop: k.name.to_string(),
+                            selector: before.to_string(),
+                            by: selector.to_string(),
+                        });
+                    }
                 }
+                cascade.props.insert(k.clone(), v.clone());
             }
         }
+        Ok(cascade)
+    }
+}
 
-        // [class] ------------------------------------------------------------------------------------
-        if let Some(class) = class {
-            let class = class_value_to_string(class)?;
-            for c_class in class {
-                //对所有包含class的styles进行过滤
-                for (k, c_styles) in styles.iter() {
-                    if matcher(k, &c_class, chain)? {
-                        res.push(c_styles.clone());
+impl StyleVisitor {
+    /// all class names (without `.`) in the style keys, in source order and without repeat
+    pub fn classes(styles: &Style) -> Vec<String> {
+        let mut classes: Vec<String> = vec![];
+        for key in styles.keys() {
+            let (_, selector) = StyleCondition::split(key);
+            let (selector, _) = split_state(selector);
+            for segment in selector.split_fixed("-") {
+                let Some(segment) = Segment::parse(&segment) else {
+                    continue;
+                };
+                for class in segment.classes {
+                    let class = class.trim_start_matches('.').to_string();
+                    if !classes.contains(&class) {
+                        classes.push(class);
                     }
                 }
             }
         }
+        classes
+    }
+}
 
-        Ok(res)
+/// split the pseudo state of style key, `.a-&::hover` => (`.a`, Some("hover"))
+fn split_state(key: &str) -> (&str, Option<&str>) {
+    match key.rsplit_once(STYLE_PESUDO) {
+        Some((selector, state)) => (selector.trim_end_matches('-'), Some(state)),
+        None => (key, None),
     }
 }
 
-/// 匹配名称是否相同
-/// 当end = c时, chain = [IdClass{id: None, class: Some("a")}, IdClass{id: Some("d"), class: None}]
-/// k: .a-.b
-/// k: .a-.b-#c
-/// k: .a-#c
-/// k: #c
-/// 匹配方式:
-/// 通过`-`进行分割，然后从后往前匹配，如果匹配成功则返回true，整个vec全匹配成功则返回true
-fn matcher(k: &str, end: &str, chain: &Vec<IdClass>) -> Result<bool, Error> {
-    // 分割
-    let k = k.split_fixed("-");
-    // dbg!(&k, end, chain);
-    // 准备一个flag作为匹配标志
-    let mut flag = 0;
-    let mut start = 0;
-    let len = k.len();
-    // 从后往前匹配
-    for k in k.iter().rev() {
-        if k == &end {
-            flag += 1;
-        } else {
-            // 这说明第一次匹配就失败了，直接break
-            if flag == 0 {
-                break;
+/// ## Specificity of style rule
+/// `(id, class, depth)`, compared in order:
+/// id beats class, class beats nesting depth, such as `#a` > `.a.b` > `.b`, `.a-.b` > `.a.b`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Specificity {
+    /// count of id selectors
+    pub id: usize,
+    /// count of class selectors
+    pub class: usize,
+    /// nesting depth
+    pub depth: usize,
+}
+
+impl Specificity {
+    /// specificity of the flattened style key, such as `.a-.b.active-#c`
+    pub fn of(key: &str) -> Self {
+        let mut specificity = Specificity::default();
+        for segment in key.split_fixed("-") {
+            specificity.depth += 1;
+            specificity.id += segment.matches('#').count();
+            specificity.class += segment.matches('.').count();
+        }
+        specificity
+    }
+}
+
+/// # Cascaded Style
+/// result of [`StyleVisitor::visit`]
+#[derive(Debug, Clone, Default)]
+pub struct Cascade {
+    /// props of all matched rules after cascading
+    pub props: StyleVal,
+    /// props which are overridden by more specific rules
+    pub overridden: Vec<Overridden>,
+    /// props of pseudo states, such as `hover` => props in `&::hover`,
+    /// they are not merged into props, how to use them depends on the target widget
+    pub states: BTreeMap<String, StyleVal>,
+    /// props of responsive rules (`@media`, `@platform`) from low to high specificity,
+    /// they are applied at runtime when all conditions match, See [`StyleCondition`]
+    pub conditions: Vec<(Vec<StyleCondition>, StyleVal)>,
+}
+
+impl Cascade {
+    /// ## merge into props of template
+    /// props in template (inline) always win, no matter normal, bind or function
+    pub fn merge(self, props: &mut Option<Props>) {
+        if self.props.is_empty() {
+            return;
+        }
+        let props = props.get_or_insert_with(HashMap::new);
+        let inline = props
+            .keys()
+            .map(|k| k.name.to_string())
+            .collect::<HashSet<_>>();
+        props.extend(
+            self.props
+                .into_iter()
+                .filter(|(k, _)| !inline.contains(&k.name)),
+        );
+    }
+}
+
+/// ## prop overridden by a more specific rule
+#[derive(Debug, Clone, PartialEq)]
+pub struct Overridden {
+    pub prop: String,
+    /// selector of the overridden rule
+    pub selector: String,
+    /// selector of the more specific rule
+    pub by: String,
+}
+
+impl Display for Overridden {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "`{}` in `{}` is overridden by `{}`",
+            self.prop, self.selector, self.by
+        )
+    }
+}
+
+/// id and classes of a template node, or the parts of a selector segment (`.a.active` => classes: [a, active])
+#[derive(Debug, Default)]
+struct Segment {
+    id: Option<String>,
+    classes: Vec<String>,
+}
+
+impl Segment {
+    fn node(node: &IdClass) -> Result<Self, Error> {
+        Ok(Segment {
+            id: node.fmt_id(),
+            classes: node.fmt_class().transpose()?.unwrap_or_default(),
+        })
+    }
+    /// parse the segment of style key, None if the segment can not match any node (such as `&::hover`)
+    fn parse(segment: &str) -> Option<Self> {
+        let mut res = Segment::default();
+        let mut rest = segment;
+        while let Some(sign) = rest.chars().next() {
+            if sign != '.' && sign != '#' {
+                return None;
             }
-            let is_id = k.starts_with("#");
-            let mut c_flag = false;
-            // 说明当前k不是end，现在需要匹配chain
-            // 用k到chain中查找是否存在，不存在直接break, 存在则将索引记录到start中，然后flag+1，start作为下次匹配的起始位置
-            for (i, id_class) in chain.iter().rev().enumerate().skip(start) {
-                if is_id {
-                    if let Some(c_id) = id_class.fmt_id() {
-                        if &c_id == k {
-                            c_flag = true;
-                            start = i;
-                        }
-                    }
-                    continue;
-                } else {
-                    if let Some(c_class) = id_class.fmt_class() {
-                        let c_class = c_class?;
-                        if c_class.contains(&k.to_string()) {
-                            c_flag = true;
-                            start = i;
-                        }
-                    }
-                    continue;
-                }
+            let end = rest[1..].find(['.', '#']).map_or(rest.len(), |i| i + 1);
+            let (part, remain) = rest.split_at(end);
+            if part.len() < 2 {
+                return None;
             }
-
-            if c_flag {
-                flag += 1;
+            if sign == '#' {
+                res.id = Some(part.to_string());
             } else {
-                break;
+                res.classes.push(part.to_string());
             }
+            rest = remain;
         }
+        Some(res)
     }
+    /// all parts of the selector segment are on the node
+    fn is_match(&self, node: &Segment) -> bool {
+        (self.id.is_some() || !self.classes.is_empty())
+            && self
+                .id
+                .as_ref()
+                .is_none_or(|id| node.id.as_ref() == Some(id))
+            && self
+                .classes
+                .iter()
+                .all(|class| node.classes.contains(class))
+    }
+}
 
-    Ok(flag == len)
+/// 匹配样式是否作用于节点
+/// 当节点id = c时, chain = [IdClass{id: None, class: Some("a")}, IdClass{id: Some("d"), class: None}]
+/// k: .a-.b
+/// k: .a-.b-#c
+/// k: .a-#c
+/// k: #c
+/// 匹配方式:
+/// 通过`-`进行分割，最后一段需要匹配节点本身，其余各段从后往前依次匹配最近的父级(不要求是直接父级)，全部匹配成功则返回true
+fn matcher(k: &str, node: &Segment, chain: &[Segment]) -> bool {
+    let mut segments = k.split_fixed("-").into_iter().rev();
+    let is_self = segments
+        .next()
+        .and_then(|segment| Segment::parse(&segment))
+        .is_some_and(|segment| segment.is_match(node));
+    if !is_self {
+        return false;
+    }
+    // 父级匹配的位置, 下一段需要从这个位置往上查找
+    let mut end = chain.len();
+    for segment in segments {
+        let Some(segment) = Segment::parse(&segment) else {
+            return false;
+        };
+        match chain[..end]
+            .iter()
+            .rposition(|ancestor| segment.is_match(ancestor))
+        {
+            Some(index) => end = index,
+            None => return false,
+        }
+    }
+    true
 }
 
 /// 存储历史父级的id或class的节点
@@ -209,3 +388,175 @@ pub fn class_value_to_string(class: &Value) -> Result<Vec<String>, Error> {
         _ => Err(err_from_to!("Value" => "String")),
     }
 }
+
+#[cfg(test)]
+mod test_style {
+    use crate::{parse::style::parse, value::Value, PropKey, PropKeyType};
+
+    use super::{IdClass, Specificity, StyleVisitor};
+
+    const STYLE: &str = r#"
+.a {
+    height: 100;
+    .b {
+        width: 100;
+    }
+    #c {
+        height: 200;
+    }
+}
+#c {
+    width: 200;
+    height: 300;
+}
+.box {
+    color: red;
+    &.active {
+        color: blue;
+    }
+}
+.theme {
+    color: green;
+}
+"#;
+
+    fn class(s: &str) -> Value {
+        Value::parse_template(s).unwrap()
+    }
+
+    fn get(props: &super::StyleVal, name: &str) -> Option<Value> {
+        props
+            .iter()
+            .find(|(k, _)| k.name == name)
+            .map(|(_, v)| v.clone())
+    }
+
+    #[test]
+    fn specificity() {
+        assert!(Specificity::of("#c") > Specificity::of(".a-.b.active"));
+        assert!(Specificity::of(".a.active") > Specificity::of(".b"));
+        assert!(Specificity::of(".a-.b") > Specificity::of(".a.b"));
+    }
+
+    #[test]
+    fn cascade() {
+        let styles = parse(STYLE).unwrap();
+        let chain = vec![
+            IdClass {
+                id: None,
+                class: Some(class("a")),
+            },
+            IdClass {
+                id: Some("d".to_string()),
+                class: None,
+            },
+        ];
+        assert_eq!(
+            StyleVisitor::classes(&styles),
+            vec!["a", "b", "box", "active", "theme"]
+        );
+        let id = "c".to_string();
+        let cascade = StyleVisitor::visit(&styles, Some(&id), None, &chain).unwrap();
+        assert_eq!(get(&cascade.props, "width"), Some(Value::ISize(200)));
+        assert_eq!(get(&cascade.props, "height"), Some(Value::ISize(200)));
+        assert_eq!(cascade.overridden.len(), 1);
+        assert_eq!(
+            cascade.overridden[0].to_string(),
+            "`height` in `#c` is overridden by `.a-#c`"
+        );
+
+        // compound class beats single class, same specificity => source order
+        let cascade =
+            StyleVisitor::visit(&styles, None, Some(&class("[theme, box, active]")), &[]).unwrap();
+        assert_eq!(
+            get(&cascade.props, "color"),
+            Value::parse_style("blue").ok()
+        );
+        let cascade =
+            StyleVisitor::visit(&styles, None, Some(&class("[theme, box]")), &[]).unwrap();
+        assert_eq!(
+            get(&cascade.props, "color"),
+            Value::parse_style("green").ok()
+        );
+        assert!(cascade.overridden.is_empty());
+
+        // inline props win
+        let mut props = Some(
+            [(
+                PropKey::new("color", false, PropKeyType::Normal),
+                class("yellow"),
+            )]
+            .into_iter()
+            .collect(),
+        );
+        cascade.merge(&mut props);
+        let props = props.unwrap();
+        assert_eq!(props.len(), 1);
+        assert_eq!(get(&props, "color"), Some(class("yellow")));
+    }
+
+    #[test]
+    fn states() {
+        let styles = parse(
+            r#"
+.btn {
+    color: red;
+    &::hover {
+        color: blue;
+    }
+}
+#ok {
+    &::hover {
+        color: green;
+    }
+    &::pressed {
+        color: black;
+    }
+}
+"#,
+        )
+        .unwrap();
+        let id = "ok".to_string();
+        let cascade = StyleVisitor::visit(&styles, Some(&id), Some(&class("btn")), &[]).unwrap();
+        assert_eq!(get(&cascade.props, "color"), Value::parse_style("red").ok());
+        assert_eq!(cascade.states.len(), 2);
+        // more specific state wins
+        assert_eq!(
+            get(&cascade.states["hover"], "color"),
+            Value::parse_style("green").ok()
+        );
+        let cascade = StyleVisitor::visit(&styles, None, Some(&class("btn")), &[]).unwrap();
+        assert_eq!(cascade.states.keys().collect::<Vec<_>>(), vec!["hover"]);
+    }
+
+    #[test]
+    fn conditions() {
+        let styles = parse(
+            r#"
+#ok {
+    @media (max_width: 600) {
+        color: green;
+    }
+}
+.btn {
+    color: red;
+    @platform(web) {
+        color: blue;
+    }
+}
+"#,
+        )
+        .unwrap();
+        let id = "ok".to_string();
+        let cascade = StyleVisitor::visit(&styles, Some(&id), Some(&class("btn")), &[]).unwrap();
+        // conditional props are not cascaded into props
+        assert_eq!(get(&cascade.props, "color"), Value::parse_style("red").ok());
+        assert_eq!(cascade.conditions.len(), 2);
+        // from low to high specificity
+        assert_eq!(cascade.conditions[0].0[0].to_string(), "@platform(web)");
+        assert_eq!(
+            get(&cascade.conditions[1].1, "color"),
+            Value::parse_style("green").ok()
+        );
+    }
+}
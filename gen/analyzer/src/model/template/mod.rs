@@ -12,10 +12,14 @@ use std::{
 use gen_utils::{
     common::Ulid,
     err_from, err_from_to,
-    error::{Error, ParseError},
+    error::{Error, ParseError, Span},
 };
 
-use crate::{template, value::Value, PropComponent};
+use crate::{
+    template,
+    value::{Interpolation, Value},
+    PropComponent,
+};
 
 use super::{EventComponent, Polls, Prop, PropKV};
 
@@ -54,6 +58,20 @@ pub struct Template {
     /// 例如：`<view id="hello" as_prop="slot" />`
     /// 表明这个组件是一个属性插槽，插槽的名字是slot
     pub as_prop: Option<String>,
+    /// 组件放入父组件(自定义组件)的哪个插槽, 没有设置时放入默认插槽
+    /// 例如：`<my_card><view slot="header" /></my_card>`
+    /// 自定义组件中使用`<slot name="header" />`声明插槽
+    pub slot: Option<String>,
+    /// 作用域插槽的数据在调用方的名字, 插槽的数据通过这个名字在绑定中使用
+    /// 例如：`<my_card><label slot="header" slot_scope="scope" :text="scope.title" /></my_card>`
+    /// 自定义组件中使用`<slot name="header" :title="title" />`声明插槽的数据
+    pub slot_scope: Option<String>,
+    /// `:for`循环中组件的key, 值改变时按key复用、移动、插入和删除子组件而不是全部重新创建
+    /// 例如：`<view :for="item in list" :key="item.id" />`
+    pub key: Option<Value>,
+    /// `:for`循环的虚拟列表, 只创建视口中可见的子组件(以及overscan), 滚动时回收复用
+    /// 例如：`<view :for="row in rows" virtual="48" overscan="4" />`, See [`VirtualFor`]
+    pub virtual_for: Option<VirtualFor>,
     /// 组件的名字，这个名字标识了组件应该如何在.gen文件中书写
     /// 例如，如果组件名字是`button`，那么在.gen文件中书写`<button></button>`就是正确的
     pub name: String,
@@ -91,9 +109,13 @@ pub struct Template {
     pub root: bool,
     /// 注释
     pub comments: Option<Vec<Comment>>,
+    /// 组件标签在源文件中的位置(标签开始`<`的位置)
+    pub span: Option<Span>,
 }
 
 impl Template {
+    /// prop which is set by the text child, See [`Template::push_text`]
+    pub const TEXT: &'static str = "text";
     pub fn new(name: &str) -> Self {
         let mut template = Self::default();
         template.name = name.to_string();
@@ -105,6 +127,12 @@ impl Template {
         template::parse(input, poll, true)
     }
 
+    /// 以容错模式解析模版, 不会在第一个错误处停止, 而是收集所有错误并返回尽可能完整的模版
+    /// See: [`template::parse_recover()`]
+    pub fn parse_recover(input: &str, poll: Arc<RwLock<Polls>>) -> (Option<Self>, Vec<Error>) {
+        template::parse_recover(input, poll, true)
+    }
+
     /// ## after parse
     /// 在 template::parse(input) 内部调用在，在所有属性被分析完成后调用这个方法
     /// See: [`self.push_prop()`]
@@ -117,13 +145,28 @@ impl Template {
         // [获取Tag被设置的属性作为Template传入的属性]------------------------------------------
         // 其中id、class会被单独提出来，其他的属性会被放入props中（for,if,inherits等也一样）
         // 在进行属性处理的时候同时获取出池化属性
-        if let Some(props) = self.props.take() {
+        if let Some(mut props) = self.props.take() {
+            // `overscan` is the option of `virtual`, it is set after all props are parsed
+            let overscan = props.remove(&PropKey::new_tag_normal(VirtualFor::OVERSCAN));
             for (k, v) in props {
                 let sugar_iter = self.push_prop(k, v, iter)?;
                 if sugar_iter.is_some() {
                     back_iter = sugar_iter;
                 }
             }
+            if let Some(overscan) = overscan {
+                let virtual_for = self
+                    .virtual_for
+                    .as_mut()
+                    .ok_or_else(|| ParseError::template("overscan must be used with `virtual`"))?;
+                virtual_for.overscan = VirtualFor::parse_overscan(&overscan)?;
+            }
+        }
+        if self.key.is_some() && !matches!(self.sugar_props, SugarProps::For(_)) {
+            return Err(ParseError::template("key must be used with `:for`").into());
+        }
+        if self.virtual_for.is_some() && !matches!(self.sugar_props, SugarProps::For(_)) {
+            return Err(ParseError::template("virtual must be used with `:for`").into());
         }
 
         // 检查组件是否有id, 如果没有id则将special作为id
@@ -147,16 +190,24 @@ impl Template {
             let (name, id) = self.get_name_and_id()?;
             // 延迟处理binds
             for (key, value) in binds {
-                poll.insert_prop(
-                    &value.as_bind()?.ident(),
-                    PropComponent {
-                        id: id.clone().into_owned(),
-                        name: name.clone().into_owned(),
-                        prop: Prop::Value(PropKV::new(key.name.to_string(), value.clone())),
-                        as_prop: self.as_prop.clone(),
-                        father_ref: self.parent.clone(),
-                    },
-                );
+                // class map and style map bind are pooled for each root ident
+                // interpolation is pooled for each root ident, the whole string is rebuilt in the setter
+                let idents = match value {
+                    Value::Interpolation(interp) => interp.idents(),
+                    _ => value.as_bind()?.idents(),
+                };
+                for ident in idents {
+                    poll.insert_prop(
+                        &ident,
+                        PropComponent {
+                            id: id.clone().into_owned(),
+                            name: name.clone().into_owned(),
+                            prop: Prop::Value(PropKV::new(key.name.to_string(), value.clone())),
+                            as_prop: self.as_prop.clone(),
+                            father_ref: self.parent.clone(),
+                        },
+                    );
+                }
             }
         }
 
@@ -179,6 +230,20 @@ impl Template {
         Ok(())
     }
 
+    /// ## locate spans in the whole source
+    /// template is parsed from the content of `<template>`, so the span offset need to add the offset of the content (`base`)
+    /// then resolve line and column from the whole `source`
+    pub fn locate(&mut self, base: usize, source: &str) {
+        if let Some(span) = self.span.as_mut() {
+            span.shift(base).resolve(source);
+        }
+        if let Some(children) = self.children.as_mut() {
+            for child in children {
+                child.locate(base, source);
+            }
+        }
+    }
+
     /// judge the root template tag is `<component>` or not
     pub fn is_static(&self) -> bool {
         self.callbacks.is_none() && self.binds.is_none()
@@ -226,6 +291,46 @@ impl Template {
                         );
                     }
                 }
+                BuiltinProps::Slot => {
+                    if key.is_normal() {
+                        self.slot.replace(value.to_string());
+                    } else {
+                        return Err(ParseError::template("slot must be a normal property").into());
+                    }
+                }
+                BuiltinProps::SlotScope => {
+                    if key.is_normal() {
+                        self.slot_scope.replace(value.to_string());
+                    } else {
+                        return Err(
+                            ParseError::template("slot_scope must be a normal property").into()
+                        );
+                    }
+                }
+                BuiltinProps::Key => {
+                    // key is the bind of loop item or index, such as `:key="item.id"`
+                    if key.is_bind() && value.as_bind()?.get_normal().is_some() {
+                        self.key.replace(value);
+                    } else {
+                        return Err(ParseError::template(
+                            "key must be a bind property, such as `:key=\"item.id\"`",
+                        )
+                        .into());
+                    }
+                }
+                BuiltinProps::Virtual => {
+                    if key.is_normal() {
+                        self.virtual_for.replace(VirtualFor::parse(&value)?);
+                    } else {
+                        return Err(
+                            ParseError::template("virtual must be a normal property").into()
+                        );
+                    }
+                }
+                BuiltinProps::Overscan => {
+                    // normal `overscan` is taken in `after_prop_parse`
+                    return Err(ParseError::template("overscan must be a normal property").into());
+                }
                 BuiltinProps::Id => {
                     if key.is_normal() {
                         self.id.replace(value.to_string());
@@ -234,10 +339,25 @@ impl Template {
                     }
                 }
                 BuiltinProps::Class => {
+                    // `:class` is a bind of field or class map, it is set at runtime, See [`crate::value::ClassItem`]
                     if key.is_normal() {
                         self.class.replace(value.clone());
+                    } else if key.is_bind() {
+                        Self::insert_prop(&mut self.binds, key, value);
                     } else {
-                        return Err(ParseError::template("class must be a normal property").into());
+                        return Err(ParseError::template(
+                            "class must be a normal or bind property",
+                        )
+                        .into());
+                    }
+                }
+                BuiltinProps::Style => {
+                    // `:style` is a bind of field or style map, static styles should be in `<style>`, See [`crate::value::StyleItem`]
+                    if key.is_bind() {
+                        let value = Value::Bind(value.as_bind()?.into_style()?);
+                        Self::insert_prop(&mut self.binds, key, value);
+                    } else {
+                        return Err(ParseError::template("style must be a bind property").into());
                     }
                 }
                 BuiltinProps::Inherits => {
@@ -355,7 +475,13 @@ impl Template {
             // [other props]---------------------------------------------------------------------------
             match key.ty {
                 PropKeyType::Normal => {
-                    Self::insert_prop(&mut self.props, key, value);
+                    if let Value::Interpolation(_) = value {
+                        // `text="'Hello {{ name }}'"` is rebuilt when `name` changes, so it is a bind
+                        let key = PropKey::new_bind(&key.name, key.is_style);
+                        Self::insert_prop(&mut self.binds, key, value);
+                    } else {
+                        Self::insert_prop(&mut self.props, key, value);
+                    }
                 }
                 PropKeyType::Bind => {
                     Self::insert_prop(&mut self.binds, key, value);
@@ -368,10 +494,45 @@ impl Template {
 
         Ok(back_iter)
     }
+    /// ## push text child as `text` prop
+    /// `<label>Hello {{ name }}</label>` equals to `<label text="'Hello {{ name }}'"></label>`,
+    /// text can not be mixed with child tags and can not be used with `text` prop at the same time
+    pub fn push_text(&mut self, text: &str) -> Result<(), Error> {
+        if self.children.is_some() {
+            return Err(ParseError::template(&format!(
+                "unexpected content `{}`, text can not be mixed with tags",
+                text
+            ))
+            .into());
+        }
+        let has_text = |props: &Option<Props>| {
+            props
+                .as_ref()
+                .is_some_and(|props| props.keys().any(|k| k.name == Self::TEXT))
+        };
+        if has_text(&self.props) || has_text(&self.binds) {
+            return Err(ParseError::template(&format!(
+                "<{}> has `text` prop, it can not have text child `{}` at the same time",
+                self.name, text
+            ))
+            .into());
+        }
+        let value = if Interpolation::is(text) {
+            Value::Interpolation(Interpolation::parse(text)?)
+        } else {
+            Value::String(text.to_string())
+        };
+        let _ = self.push_prop(PropKey::new_tag_normal(Self::TEXT), value, None)?;
+        Ok(())
+    }
 
     pub fn is_component(&self) -> bool {
         self.name.eq("component")
     }
+    /// judge the tag is `<slot>` (placeholder of slot in custom component) or not
+    pub fn is_slot(&self) -> bool {
+        self.name.eq("slot")
+    }
 
     pub fn set_parent(&mut self, id: String, name: String, root: bool) -> () {
         let _ = self.parent.replace((id, name, root).into());
@@ -444,9 +605,14 @@ impl Default for Template {
             children: Default::default(),
             parent: Default::default(),
             as_prop: None,
+            slot: None,
+            slot_scope: None,
+            key: None,
+            virtual_for: None,
             sugar_props: SugarProps::default(),
             comments: None,
             root: false,
+            span: None,
         }
     }
 }
@@ -473,64 +639,73 @@ impl SugarProps {
                     for_sign.clone(),
                 )),
             )])),
-            SugarProps::If(sugar_if) => match sugar_if {
-                SugarIf::If(sugar_if) => Ok(Some(vec![(
-                    sugar_if.expr.as_bind()?.ident(),
-                    Prop::Value(PropKV::new(
-                        If::SUGAR_SIGN.to_string(),
-                        sugar_if.expr.clone(),
-                    )),
-                )])),
-                SugarIf::ElseIf(sugar_else_if) => {
-                    let res = vec![(
-                        sugar_else_if.expr.as_bind()?.ident(),
+            SugarProps::If(sugar_if) => {
+                // expression bind is pooled for each ident, See [`crate::value::Bind::idents`]
+                let pooled = |expr: &Value, prop: Prop| -> Result<Vec<(String, Prop)>, Error> {
+                    Ok(expr
+                        .as_bind()?
+                        .idents()
+                        .into_iter()
+                        .map(|ident| (ident, prop.clone()))
+                        .collect())
+                };
+                match sugar_if {
+                    SugarIf::If(sugar_if) => Ok(Some(pooled(
+                        &sugar_if.expr,
                         Prop::Value(PropKV::new(
-                            ElseIf::SUGAR_SIGN.to_string(),
-                            sugar_else_if.expr.clone(),
+                            If::SUGAR_SIGN.to_string(),
+                            sugar_if.expr.clone(),
                         )),
-                    )];
-
-                    // for expr in sugar_else_if.else_if_exprs.iter() {
-                    //     res.push((
-                    //         expr.as_bind()?.ident(),
-                    //         Prop::Value(PropKV::new(ElseIf::SUGAR_SIGN.to_string(), expr.clone())),
-                    //     ));
-                    // }
-
-                    Ok(Some(res))
-                }
-                SugarIf::Else(sugar_else) => {
-                    // 判断Else中elseif是否有，如果有则获取最后一个elseif并附加到对应prop中，否则到if中
-                    let res = if sugar_else.else_if_exprs.is_empty() {
-                        let if_expr = sugar_else.if_expr.expr.as_bind()?.ident();
-                        vec![(
-                            if_expr.to_string(),
-                            Prop::Else(vec![PropKV::new(
+                    )?)),
+                    SugarIf::ElseIf(sugar_else_if) => {
+                        let res = pooled(
+                            &sugar_else_if.expr,
+                            Prop::Value(PropKV::new(
+                                ElseIf::SUGAR_SIGN.to_string(),
+                                sugar_else_if.expr.clone(),
+                            )),
+                        )?;
+
+                        // for expr in sugar_else_if.else_if_exprs.iter() {
+                        //     res.push((
+                        //         expr.as_bind()?.ident(),
+                        //         Prop::Value(PropKV::new(ElseIf::SUGAR_SIGN.to_string(), expr.clone())),
+                        //     ));
+                        // }
+
+                        Ok(Some(res))
+                    }
+                    SugarIf::Else(sugar_else) => {
+                        // 判断Else中elseif是否有，如果有则获取最后一个elseif并附加到对应prop中，否则到if中
+                        let res = if sugar_else.else_if_exprs.is_empty() {
+                            pooled(
+                                &sugar_else.if_expr.expr,
+                                Prop::Else(vec![PropKV::new(
+                                    Else::SUGAR_SIGN.to_string(),
+                                    sugar_else.if_expr.expr.clone(),
+                                )]),
+                            )?
+                        } else {
+                            // 如果有则获取最后一个，但Prop::Else是将所有的prop ident放入
+                            let last_else_if = sugar_else.else_if_exprs.last().unwrap();
+                            let mut props = vec![];
+                            // 添加if
+                            props.push(PropKV::new(
                                 Else::SUGAR_SIGN.to_string(),
                                 sugar_else.if_expr.expr.clone(),
-                            )]),
-                        )]
-                    } else {
-                        // 如果有则获取最后一个，但Prop::Else是将所有的prop ident放入
-                        let last_else_if =
-                            sugar_else.else_if_exprs.last().unwrap().as_bind()?.ident();
-                        let mut props = vec![];
-                        // 添加if
-                        props.push(PropKV::new(
-                            Else::SUGAR_SIGN.to_string(),
-                            sugar_else.if_expr.expr.clone(),
-                        ));
-                        // 添加else if
-                        for expr in sugar_else.else_if_exprs.iter() {
-                            props.push(PropKV::new(Else::SUGAR_SIGN.to_string(), expr.clone()));
-                        }
+                            ));
+                            // 添加else if
+                            for expr in sugar_else.else_if_exprs.iter() {
+                                props.push(PropKV::new(Else::SUGAR_SIGN.to_string(), expr.clone()));
+                            }
 
-                        vec![(last_else_if, Prop::Else(props))]
-                    };
+                            pooled(last_else_if, Prop::Else(props))?
+                        };
 
-                    Ok(Some(res))
+                        Ok(Some(res))
+                    }
                 }
-            },
+            }
             SugarProps::None => Ok(None),
         }
     }
@@ -655,6 +830,59 @@ pub struct Else {
 impl Else {
     pub const SUGAR_SIGN: &'static str = "else_sugar_sign";
 }
+
+/// # Virtual For
+/// modifier of `:for`, only the items in the viewport and `overscan` items on each side are instantiated,
+/// the widgets are recycled while scrolling
+/// - `virtual`: the height of items is measured after they are drawn
+/// - `virtual="48"`: all items have the fixed height
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VirtualFor {
+    /// fixed height of items, `None` means the items are measured
+    pub height: Option<f64>,
+    /// count of items instantiated out of the viewport on each side
+    pub overscan: usize,
+}
+
+impl VirtualFor {
+    pub const OVERSCAN: &'static str = "overscan";
+    pub const DEFAULT_OVERSCAN: usize = 2;
+
+    pub fn parse(value: &Value) -> Result<Self, Error> {
+        let height = match value {
+            Value::Bool(true) => None,
+            value => {
+                let value = value.to_string();
+                match value.parse::<f64>() {
+                    Ok(height) if height > 0.0 => Some(height),
+                    _ if value == "auto" => None,
+                    _ => {
+                        return Err(ParseError::template(&format!(
+                            "virtual should be `auto` or a fixed height of items, but found `{}`",
+                            value
+                        ))
+                        .into())
+                    }
+                }
+            }
+        };
+        Ok(Self {
+            height,
+            overscan: Self::DEFAULT_OVERSCAN,
+        })
+    }
+
+    fn parse_overscan(value: &Value) -> Result<usize, Error> {
+        let value = value.to_string();
+        value.parse::<usize>().map_err(|_| {
+            ParseError::template(&format!(
+                "overscan should be a count of items, but found `{}`",
+                value
+            ))
+            .into()
+        })
+    }
+}
 #[derive(Debug, Clone)]
 pub struct Parent {
     pub id: String,
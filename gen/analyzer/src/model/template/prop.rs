@@ -14,9 +14,16 @@ use crate::value::{Bind, Function, Value};
 /// |else    | GenUI Else Prop Key      | `else`                         |
 /// |as_prop | GenUI As Prop Key        | `as_prop="true"` or `as_prop`  |
 /// |id      | GenUI Id Prop Key        | `id="id"`                      |
-/// |class   | GenUI Class Prop Key     | `class="class1 class2"`        |
-pub const BUILTIN_PROPS: [&str; 8] = [
-    "for", "if", "else_if", "else", "as_prop", "id", "class", "inherits",
+/// |class   | GenUI Class Prop Key     | `class="class1 class2"` or `:class="{ active: is_active }"` |
+/// |style   | GenUI Style Bind Key     | `:style="{ color: text_color }"` or `:style="style_field"` |
+/// |slot    | GenUI Slot Prop Key      | `slot="header"`                |
+/// |slot_scope | GenUI Slot Scope Key  | `slot_scope="scope"` (data of scoped slot) |
+/// |key     | GenUI Loop Key Prop Key  | `:key="item.id"` (with `:for`) |
+/// |virtual | GenUI Virtual List Key   | `virtual` or `virtual="48"` (with `:for`) |
+/// |overscan| GenUI Virtual List Key   | `overscan="4"` (with `virtual`) |
+pub const BUILTIN_PROPS: [&str; 14] = [
+    "for", "if", "else_if", "else", "as_prop", "id", "class", "style", "slot", "slot_scope", "key",
+    "virtual", "overscan", "inherits",
 ];
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -28,6 +35,12 @@ pub enum BuiltinProps {
     AsProp,
     Id,
     Class,
+    Style,
+    Slot,
+    SlotScope,
+    Key,
+    Virtual,
+    Overscan,
     Inherits,
 }
 
@@ -43,12 +56,54 @@ impl FromStr for BuiltinProps {
             "as_prop" => Ok(BuiltinProps::AsProp),
             "id" => Ok(BuiltinProps::Id),
             "class" => Ok(BuiltinProps::Class),
+            "style" => Ok(BuiltinProps::Style),
+            "slot" => Ok(BuiltinProps::Slot),
+            "slot_scope" => Ok(BuiltinProps::SlotScope),
+            "key" => Ok(BuiltinProps::Key),
+            "virtual" => Ok(BuiltinProps::Virtual),
+            "overscan" => Ok(BuiltinProps::Overscan),
             "inherits" => Ok(BuiltinProps::Inherits),
             _ => Err(Error::from(format!("Invalid builtin props: {}", s))),
         }
     }
 }
 
+impl BuiltinProps {
+    /// ## the prop is written as bind (`:xxx`) or not, See [`BUILTIN_PROPS`]
+    /// `class` and `style` can be both, bind is used to toggle classes and set styles
+    pub fn is_bind(&self) -> bool {
+        matches!(
+            self,
+            BuiltinProps::For
+                | BuiltinProps::If
+                | BuiltinProps::ElseIf
+                | BuiltinProps::Else
+                | BuiltinProps::Class
+                | BuiltinProps::Style
+                | BuiltinProps::Key
+        )
+    }
+    /// ## description and format of the prop, See [`BUILTIN_PROPS`]
+    pub fn doc(&self) -> &'static str {
+        match self {
+            BuiltinProps::For => "loop sugar, such as `:for=\"(index, item) in list\"`",
+            BuiltinProps::If => "render the widget if the bind value is true",
+            BuiltinProps::ElseIf => "should be after `:if` or `:else_if`",
+            BuiltinProps::Else => "should be after `:if` or `:else_if`, no value",
+            BuiltinProps::AsProp => "use the widget as a prop slot of the parent",
+            BuiltinProps::Id => "id of the widget, can not be bind",
+            BuiltinProps::Class => "class of the widget, point to the style, bind to toggle classes, such as `:class=\"{ active: is_active }\"`",
+            BuiltinProps::Style => "inline style bind, such as `:style=\"{ color: text_color }\"`",
+            BuiltinProps::Slot => "put the widget into the named slot of the custom component",
+            BuiltinProps::SlotScope => "name of the data of the scoped slot, such as `slot_scope=\"scope\"` then `:text=\"scope.title\"`",
+            BuiltinProps::Key => "key of the `:for` widget, children are reused by key, such as `:key=\"item.id\"`",
+            BuiltinProps::Virtual => "only instantiate the visible items of `:for`, `virtual` (measured) or `virtual=\"48\"` (fixed height)",
+            BuiltinProps::Overscan => "count of items instantiated out of the viewport of `virtual` list, default is 2",
+            BuiltinProps::Inherits => "inherit another widget (`root`, `window`, `view`)",
+        }
+    }
+}
+
 /// # Property Key Type
 /// - Normal: normal property key, no prefix
 /// - Bind: bind property key, use `:` to define
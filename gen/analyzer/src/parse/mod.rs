@@ -2,3 +2,45 @@ pub mod template;
 pub mod style;
 // mod script;
 pub mod value;
+
+use gen_utils::error::{Error, ParseError, ParseType, Span};
+
+/// ## convert nom error to GenUI parse error with location
+/// - `origin`: the whole input which the parser starts from, the span offset is counted from it
+/// - `ty`: the section which is parsed (template, style or script)
+pub(crate) fn located_nom_err(
+    origin: &str,
+    err: nom::Err<nom::error::Error<&str>>,
+    ty: ParseType,
+) -> Error {
+    match err {
+        nom::Err::Incomplete(_) => {
+            let mut err = ParseError::new("incomplete input", ty);
+            err.set_span(Span::new(origin.len()));
+            err.into()
+        }
+        nom::Err::Error(e) | nom::Err::Failure(e) => {
+            let near = e.input.trim_start().lines().next().unwrap_or_default().trim();
+            let mut err = ParseError::new(
+                &format!("{} error near `{}`", e.code.description(), near),
+                ty,
+            );
+            err.set_span(Span::from_remain(origin, e.input.trim_start()));
+            err.into()
+        }
+    }
+}
+
+/// ## error for the input which can not be parsed
+pub(crate) fn remain_err(origin: &str, remain: &str, ty: ParseType) -> Error {
+    let remain = remain.trim_start();
+    let mut err = ParseError::new(
+        &format!(
+            "parse error! Still remain: {}",
+            remain.lines().next().unwrap_or_default()
+        ),
+        ty,
+    );
+    err.set_span(Span::from_remain(origin, remain));
+    err.into()
+}
@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+
+use gen_utils::common::format_float;
+
+use crate::{value::Value, PropKey, PropKeyType};
+
+pub const STYLE_KEYFRAMES: &str = "@keyframes";
+/// `transition: opacity 0.2s ease_out;`
+pub const STYLE_TRANSITION: &str = "transition";
+/// `animation: fade 1s linear infinite;`
+pub const STYLE_ANIMATION: &str = "animation";
+/// transition for all props of the pseudo state
+pub const TRANSITION_ALL: &str = "all";
+
+/// key of `@keyframes name` in [`crate::Style`], it can not match any widget
+pub fn keyframes_key(name: &str) -> String {
+    format!("{} {}", STYLE_KEYFRAMES, name)
+}
+
+/// ## time of keyframe
+/// `from` => 0, `to` => 1, `50%` => 0.5
+pub fn keyframe_time(selector: &str) -> Result<String, String> {
+    let time = match selector {
+        "from" => 0.0,
+        "to" => 100.0,
+        _ => selector
+            .strip_suffix('%')
+            .and_then(|percent| percent.trim().parse::<f64>().ok())
+            .filter(|percent| (0.0..=100.0).contains(percent))
+            .ok_or_else(|| {
+                format!(
+                    "keyframe `{}` should be `from`, `to` or a percentage in 0% ~ 100%",
+                    selector
+                )
+            })?,
+    };
+    Ok(format_float(time / 100.0))
+}
+
+/// ## parse `transition`
+/// `prop duration [ease], ...` => `{prop: {duration, ease}}`
+/// ```txt
+/// transition: opacity 0.2s ease_out, height 300ms;
+/// ```
+pub fn transition(value: &str) -> Result<Value, String> {
+    let mut transitions = HashMap::new();
+    for item in value.split(',') {
+        let mut parts = item.split_whitespace();
+        let prop = parts
+            .next()
+            .ok_or_else(|| format!("`{}` should be `prop duration [ease]`", item.trim()))?;
+        let timing = timing(&mut parts, item)?;
+        transitions.insert(key(prop), Value::Animation(timing));
+    }
+    Ok(Value::Animation(transitions))
+}
+
+/// ## parse `animation`
+/// `name duration [ease] [infinite]` => `{name, duration, ease, infinite}`
+/// ```txt
+/// animation: fade 1s ease_in infinite;
+/// ```
+pub fn animation(value: &str) -> Result<Value, String> {
+    let mut parts = value.split_whitespace().collect::<Vec<_>>();
+    let infinite = parts.last() == Some(&"infinite");
+    if infinite {
+        parts.pop();
+    }
+    let mut parts = parts.into_iter();
+    let name = parts
+        .next()
+        .ok_or_else(|| format!("`{}` should be `name duration [ease] [infinite]`", value))?;
+    let mut animation = timing(&mut parts, value)?;
+    animation.insert(key("name"), Value::String(name.to_string()));
+    animation.insert(key("infinite"), Value::Bool(infinite));
+    Ok(Value::Animation(animation))
+}
+
+/// `duration [ease]`, duration is `0.2s`, `200ms` or seconds
+fn timing<'a, I>(parts: &mut I, item: &str) -> Result<HashMap<PropKey, Value>, String>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let duration = parts.next().and_then(duration).ok_or_else(|| {
+        format!(
+            "`{}` needs a duration, such as `0.2s` or `200ms`",
+            item.trim()
+        )
+    })?;
+    let mut timing = HashMap::new();
+    timing.insert(key("duration"), Value::Double(duration));
+    if let Some(ease) = parts.next() {
+        timing.insert(key("ease"), Value::UnKnown(ease.to_string()));
+    }
+    if let Some(remain) = parts.next() {
+        return Err(format!("unexpected `{}` in `{}`", remain, item.trim()));
+    }
+    Ok(timing)
+}
+
+fn duration(s: &str) -> Option<f64> {
+    let (num, scale) = match s.strip_suffix("ms") {
+        Some(num) => (num, 0.001),
+        None => (s.strip_suffix('s').unwrap_or(s), 1.0),
+    };
+    num.parse::<f64>()
+        .ok()
+        .filter(|num| *num >= 0.0)
+        .map(|num| num * scale)
+}
+
+fn key(name: &str) -> PropKey {
+    PropKey::new(name, true, PropKeyType::Normal)
+}
+
+#[cfg(test)]
+mod test_animation {
+    use crate::value::Value;
+
+    use super::{animation, keyframe_time, transition};
+
+    fn get<'a>(value: &'a Value, name: &str) -> Option<&'a Value> {
+        value
+            .is_animation_and_get()?
+            .iter()
+            .find(|(k, _)| k.name == name)
+            .map(|(_, v)| v)
+    }
+
+    #[test]
+    fn parse() {
+        let value = transition("opacity 0.2s ease_out, height 300ms").unwrap();
+        let opacity = get(&value, "opacity").unwrap();
+        assert_eq!(get(opacity, "duration"), Some(&Value::Double(0.2)));
+        assert_eq!(
+            get(opacity, "ease"),
+            Some(&Value::UnKnown("ease_out".to_string()))
+        );
+        let height = get(&value, "height").unwrap();
+        assert_eq!(get(height, "duration"), Some(&Value::Double(0.3)));
+        assert!(transition("opacity fast").is_err());
+
+        let value = animation("fade 1s linear infinite").unwrap();
+        assert_eq!(
+            get(&value, "name"),
+            Some(&Value::String("fade".to_string()))
+        );
+        assert_eq!(get(&value, "infinite"), Some(&Value::Bool(true)));
+        assert!(animation("fade 1s linear forever").is_err());
+
+        assert_eq!(keyframe_time("from").unwrap(), "0.0");
+        assert_eq!(keyframe_time("50%").unwrap(), "0.5");
+        assert!(keyframe_time("120%").is_err());
+    }
+}
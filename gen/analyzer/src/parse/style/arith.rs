@@ -0,0 +1,176 @@
+use gen_utils::common::format_float;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    Num(f64),
+    Op(char),
+    Open,
+    Close,
+}
+
+/// ## calculate arithmetic of numbers in style value
+/// support `+ - * /` and `()`, such as `(100 - 20) / 2`
+/// - return `Ok(None)` if the value is not an arithmetic expression (such as `0.5 0.5`, `-1`, `#fff`)
+/// - `-` followed by a number and preceded by whitespace is a negative number, `10 -2` is not an expression
+/// - the result is an integer if all numbers are integers and no `/` is used, otherwise it is a float
+pub fn calc(input: &str) -> Result<Option<String>, String> {
+    let Some(tokens) = tokenize(input) else {
+        return Ok(None);
+    };
+    // at least one binary operator, `-1` is just a number
+    let binary = tokens.windows(2).any(|pair| {
+        matches!(pair[0], Token::Num(_) | Token::Close) && matches!(pair[1], Token::Op(_))
+    });
+    if !binary {
+        return Ok(None);
+    }
+    let mut parser = Parser { tokens, index: 0 };
+    let Some(value) = parser.expr()? else {
+        return Ok(None);
+    };
+    if parser.index != parser.tokens.len() {
+        return Ok(None);
+    }
+
+    let is_int = !input.contains('.') && !input.contains('/');
+    Ok(Some(if is_int && value.fract() == 0.0 {
+        format!("{}", value as i64)
+    } else {
+        format_float(value)
+    }))
+}
+
+fn tokenize(input: &str) -> Option<Vec<Token>> {
+    let chars = input.trim().chars().collect::<Vec<_>>();
+    let mut tokens = vec![];
+    let mut index = 0;
+    while index < chars.len() {
+        let c = chars[index];
+        match c {
+            c if c.is_whitespace() => {
+                index += 1;
+                continue;
+            }
+            '(' => tokens.push(Token::Open),
+            ')' => tokens.push(Token::Close),
+            '+' | '*' | '/' => tokens.push(Token::Op(c)),
+            '-' => {
+                // `a -1` => negative number, `a - 1` | `a-1` => minus
+                let after_space = index > 0 && chars[index - 1].is_whitespace();
+                let before_num = chars
+                    .get(index + 1)
+                    .is_some_and(|c| c.is_ascii_digit() || *c == '.');
+                let after_operand = matches!(tokens.last(), Some(Token::Num(_) | Token::Close));
+                if after_operand && after_space && before_num {
+                    // two operands without operator, not an expression
+                    return None;
+                }
+                tokens.push(Token::Op('-'));
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                let start = index;
+                while index < chars.len() && (chars[index].is_ascii_digit() || chars[index] == '.')
+                {
+                    index += 1;
+                }
+                let num = chars[start..index].iter().collect::<String>();
+                tokens.push(Token::Num(num.parse().ok()?));
+                continue;
+            }
+            _ => return None,
+        }
+        index += 1;
+    }
+    Some(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    index: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.index).copied();
+        self.index += 1;
+        token
+    }
+    fn peek(&self) -> Option<Token> {
+        self.tokens.get(self.index).copied()
+    }
+    /// expr: term (('+' | '-') term)*
+    fn expr(&mut self) -> Result<Option<f64>, String> {
+        let Some(mut value) = self.term()? else {
+            return Ok(None);
+        };
+        while let Some(Token::Op(op @ ('+' | '-'))) = self.peek() {
+            self.index += 1;
+            let Some(rhs) = self.term()? else {
+                return Ok(None);
+            };
+            value = if op == '+' { value + rhs } else { value - rhs };
+        }
+        Ok(Some(value))
+    }
+    /// term: factor (('*' | '/') factor)*
+    fn term(&mut self) -> Result<Option<f64>, String> {
+        let Some(mut value) = self.factor()? else {
+            return Ok(None);
+        };
+        while let Some(Token::Op(op @ ('*' | '/'))) = self.peek() {
+            self.index += 1;
+            let Some(rhs) = self.factor()? else {
+                return Ok(None);
+            };
+            if op == '*' {
+                value *= rhs;
+            } else if rhs == 0.0 {
+                return Err("divide by zero".to_string());
+            } else {
+                value /= rhs;
+            }
+        }
+        Ok(Some(value))
+    }
+    /// factor: '-' factor | number | '(' expr ')'
+    fn factor(&mut self) -> Result<Option<f64>, String> {
+        match self.next() {
+            Some(Token::Op('-')) => Ok(self.factor()?.map(|value| -value)),
+            Some(Token::Num(num)) => Ok(Some(num)),
+            Some(Token::Open) => {
+                let value = self.expr()?;
+                if self.next() != Some(Token::Close) {
+                    return Ok(None);
+                }
+                Ok(value)
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_arith {
+    use super::calc;
+
+    #[test]
+    fn calc_expr() {
+        assert_eq!(calc("10 + 20 * 2"), Ok(Some("50".to_string())));
+        assert_eq!(calc("(100 - 20) / 2"), Ok(Some("40.0".to_string())));
+        assert_eq!(calc("16 * 1.5"), Ok(Some("24.0".to_string())));
+        assert_eq!(calc("10-2"), Ok(Some("8".to_string())));
+        assert_eq!(calc("-(2 + 3) * 2"), Ok(Some("-10".to_string())));
+        assert!(calc("1 / 0").is_err());
+    }
+
+    #[test]
+    fn not_expr() {
+        assert_eq!(calc("0.5 0.5"), Ok(None));
+        assert_eq!(calc("0.5 -0.5"), Ok(None));
+        assert_eq!(calc("-1"), Ok(None));
+        assert_eq!(calc("#fff"), Ok(None));
+        assert_eq!(calc("rgba(0, 0, 0, 0.5)"), Ok(None));
+        assert_eq!(calc("linear_gradient(180deg, #7, #3)"), Ok(None));
+        assert_eq!(calc("1 +"), Ok(None));
+    }
+}
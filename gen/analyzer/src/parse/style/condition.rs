@@ -0,0 +1,253 @@
+use std::{fmt::Display, str::FromStr};
+
+use gen_utils::common::{format_float, Os};
+
+/// `@media (max_width: 600) and (min_width: 300) {...}`
+pub const STYLE_MEDIA: &str = "@media";
+/// `@platform(android|ios) {...}`
+pub const STYLE_PLATFORM: &str = "@platform";
+
+/// # Style Condition
+/// condition of responsive style rules, props in the block are applied at runtime only when it matches
+///
+/// the condition is stored as the prefix of the style key, such as `@media(max_width:600.0) .a-.b`,
+/// nested conditions are joined by space: `@platform(web) @media(max_width:600.0) .a`, See [`StyleCondition::split`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum StyleCondition {
+    /// all features should match the window size
+    Media(Vec<MediaFeature>),
+    /// one of the platforms
+    Platform(Vec<Platform>),
+}
+
+impl StyleCondition {
+    /// ## parse condition in source
+    /// - `kind`: `@media` | `@platform`
+    /// - `query`: `(max_width: 600) and (min_width: 300)` | `(android|ios)`
+    pub fn parse(kind: &str, query: &str) -> Result<Self, String> {
+        let query = query.split_whitespace().collect::<String>();
+        let inner = query
+            .strip_prefix('(')
+            .and_then(|query| query.strip_suffix(')'))
+            .filter(|inner| !inner.is_empty())
+            .ok_or_else(|| format!("`{} {}` should be `{}(...)`", kind, query, kind))?;
+        match kind {
+            STYLE_MEDIA => inner
+                .split(")and(")
+                .map(MediaFeature::from_str)
+                .collect::<Result<Vec<_>, _>>()
+                .map(StyleCondition::Media),
+            STYLE_PLATFORM => inner
+                .split('|')
+                .map(Platform::from_str)
+                .collect::<Result<Vec<_>, _>>()
+                .map(StyleCondition::Platform),
+            _ => Err(format!("unknown style condition `{}`", kind)),
+        }
+    }
+
+    /// ## split conditions from the style key
+    /// `@platform(web) @media(max_width:600.0) .a-.b` => ([platform, media], `.a-.b`)
+    ///
+    /// keys without conditions (including `@keyframes name`) are returned as they are
+    pub fn split(key: &str) -> (Vec<StyleCondition>, &str) {
+        let mut conditions = vec![];
+        let mut rest = key;
+        while let Some((condition, remain)) = rest.split_once(' ').filter(|(condition, _)| {
+            condition.starts_with(STYLE_MEDIA) || condition.starts_with(STYLE_PLATFORM)
+        }) {
+            let Some(condition) = condition
+                .find('(')
+                .and_then(|index| Self::parse(&condition[..index], &condition[index..]).ok())
+            else {
+                break;
+            };
+            conditions.push(condition);
+            rest = remain;
+        }
+        (conditions, rest)
+    }
+}
+
+/// the key form without space, it can be parsed by [`StyleCondition::parse`]
+impl Display for StyleCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StyleCondition::Media(features) => {
+                let features = features
+                    .iter()
+                    .map(|feature| format!("({})", feature))
+                    .collect::<Vec<_>>()
+                    .join("and");
+                write!(f, "{}{}", STYLE_MEDIA, features)
+            }
+            StyleCondition::Platform(platforms) => {
+                let platforms = platforms
+                    .iter()
+                    .map(|platform| platform.to_string())
+                    .collect::<Vec<_>>()
+                    .join("|");
+                write!(f, "{}({})", STYLE_PLATFORM, platforms)
+            }
+        }
+    }
+}
+
+/// ## media feature
+/// `max_width: 600` => the width of window <= 600
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MediaFeature {
+    pub kind: MediaKind,
+    pub value: f64,
+}
+
+impl MediaFeature {
+    pub fn is_match(&self, width: f64, height: f64) -> bool {
+        match self.kind {
+            MediaKind::MinWidth => width >= self.value,
+            MediaKind::MaxWidth => width <= self.value,
+            MediaKind::MinHeight => height >= self.value,
+            MediaKind::MaxHeight => height <= self.value,
+        }
+    }
+}
+
+impl FromStr for MediaFeature {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, value) = s
+            .split_once(':')
+            .ok_or_else(|| format!("media feature `{}` should be `name: value`", s))?;
+        let kind = kind.parse::<MediaKind>()?;
+        let value = value
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|value| *value >= 0.0)
+            .ok_or_else(|| format!("value of media feature `{}` should be a number", kind))?;
+        Ok(Self { kind, value })
+    }
+}
+
+impl Display for MediaFeature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.kind, format_float(self.value))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaKind {
+    MinWidth,
+    MaxWidth,
+    MinHeight,
+    MaxHeight,
+}
+
+impl FromStr for MediaKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "min_width" => Ok(MediaKind::MinWidth),
+            "max_width" => Ok(MediaKind::MaxWidth),
+            "min_height" => Ok(MediaKind::MinHeight),
+            "max_height" => Ok(MediaKind::MaxHeight),
+            _ => Err(format!(
+                "unknown media feature `{}`, expected one of: min_width, max_width, min_height, max_height",
+                s
+            )),
+        }
+    }
+}
+
+impl Display for MediaKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            MediaKind::MinWidth => "min_width",
+            MediaKind::MaxWidth => "max_width",
+            MediaKind::MinHeight => "min_height",
+            MediaKind::MaxHeight => "max_height",
+        })
+    }
+}
+
+/// ## platform in `@platform(...)`
+/// `desktop` is one of windows, macos and linux, See [`Os::DESKTOP`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Platform {
+    Os(Os),
+    Desktop,
+}
+
+impl Platform {
+    /// the platform includes the os or not
+    pub fn is_match(&self, os: Os) -> bool {
+        match self {
+            Platform::Os(platform) => *platform == os,
+            Platform::Desktop => os.is_desktop(),
+        }
+    }
+}
+
+impl FromStr for Platform {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "desktop" => Ok(Platform::Desktop),
+            _ => s.parse::<Os>().map(Platform::Os).map_err(|_| {
+                format!(
+                    "unknown platform `{}`, expected one of: android, ios, web, desktop, windows, macos, linux",
+                    s
+                )
+            }),
+        }
+    }
+}
+
+impl Display for Platform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Platform::Os(os) => os.fmt(f),
+            Platform::Desktop => f.write_str("desktop"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_condition {
+    use gen_utils::common::Os;
+
+    use super::StyleCondition;
+
+    #[test]
+    fn parse_and_split() {
+        let media =
+            StyleCondition::parse("@media", "(max_width: 600) and (min_height: 300.5)").unwrap();
+        assert_eq!(
+            media.to_string(),
+            "@media(max_width:600.0)and(min_height:300.5)"
+        );
+        let platform = StyleCondition::parse("@platform", "(android | desktop)").unwrap();
+        assert_eq!(platform.to_string(), "@platform(android|desktop)");
+        let StyleCondition::Platform(platforms) = &platform else {
+            panic!("should be platform");
+        };
+        assert!(platforms[1].is_match(Os::Mac));
+
+        let key = format!("{} {} .a-.b", platform, media);
+        let (conditions, selector) = StyleCondition::split(&key);
+        assert_eq!(conditions, vec![platform, media]);
+        assert_eq!(selector, ".a-.b");
+        assert_eq!(
+            StyleCondition::split("@keyframes fade").1,
+            "@keyframes fade"
+        );
+
+        assert!(StyleCondition::parse("@media", "(max_width)").is_err());
+        assert!(StyleCondition::parse("@media", "(width: 600)").is_err());
+        assert!(StyleCondition::parse("@platform", "(tv)").is_err());
+        assert!(StyleCondition::parse("@platform", "android").is_err());
+    }
+}
@@ -0,0 +1,505 @@
+use std::collections::HashMap;
+
+use gen_utils::{
+    common::tokenizer::STYLE_PESUDO,
+    error::{Error, ParseError, Span},
+};
+
+use crate::{value::Value, PropKey, PropKeyType, Style};
+
+use super::{
+    animation, calc, keyframe_time, keyframes_key, transition, Node, StyleCondition,
+    STYLE_ANIMATION, STYLE_KEYFRAMES, STYLE_PARENT, STYLE_TRANSITION, STYLE_VAR,
+};
+
+/// max depth of nested `@include`, avoid infinite recursion
+const MAX_INCLUDE_DEPTH: usize = 32;
+
+/// lexical scope of a block, variables and mixins are visible in the block and nested blocks after declared
+#[derive(Default)]
+struct Scope<'a, 'n> {
+    vars: HashMap<&'a str, String>,
+    mixins: HashMap<&'a str, &'n Node<'a>>,
+}
+
+/// # Style Expander
+/// expand [`Node`] into [`Style`]:
+/// - variables are replaced, arithmetic is calculated
+/// - mixins are expanded where they are included
+/// - nested selectors are flattened, `&` is replaced by the parent selector
+/// - `@media` and `@platform` are the prefix of the keys of the rules in them
+pub(crate) struct Expander<'a, 'n> {
+    /// whole input of style, used to locate errors
+    origin: &'a str,
+    scopes: Vec<Scope<'a, 'n>>,
+    /// depth of `@include`
+    depth: usize,
+    /// `@media` and `@platform` which the current block is in
+    conditions: Vec<StyleCondition>,
+    style: Style,
+    /// paths of `@import`
+    imports: Vec<String>,
+}
+
+impl<'a, 'n> Expander<'a, 'n> {
+    pub fn new(origin: &'a str) -> Self {
+        Self {
+            origin,
+            scopes: vec![],
+            depth: 0,
+            conditions: vec![],
+            style: Style::new(),
+            imports: vec![],
+        }
+    }
+
+    /// return the expanded style and paths of `@import`
+    pub fn expand(mut self, nodes: &'n [Node<'a>]) -> Result<(Style, Vec<String>), Error> {
+        let mut props = vec![];
+        self.block(nodes, None, &mut props)?;
+        Ok((self.style, self.imports))
+    }
+
+    /// expand nodes in a new scope
+    fn block(
+        &mut self,
+        nodes: &'n [Node<'a>],
+        selector: Option<&str>,
+        props: &mut Vec<(PropKey, Value)>,
+    ) -> Result<(), Error> {
+        self.scopes.push(Scope::default());
+        let res = self.nodes(nodes, selector, props);
+        self.scopes.pop();
+        res
+    }
+
+    fn nodes(
+        &mut self,
+        nodes: &'n [Node<'a>],
+        selector: Option<&str>,
+        props: &mut Vec<(PropKey, Value)>,
+    ) -> Result<(), Error> {
+        for node in nodes {
+            match node {
+                Node::Var { name, value, at } => {
+                    let value = self.resolve(value, at)?;
+                    self.scope().vars.insert(name, value);
+                }
+                Node::Prop { key, value, at } => {
+                    if selector.is_none() {
+                        return Err(
+                            self.err(&format!("property `{}` should be in a style rule", key), at)
+                        );
+                    }
+                    let value = self.resolve(value, at)?;
+                    let value = match *key {
+                        STYLE_TRANSITION => transition(&value).map_err(|e| self.err(&e, at))?,
+                        STYLE_ANIMATION => animation(&value).map_err(|e| self.err(&e, at))?,
+                        _ => Value::parse_style_prop(key, &value)
+                            .map_err(|e| self.err(&e.to_string(), at))?,
+                    };
+                    props.push((PropKey::from_value_with(&value, key, true), value));
+                }
+                Node::Import { path, at } => {
+                    if self.scopes.len() > 1 {
+                        return Err(self.err("`@import` should be at the top level of style", at));
+                    }
+                    let path = path.trim();
+                    if !self.imports.iter().any(|import| import == path) {
+                        self.imports.push(path.to_string());
+                    }
+                }
+                Node::Keyframes { name, frames, at } => {
+                    if self.scopes.len() > 1 {
+                        return Err(
+                            self.err("`@keyframes` should be at the top level of style", at)
+                        );
+                    }
+                    let key = keyframes_key(name);
+                    let mut keyframes = vec![];
+                    for (frame, body, at) in frames {
+                        let time = keyframe_time(frame).map_err(|e| self.err(&e, at))?;
+                        let mut frame_props = vec![];
+                        self.block(body, Some(&key), &mut frame_props)?;
+                        keyframes.push((
+                            PropKey::new(&time, true, PropKeyType::Normal),
+                            Value::Animation(frame_props.into_iter().collect()),
+                        ));
+                    }
+                    self.style.insert(key, keyframes.into_iter().collect());
+                }
+                Node::Condition {
+                    kind,
+                    query,
+                    body,
+                    at,
+                } => {
+                    // variables can be used in the query: `@media (max_width: $narrow)`
+                    let query = self.resolve(query, at)?;
+                    let condition =
+                        StyleCondition::parse(kind, &query).map_err(|e| self.err(&e, at))?;
+                    if selector.is_some_and(|selector| {
+                        selector.starts_with(STYLE_KEYFRAMES) || selector.contains(STYLE_PESUDO)
+                    }) {
+                        return Err(self.err(
+                            &format!("`{}` can not be used in `@keyframes` or pseudo state", kind),
+                            at,
+                        ));
+                    }
+                    self.conditions.push(condition);
+                    let mut condition_props = vec![];
+                    let res = self.block(body, selector, &mut condition_props);
+                    if let (Ok(_), Some(selector)) = (&res, selector) {
+                        if !condition_props.is_empty() {
+                            let key = self.key(selector);
+                            self.style.entry(key).or_default().extend(condition_props);
+                        }
+                    }
+                    self.conditions.pop();
+                    res?;
+                }
+                Node::Mixin { name, .. } => {
+                    self.scope().mixins.insert(name, node);
+                }
+                Node::Include { name, args, at } => {
+                    self.include(name, args, at, selector, props)?;
+                }
+                Node::Rule {
+                    selector: child,
+                    body,
+                    at,
+                } => {
+                    if selector.is_some_and(|selector| selector.starts_with(STYLE_KEYFRAMES)) {
+                        return Err(self.err("style rule can not be nested in `@keyframes`", at));
+                    }
+                    let key = self.selector(selector, child, at)?;
+                    if !self.conditions.is_empty() && key.contains(STYLE_PESUDO) {
+                        return Err(self.err(
+                            "pseudo state can not be used in `@media` or `@platform`",
+                            at,
+                        ));
+                    }
+                    let mut child_props = vec![];
+                    self.block(body, Some(&key), &mut child_props)?;
+                    // same selector in different places is merged, the latter wins
+                    let key = self.key(&key);
+                    self.style.entry(key).or_default().extend(child_props);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// expand the body of mixin with arguments
+    fn include(
+        &mut self,
+        name: &str,
+        args: &[&'a str],
+        at: &'a str,
+        selector: Option<&str>,
+        props: &mut Vec<(PropKey, Value)>,
+    ) -> Result<(), Error> {
+        let Some(Node::Mixin { params, body, .. }) = self
+            .scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.mixins.get(name).copied())
+        else {
+            return Err(self.err(&format!("mixin `{}` is not found", name), at));
+        };
+        if args.len() > params.len() {
+            return Err(self.err(
+                &format!(
+                    "mixin `{}` takes {} arguments but {} are given",
+                    name,
+                    params.len(),
+                    args.len()
+                ),
+                at,
+            ));
+        }
+        if self.depth >= MAX_INCLUDE_DEPTH {
+            return Err(self.err(&format!("mixin `{}` is included recursively", name), at));
+        }
+        // arguments are resolved in the scope of `@include`
+        let args = args
+            .iter()
+            .map(|arg| self.resolve(arg, at))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        self.scopes.push(Scope::default());
+        self.depth += 1;
+        let res = (|| {
+            for (index, (param, default)) in params.iter().enumerate() {
+                let value = match (args.get(index), default) {
+                    (Some(arg), _) => arg.to_string(),
+                    (None, Some(default)) => self.resolve(default, at)?,
+                    (None, None) => {
+                        return Err(self.err(
+                            &format!("missing argument `${}` of mixin `{}`", param, name),
+                            at,
+                        ))
+                    }
+                };
+                self.scope().vars.insert(param, value);
+            }
+            self.nodes(body, selector, props)
+        })();
+        self.depth -= 1;
+        self.scopes.pop();
+        res
+    }
+
+    /// key of nested selector
+    /// - `.a { .b {} }` => `.a-.b`
+    /// - `.a { &::hover {} }` => `.a-&::hover`
+    /// - `.a { &.b {} }` => `.a.b`, `.a { &_b {} }` => `.a_b`
+    fn selector(&self, parent: Option<&str>, child: &str, at: &'a str) -> Result<String, Error> {
+        match (parent, child.strip_prefix(STYLE_PARENT)) {
+            (Some(parent), Some(suffix)) if !suffix.starts_with("::") => {
+                Ok(format!("{}{}", parent, suffix))
+            }
+            (None, Some(suffix)) if !suffix.starts_with("::") => Err(self.err(
+                &format!("`{}` can only be used in nested style rule", child),
+                at,
+            )),
+            (Some(parent), _) => Ok(format!("{}-{}", parent, child)),
+            (None, _) => Ok(child.to_string()),
+        }
+    }
+
+    /// key of the selector in [`Style`], conditions are the prefix: `@media(max_width:600.0) .a`
+    fn key(&self, selector: &str) -> String {
+        self.conditions
+            .iter()
+            .map(|condition| condition.to_string())
+            .chain(std::iter::once(selector.to_string()))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// replace variables and calculate arithmetic
+    fn resolve(&self, value: &str, at: &'a str) -> Result<String, Error> {
+        let mut res = String::new();
+        let mut rest = value;
+        while let Some(index) = rest.find(STYLE_VAR) {
+            res.push_str(&rest[..index]);
+            let after = &rest[index + STYLE_VAR.len()..];
+            let len = after
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(after.len());
+            let (name, remain) = after.split_at(len);
+            // `$a.b` is a bind value
+            let var = if remain.starts_with('.') {
+                None
+            } else {
+                self.var(name)
+            };
+            match var {
+                Some(var) => res.push_str(var),
+                None => {
+                    res.push_str(STYLE_VAR);
+                    res.push_str(name);
+                }
+            }
+            rest = remain;
+        }
+        res.push_str(rest);
+
+        match calc(&res) {
+            Ok(Some(value)) => Ok(value),
+            Ok(None) => Ok(res),
+            Err(e) => Err(self.err(&format!("{}: `{}`", e, value), at)),
+        }
+    }
+
+    fn var(&self, name: &str) -> Option<&str> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.vars.get(name))
+            .map(|value| value.as_str())
+    }
+
+    fn scope(&mut self) -> &mut Scope<'a, 'n> {
+        self.scopes.last_mut().expect("scope should exist")
+    }
+
+    fn err(&self, msg: &str, at: &'a str) -> Error {
+        let mut err = ParseError::other(msg, "DSL Style");
+        err.set_span(Span::from_remain(self.origin, at));
+        err.into()
+    }
+}
+
+#[cfg(test)]
+mod test_expand {
+    use crate::{parse::style::parse, value::Value};
+
+    fn prop(style: &crate::Style, key: &str, prop: &str) -> Option<Value> {
+        style
+            .get(key)?
+            .iter()
+            .find(|(k, _)| k.name == prop)
+            .map(|(_, v)| v.clone())
+    }
+
+    #[test]
+    fn vars_and_arith() {
+        let style = parse(
+            r#"
+$size: 16;
+.a {
+    font_size: $size * 2;
+    $size: 10;
+    height: ($size + 20) / 2;
+    .b {
+        width: $size;
+        color: $unknown;
+    }
+}
+.c {
+    width: $size;
+}
+"#,
+        )
+        .unwrap();
+        assert_eq!(prop(&style, ".a", "font_size"), Some(Value::ISize(32)));
+        assert_eq!(prop(&style, ".a", "height"), Some(Value::Double(15.0)));
+        assert_eq!(prop(&style, ".a-.b", "width"), Some(Value::ISize(10)));
+        // not a variable, still a bind
+        assert!(matches!(
+            prop(&style, ".a-.b", "color"),
+            Some(Value::Bind(_))
+        ));
+        assert_eq!(prop(&style, ".c", "width"), Some(Value::ISize(16)));
+    }
+
+    #[test]
+    fn mixin_and_parent() {
+        let style = parse(
+            r#"
+@mixin size($w, $h: $w / 2) {
+    width: $w;
+    height: $h;
+}
+.btn {
+    @include size(100);
+    &_primary {
+        @include size(20, 30);
+    }
+    &::hover {
+        height: 1;
+    }
+}
+"#,
+        )
+        .unwrap();
+        assert_eq!(prop(&style, ".btn", "width"), Some(Value::ISize(100)));
+        assert_eq!(prop(&style, ".btn", "height"), Some(Value::Double(50.0)));
+        assert_eq!(
+            prop(&style, ".btn_primary", "height"),
+            Some(Value::ISize(30))
+        );
+        assert!(style.contains_key(".btn-&::hover"));
+    }
+
+    #[test]
+    fn errors() {
+        let err = parse(".a {\n    @include unknown;\n}").unwrap_err();
+        assert!(err.to_string().contains("`unknown`"));
+        assert_eq!(err.span().map(|span| span.offset), Some(9));
+        assert!(parse("&_a { width: 1; }").is_err());
+        assert!(parse("width: 1;").is_err());
+        assert!(parse("@mixin a { @include a; }\n.b { @include a; }").is_err());
+        assert!(parse(".a { @import \"theme.gen\"; }").is_err());
+    }
+
+    #[test]
+    fn animations() {
+        let style = parse(
+            r#"
+$duration: 0.3s;
+@keyframes fade {
+    from { opacity: 0; }
+    50% { opacity: 0.8; }
+    to { opacity: 1; }
+}
+.card {
+    transition: opacity $duration ease_out;
+    animation: fade 1s linear infinite;
+}
+"#,
+        )
+        .unwrap();
+        let keyframes = style.get("@keyframes fade").unwrap();
+        assert_eq!(keyframes.len(), 3);
+        assert!(keyframes
+            .iter()
+            .any(|(k, v)| k.name == "0.5" && v.is_animation_and_get().is_some()));
+        assert!(matches!(
+            prop(&style, ".card", "transition"),
+            Some(Value::Animation(_))
+        ));
+        assert!(matches!(
+            prop(&style, ".card", "animation"),
+            Some(Value::Animation(_))
+        ));
+        assert!(parse(".a { @keyframes b { from { opacity: 0; } } }").is_err());
+        assert!(parse("@keyframes b { half { opacity: 0; } }").is_err());
+        assert!(parse("@keyframes b { from { .c { opacity: 0; } } }").is_err());
+    }
+
+    #[test]
+    fn conditions() {
+        let style = parse(
+            r#"
+$narrow: 600;
+@media (max_width: $narrow) {
+    .a {
+        height: 10;
+        .b {
+            width: 1;
+        }
+    }
+}
+.a {
+    height: 20;
+    @platform(android|ios) {
+        height: 30;
+        @media (min_width: 300) and (max_width: 900) {
+            width: 2;
+        }
+    }
+}
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            prop(&style, "@media(max_width:600.0) .a", "height"),
+            Some(Value::ISize(10))
+        );
+        assert!(style.contains_key("@media(max_width:600.0) .a-.b"));
+        assert_eq!(prop(&style, ".a", "height"), Some(Value::ISize(20)));
+        assert_eq!(
+            prop(&style, "@platform(android|ios) .a", "height"),
+            Some(Value::ISize(30))
+        );
+        assert!(style
+            .contains_key("@platform(android|ios) @media(min_width:300.0)and(max_width:900.0) .a"));
+        assert!(parse("@media (max_width: 600) { height: 1; }").is_err());
+        assert!(parse("@platform(tv) { .a { height: 1; } }").is_err());
+        assert!(parse(".a { &::hover { @media (max_width: 1) { height: 1; } } }").is_err());
+        assert!(parse("@media (max_width: 1) { .a { &::hover { height: 1; } } }").is_err());
+    }
+
+    #[test]
+    fn imports() {
+        let (style, imports) = crate::parse::style::parse_with_imports(
+            "@import \"components/theme.gen\";\n@import \"base.gen\";\n.a { width: 1; }",
+        )
+        .unwrap();
+        assert_eq!(imports, vec!["components/theme.gen", "base.gen"]);
+        assert!(style.contains_key(".a"));
+    }
+}
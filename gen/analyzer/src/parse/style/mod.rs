@@ -0,0 +1,415 @@
+mod animation;
+mod arith;
+mod condition;
+mod expand;
+
+use gen_utils::{
+    common::tokenizer::{FUNCTION_SIGN, IMPORT},
+    error::{Error, ParseType},
+    parser::{parse_value, trim},
+};
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_until, take_until1, take_while1},
+    character::complete::{multispace0, multispace1},
+    combinator::{opt, recognize},
+    error::ErrorKind,
+    multi::many0,
+    sequence::{delimited, pair, preceded, terminated, tuple},
+    IResult,
+};
+
+use gen_utils::common::tokenizer::{
+    HOLDER_END, HOLDER_START, STYLE_CLASS, STYLE_END, STYLE_ID, STYLE_PESUDO, STYLE_START,
+};
+
+use crate::{nom_err, Comment, Style};
+
+use super::{located_nom_err, remain_err};
+
+pub use animation::*;
+pub use arith::calc;
+pub use condition::*;
+use expand::Expander;
+
+pub const STYLE_VAR: &str = "$";
+pub const STYLE_PARENT: &str = "&";
+pub const STYLE_MIXIN: &str = "@mixin";
+pub const STYLE_INCLUDE: &str = "@include";
+pub const STYLE_IMPORT: &str = "@import";
+
+#[allow(dead_code)]
+pub fn parse_style_tag(input: &str) -> IResult<&str, &str> {
+    let (input, _) = trim(tag(STYLE_START))(input)?;
+    let (_, input) = take_until(STYLE_END)(input)?;
+    Ok((input, "style"))
+}
+
+/// # Style Node
+/// node in `<style>` before expanding, it keeps the nesting and the order,
+/// variables, mixins and `&` are expanded into [`Style`] by [`Expander`]
+#[derive(Debug, Clone)]
+pub(crate) enum Node<'a> {
+    /// `$name: value;`
+    Var {
+        name: &'a str,
+        value: &'a str,
+        at: &'a str,
+    },
+    /// `key: value;`
+    Prop {
+        key: &'a str,
+        value: &'a str,
+        at: &'a str,
+    },
+    /// `@mixin name($a, $b: default) {...}`
+    Mixin {
+        name: &'a str,
+        params: Vec<(&'a str, Option<&'a str>)>,
+        body: Vec<Node<'a>>,
+    },
+    /// `@include name(a, b);`
+    Include {
+        name: &'a str,
+        args: Vec<&'a str>,
+        at: &'a str,
+    },
+    /// `@import "path/to/theme.gen";`
+    Import { path: &'a str, at: &'a str },
+    /// `@keyframes name { from {...} 50% {...} to {...} }`
+    Keyframes {
+        name: &'a str,
+        /// (`from` | `to` | `N%`, body)
+        frames: Vec<(&'a str, Vec<Node<'a>>, &'a str)>,
+        at: &'a str,
+    },
+    /// `@media (max_width: 600) {...}` | `@platform(android|ios) {...}`
+    Condition {
+        /// `@media` | `@platform`
+        kind: &'a str,
+        /// `(max_width: 600) and (min_width: 300)` | `(android|ios)`
+        query: &'a str,
+        body: Vec<Node<'a>>,
+        at: &'a str,
+    },
+    /// `selector {...}`
+    Rule {
+        selector: String,
+        body: Vec<Node<'a>>,
+        at: &'a str,
+    },
+}
+
+/// ## parser ident
+/// - class
+/// - id
+/// - pesudo
+/// - import
+/// - identifier
+fn parse_ident(input: &str) -> IResult<&str, String> {
+    let (input, (style_type, name)) = pair(
+        alt((
+            trim(tag(STYLE_CLASS)),
+            trim(tag(STYLE_ID)),
+            trim(tag(STYLE_PESUDO)),
+            trim(tag(IMPORT)),
+            trim(tag(FUNCTION_SIGN)),
+        )),
+        parse_value,
+    )(input)?;
+
+    // let style = Style::new_style_start(name, style_type.into());
+    Ok((input, format!("{}{}", style_type, name)))
+}
+
+/// ## parse parent reference ident
+/// - `&.active` : the parent with class `active`
+/// - `&#main` : the parent with id `main`
+/// - `&_primary` : the parent name with suffix, `.btn { &_primary {} }` => `.btn_primary`
+pub(crate) fn parse_parent_ident(input: &str) -> IResult<&str, String> {
+    let (input, ident) = trim(recognize(pair(
+        tag(STYLE_PARENT),
+        take_while1(|c: char| c.is_alphanumeric() || c == '_' || c == '.' || c == '#'),
+    )))(input)?;
+    Ok((input, ident.to_string()))
+}
+
+fn parse_property_key(input: &str) -> IResult<&str, &str> {
+    parse_value(input)
+}
+
+/// ## parse style property
+/// - normal : `xxx:zzz;`
+/// - bind : `xxx:$zzz;`
+/// - function : `xxx:zzz();`
+fn parse_property(input: &str) -> IResult<&str, Node<'_>> {
+    let at = input;
+    let (input, (key, _, value)) =
+        tuple((parse_property_key, trim(tag(":")), take_until1(";")))(input)?;
+    //remove `;`
+    let (input, _) = trim(tag(";"))(input)?;
+    Ok((
+        input,
+        Node::Prop {
+            key,
+            value: value.trim(),
+            at,
+        },
+    ))
+}
+
+/// ## parse style variable
+/// `$name: value;`
+fn parse_var(input: &str) -> IResult<&str, Node<'_>> {
+    let at = input;
+    let (input, (name, _, value)) = tuple((
+        preceded(tag(STYLE_VAR), parse_value),
+        trim(tag(":")),
+        take_until1(";"),
+    ))(input)?;
+    let (input, _) = trim(tag(";"))(input)?;
+    Ok((
+        input,
+        Node::Var {
+            name,
+            value: value.trim(),
+            at,
+        },
+    ))
+}
+
+/// content in balanced `(...)`, such as `(a, rgba(0, 0, 0, 0.5))`
+pub(crate) fn parse_paren(input: &str) -> IResult<&str, &str> {
+    if !input.starts_with('(') {
+        return Err(nom_err!(input, ErrorKind::Tag));
+    }
+    let mut level = 0;
+    for (index, c) in input.char_indices() {
+        match c {
+            '(' => level += 1,
+            ')' => {
+                level -= 1;
+                if level == 0 {
+                    return Ok((&input[index + 1..], &input[1..index]));
+                }
+            }
+            _ => {}
+        }
+    }
+    Err(nom_err!(input, ErrorKind::TakeUntil))
+}
+
+/// split by `,` which is not in `(...)`
+pub(crate) fn split_args(input: &str) -> Vec<&str> {
+    let mut args = vec![];
+    let (mut level, mut start) = (0, 0);
+    for (index, c) in input.char_indices() {
+        match c {
+            '(' => level += 1,
+            ')' => level -= 1,
+            ',' if level == 0 => {
+                args.push(input[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    args.push(input[start..].trim());
+    args.retain(|arg| !arg.is_empty());
+    args
+}
+
+/// ## parse mixin
+/// `@mixin name {...}` | `@mixin name($a, $b: 10) {...}`
+fn parse_mixin(input: &str) -> IResult<&str, Node<'_>> {
+    let (input, name) = preceded(pair(tag(STYLE_MIXIN), multispace1), parse_value)(input)?;
+    let (input, params) = opt(preceded(multispace0, parse_paren))(input)?;
+    let params = params
+        .map(split_args)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|param| {
+            let (name, default) = param
+                .split_once(':')
+                .map_or((param, None), |(name, default)| {
+                    (name.trim(), Some(default.trim()))
+                });
+            (name.trim_start_matches(STYLE_VAR), default)
+        })
+        .collect();
+    let (input, body) = parse_block(input)?;
+    Ok((input, Node::Mixin { name, params, body }))
+}
+
+/// ## parse include
+/// `@include name;` | `@include name(a, b);`
+fn parse_include(input: &str) -> IResult<&str, Node<'_>> {
+    let at = input;
+    let (input, name) = preceded(pair(tag(STYLE_INCLUDE), multispace1), parse_value)(input)?;
+    let (input, args) = opt(preceded(multispace0, parse_paren))(input)?;
+    let (input, _) = trim(tag(";"))(input)?;
+    Ok((
+        input,
+        Node::Include {
+            name,
+            args: args.map(split_args).unwrap_or_default(),
+            at,
+        },
+    ))
+}
+
+/// ## parse import
+/// `@import "path/to/theme.gen";`
+fn parse_import(input: &str) -> IResult<&str, Node<'_>> {
+    let at = input;
+    let (input, path) = preceded(
+        pair(tag(STYLE_IMPORT), multispace0),
+        delimited(tag("\""), take_until("\""), tag("\"")),
+    )(input)?;
+    let (input, _) = trim(tag(";"))(input)?;
+    Ok((input, Node::Import { path, at }))
+}
+
+/// ## parse keyframes
+/// `@keyframes name { from {...} 50% {...} to {...} }`
+fn parse_keyframes(input: &str) -> IResult<&str, Node<'_>> {
+    let at = input;
+    let (input, name) = preceded(pair(tag(STYLE_KEYFRAMES), multispace1), parse_value)(input)?;
+    let (input, frames) = delimited(
+        trim(tag(HOLDER_START)),
+        many0(preceded(
+            parse_comment,
+            preceded(multispace0, |input| {
+                let at = input;
+                let (input, frame) = recognize(take_while1(|c: char| {
+                    c.is_alphanumeric() || c == '%' || c == '.'
+                }))(input)?;
+                let (input, body) = parse_block(input)?;
+                Ok((input, (frame, body, at)))
+            }),
+        )),
+        preceded(parse_comment, trim(tag(HOLDER_END))),
+    )(input)?;
+    Ok((input, Node::Keyframes { name, frames, at }))
+}
+
+/// ## parse responsive condition
+/// `@media (max_width: 600) {...}` | `@platform(android|ios) {...}`
+fn parse_condition(input: &str) -> IResult<&str, Node<'_>> {
+    let at = input;
+    let (input, (kind, query)) = pair(
+        alt((tag(STYLE_MEDIA), tag(STYLE_PLATFORM))),
+        take_until1(HOLDER_START),
+    )(input)?;
+    let (input, body) = parse_block(input)?;
+    Ok((
+        input,
+        Node::Condition {
+            kind,
+            query: query.trim(),
+            body,
+            at,
+        },
+    ))
+}
+
+/// ## parse rule
+/// `.class {...}` | `#id {...}` | `&::pseudo {...}` | `&.class {...}`
+fn parse_rule(input: &str) -> IResult<&str, Node<'_>> {
+    let at = input;
+    let (input, selector) = alt((parse_ident, parse_parent_ident))(input)?;
+    let (input, body) = parse_block(input)?;
+    Ok((input, Node::Rule { selector, body, at }))
+}
+
+/// `{ nodes }`
+fn parse_block(input: &str) -> IResult<&str, Vec<Node<'_>>> {
+    delimited(trim(tag(HOLDER_START)), parse_nodes, trim(tag(HOLDER_END)))(input)
+}
+
+/// nodes in block, comments between nodes are ignored
+fn parse_nodes(input: &str) -> IResult<&str, Vec<Node<'_>>> {
+    terminated(
+        many0(preceded(
+            parse_comment,
+            preceded(
+                multispace0,
+                alt((
+                    parse_import,
+                    parse_keyframes,
+                    parse_condition,
+                    parse_var,
+                    parse_mixin,
+                    parse_include,
+                    parse_property,
+                    parse_rule,
+                )),
+            ),
+        )),
+        parse_comment,
+    )(input)
+}
+
+#[allow(dead_code)]
+fn parse_comment(input: &str) -> IResult<&str, Vec<Comment>> {
+    many0(Comment::parse)(input)
+}
+
+/// # parse styleⓂ️
+/// main style parser
+/// ```txt
+/// $main: #fff;
+/// @mixin size($w, $h: 32) {
+///   width: $w;
+///   height: $h;
+/// }
+/// .a{
+///   color: $main;
+///   @include size(100 * 2);
+///   .b {
+///     color: blue;
+///   }
+///   &_c {
+///     color: red;
+///   }
+/// }
+/// // ----- after ---------
+/// {
+///     .a : {color: #fff, width: 200, height: 32}, // a class style
+///     .a-.b : {color: blue}, // b class style, which is a child of a
+///     .a_c : {color: red} // `&` is replaced by the parent
+/// }
+/// ```
+/// ## Expand
+/// - `$name: value;` : variable, visible in the block (and nested blocks) after it is declared,
+///   `$name` which is not a variable is still a bind value
+/// - `@mixin` / `@include` : the body of mixin is expanded where it is included
+/// - arithmetic of numbers (`+ - * /` and `()`) is calculated, See [`calc`]
+/// - `&` in nested selector is replaced by the parent selector
+/// - `@keyframes name {...}` is stored as `@keyframes name : {time: Value::Animation(props)}`, See [`keyframe_time`]
+/// - `transition` and `animation` are parsed into [`crate::value::Value::Animation`], See [`transition`], [`animation`]
+/// - `@media (...) {...}` and `@platform(...) {...}` can be at the top level or nested in rules,
+///   the condition is the prefix of the key: `@media(max_width:600.0) .a`, See [`StyleCondition`]
+///
+/// `@import` is ignored, See [`parse_with_imports`]
+///
+/// errors have span which offset is counted from `input`
+pub fn parse(input: &str) -> Result<Style, Error> {
+    parse_with_imports(input).map(|(style, _)| style)
+}
+
+/// ## parse style and `@import` paths
+/// `@import "components/theme.gen";` should be at the top level, the path is relative to the source dir,
+/// the imported style is merged by [`crate::Model::new`]
+pub fn parse_with_imports(input: &str) -> Result<(Style, Vec<String>), Error> {
+    match parse_nodes(input) {
+        Ok((remain, nodes)) => {
+            if remain.trim().is_empty() {
+                return Expander::new(input).expand(&nodes);
+            }
+
+            Err(remain_err(input, remain, ParseType::Style))
+        }
+        Result::Err(e) => Err(located_nom_err(input, e, ParseType::Style)),
+    }
+}
@@ -1,25 +1,29 @@
 use crate::model::Template;
 use crate::value::{Bind, Ident, Value};
 use crate::{nom_err, Comment, Polls, PropKey, PropKeyType, SugarIter};
-use gen_utils::error::{Error, ParseError};
+use gen_utils::error::{Error, ParseError, ParseType, Span};
 use gen_utils::parser::parse_value;
 use gen_utils::{
     common::tokenizer::{END_SIGN, END_START_SIGN, EQUAL_SIGN, SELF_END_SIGN},
     parser::{parse_string, trim},
 };
-use nom::combinator::opt;
+use nom::combinator::{map, not, opt, verify};
 use nom::error::ErrorKind;
 use nom::{
     branch::alt,
-    bytes::complete::{tag, take_until},
+    bytes::complete::{tag, take_till1, take_until},
     character::complete::char,
     multi::many0,
     sequence::{delimited, preceded, tuple},
     IResult,
 };
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::rc::Rc;
 use std::sync::{Arc, RwLock};
 
+use super::{located_nom_err, remain_err};
+
 /// ## ⚡️ parse normal label 🆗
 /// use in tag_start | tag_end to parse the tag_name
 /// ### example
@@ -42,37 +46,39 @@ fn parse_tag_name(input: &str) -> IResult<&str, &str> {
 /// let res = parse_tag_start(input).unwrap();
 /// ```
 pub fn parse_tag_start(input: &str) -> IResult<&str, (Template, CloseType)> {
-    let (remain, (name, props)) = trim(preceded(
-        char('<'),
-        tuple((parse_tag_name, parse_properties)),
-    ))(input)?;
-
-    let props = if let Some(props) = props {
-        Some(HashMap::from_iter(props.into_iter()))
-    } else {
-        None
-    };
-
-    // let mut tag = Tag::new_tag_props(name, props);
-    let mut template = Template::new(name);
-    let mut remain = remain.trim();
+    let (remain, (name, props)) = trim(tuple((parse_tag_open, parse_properties)))(input)?;
     // check if remain start with `/>`, if true, is end tag
-    let close_type = if remain.starts_with(SELF_END_SIGN) {
-        // unimplemented!("self closed tag not support yet, please use `>` to close tag, expect support version 0.2.1+");
-        // remain = remain.trim_start_matches(SELF_END_SIGN);
-        // tag.set_ty(CloseType::SelfClosed);
-        remain = remain.strip_prefix(SELF_END_SIGN).unwrap_or(remain);
-        CloseType::SelfClosed
-    } else {
-        remain = remain.trim_start_matches(END_SIGN);
-        CloseType::Usual
+    let remain = remain.trim();
+    let Some((remain, close_type)) = parse_close_type(remain) else {
+        // tag is not closed, the remain can not be treated as text child
+        return Err(nom_err!(remain, ErrorKind::Tag));
     };
 
-    template.props = props;
+    let mut template = Template::new(name);
+    template.props = props.map(HashMap::from_iter);
 
     Ok((remain, (template, close_type)))
 }
 
+/// ## parse the open of tag start (`<tag_name`)
+fn parse_tag_open(input: &str) -> IResult<&str, &str> {
+    trim(preceded(char('<'), parse_tag_name))(input)
+}
+
+/// ## parse the close of tag start
+/// - self closed: `/>`
+/// - usual: `>`, children and close tag follow
+fn parse_close_type(input: &str) -> Option<(&str, CloseType)> {
+    let input = input.trim_start();
+    if let Some(remain) = input.strip_prefix(SELF_END_SIGN) {
+        Some((remain, CloseType::SelfClosed))
+    } else {
+        input
+            .strip_prefix(END_SIGN)
+            .map(|remain| (remain, CloseType::Usual))
+    }
+}
+
 /// ## parse property key 🆗
 /// - normal: k
 /// - bind: :k
@@ -139,10 +145,10 @@ fn parse_property(input: &str) -> IResult<&str, (PropKey, Value)> {
     }
 
     let (input, value) = preceded(tag(EQUAL_SIGN), parse_string)(input)?;
-    // parse value
+    // parse value, the property is complete here, so if value is invalid, stop and point to the value
     let value = key_type
         .to_value(value)
-        .map_err(|_| nom_err!(value, ErrorKind::Tag))?;
+        .map_err(|_| nom::Err::Failure(nom::error::Error::new(value, ErrorKind::Verify)))?;
     Ok((input, (PropKey::new(key, false, key_type), value)))
 }
 
@@ -174,6 +180,21 @@ fn parse_comment(input: &str) -> IResult<&str, Vec<Comment>> {
     many0(Comment::parse)(input)
 }
 
+/// ## parse text child of tag (until next `<`), the text is trimmed
+/// - `Hello {{ name }}` in `<label>Hello {{ name }}</label>`, See [`Template::push_text`]
+/// - comment (`// xxx`) is not text, it belongs to the next tag
+pub(crate) fn parse_text(input: &str) -> IResult<&str, &str> {
+    preceded(
+        not(Comment::parse),
+        map(
+            verify(take_till1(|c| c == '<'), |text: &str| {
+                !text.trim().is_empty()
+            }),
+            str::trim,
+        ),
+    )(input)
+}
+
 #[deprecated = "use parse_end_tag_common instead"]
 #[allow(dead_code)]
 fn to_end_tag(input: &str, tag_name: String) -> IResult<&str, &str> {
@@ -234,26 +255,73 @@ fn parse_end_tag(input: &str, name: String) -> IResult<&str, (&str, &str)> {
     Ok((input, (END_START_SIGN, value)))
 }
 
+/// ## set the children and text children of the tag
+/// each child is bound to the tag and checked (See [`Template::after_all`]), then the texts are pushed (See [`Template::push_text`]),
+/// errors are located at the child or the tag
+fn set_children(
+    template: &mut Template,
+    mut children: Vec<Template>,
+    texts: Vec<&str>,
+    poll: &Arc<RwLock<Polls>>,
+) -> Vec<Error> {
+    let mut errors = vec![];
+    if !children.is_empty() {
+        let (special, name) = template.as_parent();
+        for child in children.iter_mut() {
+            child.set_parent(special.to_string(), name.to_string(), template.root);
+            if let Err(e) = child.after_all(Arc::clone(poll)) {
+                errors.push(e.or_span(child.span.clone().unwrap_or_default()));
+            }
+        }
+        template.children.replace(children);
+    }
+    for text in texts {
+        if let Err(e) = template.push_text(text) {
+            errors.push(e.or_span(template.span.clone().unwrap_or_default()));
+        }
+    }
+    errors
+}
+
+/// ## store the error which is not a syntax error (such as `else` without `if`)
+/// nom can only carry the position, so the real error is stored here and the parser stops with `nom::Err::Failure`
+fn fail_with<'a>(
+    cause: &Rc<RefCell<Option<Error>>>,
+    err: Error,
+    span: Option<&Span>,
+    input: &'a str,
+) -> nom::Err<nom::error::Error<&'a str>> {
+    let err = match span {
+        Some(span) => err.or_span(span.clone()),
+        None => err,
+    };
+    cause.borrow_mut().replace(err);
+    nom::Err::Failure(nom::error::Error::new(input, ErrorKind::Fail))
+}
+
 /// ## parse tag ✅ 🆗 Result<(&'a str, Template), nom::Err<nom::error::Error<&'a str>>>
+/// - origin: the whole template input, use to count the span of each tag
+/// - cause: see [`fail_with`]
 #[allow(dead_code)]
 fn parse_tag<'a>(
+    origin: &'a str,
+    cause: Rc<RefCell<Option<Error>>>,
     poll: Arc<RwLock<Polls>>,
     mut root: bool,
     mut iter: Option<SugarIter>,
 ) -> impl FnMut(&'a str) -> IResult<&'a str, Template> {
-    move |input: &str| {
+    move |input: &'a str| {
         // [parse comment if exist] ------------------------------------------------------------------------------------
         let (input, comments) = parse_comment(input)?;
         // [parse tag start] -------------------------------------------------------------------------------------------
+        let tag_start = input.trim_start();
         let (input, (mut template, close_type)) = parse_tag_start(input)?;
+        template.span.replace(Span::from_remain(origin, tag_start));
         template.root = root;
         root = false;
         let sugar_iter = template
             .after_prop_parse(Arc::clone(&poll), iter.as_ref())
-            .map_err(|e| {
-                eprintln!("parse_tag error: {:?}", e);
-                nom_err!(input, ErrorKind::Fail)
-            })?;
+            .map_err(|e| fail_with(&cause, e, template.span.as_ref(), tag_start))?;
         iter = sugar_iter;
         if !comments.is_empty() {
             template.comments.replace(comments);
@@ -271,30 +339,28 @@ fn parse_tag<'a>(
                 match parse_end_tag(input, tag_name.to_string()) {
                     Ok((input, _)) => input,
                     Err(_) => {
+                        // text child, it can not be mixed with tags (checked after children are set)
+                        let (input, mut texts) =
+                            opt(parse_text)(input).map(|(i, t)| (i, Vec::from_iter(t)))?;
                         // has children, parse children
-                        let (input, mut children) =
-                            many0(parse_tag(Arc::clone(&poll), root, iter.clone()))(input)?;
+                        let (input, children) = many0(parse_tag(
+                            origin,
+                            Rc::clone(&cause),
+                            Arc::clone(&poll),
+                            root,
+                            iter.clone(),
+                        ))(input)?;
+                        let (input, text) = opt(parse_text)(input)?;
+                        texts.extend(text);
 
                         let input = match parse_end_tag_common(input) {
                             Ok((remain, _)) => remain,
                             Err(_) => input,
                         };
 
-                        if !children.is_empty() {
-                            let (special, name) = template.as_parent();
-                            for child in children.iter_mut() {
-                                child.set_parent(
-                                    special.to_string(),
-                                    name.to_string(),
-                                    template.root,
-                                );
-                                child.after_all(Arc::clone(&poll)).map_err(|e| {
-                                    eprintln!("parse_tag error: {:?}", e);
-                                    nom_err!(input, ErrorKind::Fail)
-                                })?;
-                            }
-
-                            template.children.replace(children);
+                        let errors = set_children(&mut template, children, texts, &poll);
+                        if let Some(e) = errors.into_iter().next() {
+                            return Err(fail_with(&cause, e, None, input));
                         }
                         let input = input.trim();
                         // 这里说明有和当前ast_node同级的标签，需要返回到上一级来解析
@@ -315,22 +381,553 @@ fn parse_tag<'a>(
 }
 
 /// ## parse template Ⓜ️
-/// main template parser
+/// main template parser, errors have span which offset is counted from `input`
 #[allow(dead_code)]
 pub fn parse(input: &str, poll: Arc<RwLock<Polls>>, root: bool) -> Result<Template, Error> {
-    match parse_tag(poll, root, None)(input) {
+    let cause = Rc::new(RefCell::new(None));
+    match parse_tag(input, Rc::clone(&cause), poll, root, None)(input) {
         Ok((remain, template)) => {
             if remain.is_empty() {
                 return Ok(template);
             }
-            Err(ParseError::template(remain).into())
+            Err(remain_err(input, remain, ParseType::Template))
+        }
+        Err(e) => match cause.borrow_mut().take() {
+            Some(err) => Err(err),
+            None => Err(located_nom_err(input, e, ParseType::Template)),
+        },
+    }
+}
+
+// [recover] -----------------------------------------------------------------------------------------------------------
+
+/// ## template parser with error recovery
+/// it does not stop at the first error, the error is recorded and the parser skips to the next tag boundary (`<`),
+/// so that all mistakes in a template can be reported in one pass
+struct Recover<'a> {
+    /// the whole template input, use to count the span of each error
+    origin: &'a str,
+    poll: Arc<RwLock<Polls>>,
+    /// names of the tags which are opened but not closed yet
+    opened: Vec<String>,
+    errors: Vec<Error>,
+}
+
+impl<'a> Recover<'a> {
+    fn new(origin: &'a str, poll: Arc<RwLock<Polls>>) -> Self {
+        Self {
+            origin,
+            poll,
+            opened: vec![],
+            errors: vec![],
+        }
+    }
+    /// record error at `at`, if the error already has span, keep it
+    fn error(&mut self, err: Error, at: &'a str) {
+        self.errors
+            .push(err.or_span(Span::from_remain(self.origin, at.trim_start())));
+    }
+    fn error_msg(&mut self, msg: &str, at: &'a str) {
+        self.error(ParseError::template(msg).into(), at);
+    }
+    /// skip to the next `<` (the first char is always skipped)
+    fn skip_to_tag(input: &'a str) -> &'a str {
+        let input = input.trim_start();
+        match input.get(1..).and_then(|s| s.find('<')) {
+            Some(index) => &input[index + 1..],
+            None => &input[input.len()..],
+        }
+    }
+    /// skip a broken property, stop at whitespace, `>`, `/>` or `<` which is not in a string
+    fn skip_property(input: &'a str) -> &'a str {
+        let mut quote = None;
+        for (index, c) in input.char_indices() {
+            match quote {
+                Some(q) => {
+                    if c == q {
+                        quote = None;
+                    }
+                }
+                None => match c {
+                    '"' | '\'' => quote = Some(c),
+                    '>' | '<' => return &input[index..],
+                    '/' if input[index..].starts_with(SELF_END_SIGN) => return &input[index..],
+                    c if c.is_whitespace() => return &input[index..],
+                    _ => {}
+                },
+            }
+        }
+        &input[input.len()..]
+    }
+    /// ## parse properties until `>` or `/>`
+    /// broken properties are recorded and skipped, return `None` as close type if the tag is not closed
+    fn properties(
+        &mut self,
+        mut input: &'a str,
+    ) -> (&'a str, Vec<(PropKey, Value)>, Option<CloseType>) {
+        let mut props = vec![];
+        loop {
+            input = input.trim_start();
+            if let Some((remain, close_type)) = parse_close_type(input) {
+                return (remain, props, Some(close_type));
+            }
+            if input.is_empty() || input.starts_with('<') {
+                return (input, props, None);
+            }
+            match parse_property(input) {
+                Ok((remain, prop)) => {
+                    props.push(prop);
+                    input = remain;
+                }
+                Err(_) => {
+                    let remain = Self::skip_property(input);
+                    let broken = &input[..input.len() - remain.len()];
+                    self.error_msg(&format!("invalid property `{}`", broken), input);
+                    input = remain;
+                }
+            }
+        }
+    }
+    /// ## parse a tag and its children
+    /// return `None` if the tag start can not be parsed at all, the input is skipped to the next tag
+    fn tag(
+        &mut self,
+        input: &'a str,
+        root: bool,
+        iter: &mut Option<SugarIter>,
+    ) -> (&'a str, Option<Template>) {
+        // [parse comment if exist] ------------------------------------------------------------------------------------
+        let (input, comments) = parse_comment(input).unwrap_or((input, vec![]));
+        // [parse tag start] -------------------------------------------------------------------------------------------
+        let tag_start = input.trim_start();
+        let (input, name) = match parse_tag_open(tag_start) {
+            Ok(res) => res,
+            Err(_) => {
+                self.error_msg("expect a tag, such as `<view>`", tag_start);
+                return (Self::skip_to_tag(tag_start), None);
+            }
+        };
+        let mut template = Template::new(name);
+        template
+            .span
+            .replace(Span::from_remain(self.origin, tag_start));
+        template.root = root;
+        if !comments.is_empty() {
+            template.comments.replace(comments);
+        }
+        let (input, props, close_type) = self.properties(input);
+        let close_type = close_type.unwrap_or_else(|| {
+            self.error_msg(
+                &format!("tag `<{}` is not closed, expect `>` or `/>`", name),
+                tag_start,
+            );
+            CloseType::SelfClosed
+        });
+        if !props.is_empty() {
+            template.props.replace(HashMap::from_iter(props));
+        }
+        match template.after_prop_parse(Arc::clone(&self.poll), iter.as_ref()) {
+            Ok(sugar_iter) => *iter = sugar_iter,
+            Err(e) => {
+                self.error(e, tag_start);
+                *iter = None;
+            }
+        }
+        // [parse children] --------------------------------------------------------------------------------------------
+        let input = match close_type {
+            CloseType::SelfClosed => input,
+            CloseType::Usual => {
+                self.opened.push(template.name.to_string());
+                let mut texts = vec![];
+                let (input, children) = self.children(input, &template, iter.clone(), &mut texts);
+                self.opened.pop();
+
+                let errors = set_children(&mut template, children, texts, &self.poll);
+                self.errors.extend(errors);
+                input
+            }
+        };
+
+        (input, Some(template))
+    }
+    /// ## parse children until the close tag of `parent`
+    /// text children are collected into `texts`, See [`Template::push_text`]
+    fn children(
+        &mut self,
+        mut input: &'a str,
+        parent: &Template,
+        mut iter: Option<SugarIter>,
+        texts: &mut Vec<&'a str>,
+    ) -> (&'a str, Vec<Template>) {
+        let mut children = vec![];
+        let not_closed =
+            |name: &str| format!("tag `<{}>` is not closed, expect `</{}>`", name, name);
+        loop {
+            // comments belong to the next child, so only peek here
+            let peek = parse_comment(input)
+                .map_or(input, |(remain, _)| remain)
+                .trim_start();
+            if peek.is_empty() {
+                let span = parent.span.clone().unwrap_or_default();
+                self.errors.push(
+                    Error::from(ParseError::template(&not_closed(&parent.name))).or_span(span),
+                );
+                return (peek, children);
+            }
+            if peek.starts_with(END_START_SIGN) {
+                match parse_end_tag_common(peek) {
+                    Ok((remain, (_, name))) if name == parent.name => {
+                        return (remain, children);
+                    }
+                    Ok((remain, (_, name))) => {
+                        if self.opened.iter().any(|opened| opened == name) {
+                            // close tag of the ancestor, leave it to the ancestor
+                            self.error_msg(&not_closed(&parent.name), peek);
+                            return (peek, children);
+                        }
+                        self.error_msg(&format!("unexpected close tag `</{}>`", name), peek);
+                        input = remain;
+                    }
+                    Err(_) => match preceded(trim(tag(END_START_SIGN)), parse_tag_name)(peek) {
+                        Ok((remain, name)) if name == parent.name => {
+                            self.error_msg(
+                                &format!("close tag `</{}` is not complete, expect `>`", name),
+                                peek,
+                            );
+                            return (remain, children);
+                        }
+                        _ => {
+                            self.error_msg("invalid close tag", peek);
+                            input = Self::skip_to_tag(peek);
+                        }
+                    },
+                }
+                continue;
+            }
+            if let Ok((remain, text)) = parse_text(peek) {
+                texts.push(text);
+                input = remain;
+                continue;
+            }
+
+            let (remain, child) = self.tag(input, false, &mut iter);
+            if let Some(child) = child {
+                children.push(child);
+            }
+            input = remain;
         }
-        Err(e) => Err(ParseError::template(&e.to_string()).into()),
     }
 }
 
+/// ## parse template with error recovery Ⓜ️
+/// parse as much as possible and collect all errors (with span counted from `input`)
+/// ### return
+/// - the partial template tree, `None` if no tag can be parsed
+/// - all errors, empty means the template is valid
+pub fn parse_recover(
+    input: &str,
+    poll: Arc<RwLock<Polls>>,
+    root: bool,
+) -> (Option<Template>, Vec<Error>) {
+    let mut recover = Recover::new(input, poll);
+    let mut remain = input;
+    let mut template = None;
+    while template.is_none() && !remain.trim().is_empty() {
+        let (next, res) = recover.tag(remain, root, &mut None);
+        template = res;
+        remain = next;
+    }
+    if !remain.trim().is_empty() {
+        recover
+            .errors
+            .push(remain_err(input, remain, ParseType::Template));
+    }
+    (template, recover.errors)
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum CloseType {
     SelfClosed,
     Usual,
 }
+
+#[cfg(test)]
+mod test_template {
+    use std::sync::{Arc, RwLock};
+
+    use crate::{
+        value::{Bind, Value},
+        VirtualFor,
+    };
+
+    use super::{parse, parse_recover};
+
+    #[test]
+    fn recover_all_errors() {
+        let input = r#"<component name="Hello">
+    <view>
+        <label text=hello />
+        <button text="'ok'">
+    </view>
+    hello
+    <label else />
+</component>"#;
+        let poll = Arc::new(RwLock::new(Default::default()));
+        assert!(parse(input, Arc::clone(&poll), true).is_err());
+
+        let (template, errors) = parse_recover(input, poll, true);
+        let template = template.expect("partial template");
+        assert_eq!(template.name, "component");
+        assert_eq!(template.children.as_ref().map(|c| c.len()), Some(2));
+        assert!(errors.iter().all(|e| e.span().is_some()));
+        // errors of the children come first, the text mixed with tags is checked when the component is closed
+        let errors = errors.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+        assert_eq!(errors.len(), 4);
+        assert!(errors[0].contains("invalid property `text=hello`"));
+        assert!(errors[1].contains("tag `<button>` is not closed, expect `</button>`"));
+        assert!(errors[2].contains("else sugar sync must be after `if` or `else_if`"));
+        assert!(errors[3].contains("unexpected content `hello`, text can not be mixed with tags"));
+    }
+
+    #[test]
+    fn recover_valid() {
+        let input = r#"<component name="Hello"><view><label text="'a'" /></view></component>"#;
+        let poll = Arc::new(RwLock::new(Default::default()));
+        let (template, errors) = parse_recover(input, poll, true);
+        assert!(template.is_some());
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn class_bind() {
+        use crate::value::{Bind, Value};
+
+        let input = r#"<component name="Hello">
+    <button id="btn" class="btn" :class="{ active: is_active, hidden: !state.visible }"></button>
+    <label id="lb" :class="lb_class"></label>
+</component>"#;
+        let poll = Arc::new(RwLock::new(Default::default()));
+        let template = parse(input, Arc::clone(&poll), true).unwrap();
+        let children = template.children.unwrap();
+        let btn = &children[0];
+        assert!(btn.class.is_some());
+        let class = btn.binds.as_ref().unwrap().values().next().unwrap();
+        let Value::Bind(bind @ Bind::Class(items)) = class else {
+            panic!("should be class map bind");
+        };
+        assert_eq!(items.len(), 2);
+        assert!(items[1].not);
+        assert_eq!(bind.idents(), vec!["is_active", "state"]);
+        assert_eq!(
+            bind.to_string(),
+            "{active: is_active, hidden: !state.visible}"
+        );
+
+        let poll = poll.read().unwrap();
+        let binds = poll.binds.as_ref().unwrap();
+        // class map is pooled by the root field, `set_state` re-applies the class
+        for ident in ["is_active", "state", "lb_class"] {
+            assert_eq!(binds[ident][0].prop.as_str(), "class");
+        }
+
+        let poll = Arc::new(RwLock::new(Default::default()));
+        assert!(parse(
+            r#"<component name="Hello"><view :class="{ active }"></view></component>"#,
+            poll,
+            true
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn style_bind() {
+        use crate::value::{Bind, Value};
+
+        let input = r#"<component name="Hello">
+    <label id="lb" :style="{ color: text_color, font_size: size.font }"></label>
+    <button id="btn" :style="btn_style"></button>
+</component>"#;
+        let poll = Arc::new(RwLock::new(Default::default()));
+        let template = parse(input, Arc::clone(&poll), true).unwrap();
+        let children = template.children.unwrap();
+        let style = children[0].binds.as_ref().unwrap().values().next().unwrap();
+        let Value::Bind(bind @ Bind::Style(items)) = style else {
+            panic!("should be style map bind");
+        };
+        assert_eq!(items[1].prop, "font_size");
+        assert_eq!(bind.idents(), vec!["text_color", "size"]);
+        assert_eq!(
+            bind.to_string(),
+            "{color: text_color, font_size: size.font}"
+        );
+        let style = children[1].binds.as_ref().unwrap().values().next().unwrap();
+        assert!(matches!(style, Value::Bind(Bind::Normal(_))));
+
+        let poll = poll.read().unwrap();
+        let binds = poll.binds.as_ref().unwrap();
+        // style map is pooled by the root field, `set_size` sets `font_size`
+        for ident in ["text_color", "size", "btn_style"] {
+            assert_eq!(binds[ident][0].prop.as_str(), "style");
+        }
+
+        for input in [
+            r#"<component name="Hello"><label :style="{ color: !dark }"></label></component>"#,
+            r#"<component name="Hello"><label style="color: red"></label></component>"#,
+        ] {
+            let poll = Arc::new(RwLock::new(Default::default()));
+            assert!(parse(input, poll, true).is_err());
+        }
+    }
+
+    #[test]
+    fn slot() {
+        let input = r#"<component name="Hello">
+    <my_card id="card">
+        <view slot="header"></view>
+        <label text="'body'"></label>
+        <label slot="footer" slot_scope="scope" :text="scope.title"></label>
+    </my_card>
+    <slot name="footer" :title="title"></slot>
+</component>"#;
+        let poll = Arc::new(RwLock::new(Default::default()));
+        let template = parse(input, poll, true).unwrap();
+        let children = template.children.unwrap();
+        let card = children[0].children.as_ref().unwrap();
+        assert_eq!(card[0].slot.as_deref(), Some("header"));
+        assert!(card[1].slot.is_none());
+        assert_eq!(card[2].slot_scope.as_deref(), Some("scope"));
+        assert!(card[0].slot_scope.is_none());
+        assert!(children[1].is_slot());
+        assert!(children[1].binds.is_some());
+
+        let poll = Arc::new(RwLock::new(Default::default()));
+        assert!(parse(
+            r#"<component name="Hello"><my_card><view :slot="name"></view></my_card></component>"#,
+            poll,
+            true
+        )
+        .is_err());
+        let poll = Arc::new(RwLock::new(Default::default()));
+        assert!(parse(
+            r#"<component name="Hello"><my_card><view :slot_scope="s"></view></my_card></component>"#,
+            poll,
+            true
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn for_key() {
+        let input = r#"<component name="Hello">
+    <label :key="item.id" :for="item in list" :text="item.name"></label>
+</component>"#;
+        let poll = Arc::new(RwLock::new(Default::default()));
+        let template = parse(input, poll, true).unwrap();
+        let label = &template.children.unwrap()[0];
+        assert_eq!(label.key.as_ref().unwrap().to_string(), "item.id");
+        let binds = label.binds.as_ref().unwrap();
+        assert!(binds.keys().all(|k| k.name != "key"));
+
+        for input in [
+            r#"<component name="Hello"><label :key="item.id"></label></component>"#,
+            r#"<component name="Hello"><label key="id" :for="item in list"></label></component>"#,
+        ] {
+            let poll = Arc::new(RwLock::new(Default::default()));
+            assert!(parse(input, poll, true).is_err());
+        }
+    }
+
+    #[test]
+    fn virtual_for() {
+        let input = r#"<component name="Hello">
+    <view id="rows" height="300">
+        <label :for="row in rows" virtual="48" overscan="4" :text="row"></label>
+        <label :for="row in rows" virtual :text="row"></label>
+    </view>
+</component>"#;
+        let poll = Arc::new(RwLock::new(Default::default()));
+        let template = parse(input, poll, true).unwrap();
+        let rows = template.children.unwrap()[0].children.clone().unwrap();
+        let fixed = rows[0].virtual_for.unwrap();
+        assert_eq!((fixed.height, fixed.overscan), (Some(48.0), 4));
+        assert!(rows[0].props.is_none());
+        let measured = rows[1].virtual_for.unwrap();
+        assert_eq!(
+            (measured.height, measured.overscan),
+            (None, VirtualFor::DEFAULT_OVERSCAN)
+        );
+
+        for input in [
+            r#"<component name="Hello"><label virtual></label></component>"#,
+            r#"<component name="Hello"><label :for="row in rows" overscan="4"></label></component>"#,
+            r#"<component name="Hello"><label :for="row in rows" virtual="-1"></label></component>"#,
+        ] {
+            let poll = Arc::new(RwLock::new(Default::default()));
+            assert!(parse(input, poll, true).is_err());
+        }
+    }
+
+    #[test]
+    fn interpolation() {
+        let input = r#"<component name="Hello">
+    <view>
+        <label id="welcome" text="'Welcome, {{ user.name }}!'"></label>
+        <label id="inbox">You have {{ count }} messages, {{ user.name }}</label>
+        <label id="plain">Hello</label>
+    </view>
+</component>"#;
+        let poll = Arc::new(RwLock::new(Default::default()));
+        let template = parse(input, Arc::clone(&poll), true).unwrap();
+        let labels = template.children.unwrap()[0].children.clone().unwrap();
+        for label in &labels[..2] {
+            let binds = label.binds.as_ref().unwrap();
+            let (key, value) = binds.iter().next().unwrap();
+            assert!(key.is_bind() && key.name == "text");
+            assert!(matches!(value, Value::Interpolation(_)));
+            assert!(label.props.is_none());
+        }
+        let plain = labels[2].props.as_ref().unwrap();
+        assert_eq!(
+            plain.values().next(),
+            Some(&Value::String("Hello".to_string()))
+        );
+        // pooled by the root ident
+        let poll = poll.read().unwrap();
+        let binds = poll.binds.as_ref().unwrap();
+        assert_eq!(binds["user"].len(), 2);
+        assert_eq!(binds["count"][0].id, "inbox");
+
+        for input in [
+            r#"<component name="Hello"><label text="'Hi {{ name'"></label></component>"#,
+            r#"<component name="Hello"><label text="'Hi'">{{ name }}</label></component>"#,
+            r#"<component name="Hello"><view>Hi<label></label></view></component>"#,
+        ] {
+            let poll = Arc::new(RwLock::new(Default::default()));
+            assert!(parse(input, Arc::clone(&poll), true).is_err());
+            let poll = Arc::new(RwLock::new(Default::default()));
+            assert_eq!(parse_recover(input, poll, true).1.len(), 1);
+        }
+    }
+
+    #[test]
+    fn expr() {
+        let input = r#"<component name="Hello">
+    <label id="a" :if="count > 0 && !loading" :text="count.to_string()"></label>
+    <label id="b" :else="true"></label>
+</component>"#;
+        let poll = Arc::new(RwLock::new(Default::default()));
+        let template = parse(input, Arc::clone(&poll), true).unwrap();
+        let label = &template.children.unwrap()[0];
+        let (_, value) = label.binds.as_ref().unwrap().iter().next().unwrap();
+        assert!(matches!(value, Value::Bind(Bind::Expr(_))));
+        // pooled by every ident of the expression
+        let poll = poll.read().unwrap();
+        let binds = poll.binds.as_ref().unwrap();
+        assert_eq!(binds["count"].len(), 3);
+        assert_eq!(binds["loading"].len(), 2);
+
+        let input = r#"<component name="Hello"><label :if="a < b < c"></label></component>"#;
+        let poll = Arc::new(RwLock::new(Default::default()));
+        assert!(parse(input, poll, true).is_err());
+    }
+}
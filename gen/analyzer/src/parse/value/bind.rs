@@ -12,7 +12,7 @@ use nom::multi::{many1, separated_list0};
 use nom::sequence::{pair, preceded, separated_pair};
 use nom::{bytes::complete::tag, sequence::delimited, IResult};
 
-use super::Function;
+use super::{Expr, Function};
 
 /// # Bind Value
 /// - in template: `:bind="A"` A is a bind ident
@@ -23,6 +23,12 @@ use super::Function;
 /// in template: `:bind="hello()"` See [`Function`]
 /// ## For Bind
 /// in template: `:for="(index, item) in iter_ident"` See [`For`]
+/// ## Class Bind
+/// in template: `:class="{ active: is_active, hidden: !visible }"` See [`ClassItem`]
+/// ## Style Bind
+/// in template: `:style="{ color: text_color }"` See [`StyleItem`]
+/// ## Expr Bind
+/// in template: `:if="count > 0 && !loading"`, `:text="items.len().to_string()"` See [`Expr`]
 /// ## Test
 /// See [test_bind](tests/src/parser/value/bind.rs)
 #[derive(Debug, Clone, PartialEq)]
@@ -33,14 +39,46 @@ pub enum Bind {
     Fn(Function),
     /// for loop bind
     For(For),
+    /// class map bind, only for `:class`
+    Class(Vec<ClassItem>),
+    /// style map bind, only for `:style`
+    Style(Vec<StyleItem>),
+    /// inline expression, it is only used when the bind is not a ident or function
+    Expr(Expr),
 }
 
 impl Bind {
+    /// ident of the bind, for class map it is the first bind ident, See [`Bind::idents`]
     pub fn ident(&self) -> String {
         match self {
             Bind::Normal(n) => Ident::fmt_idents(n),
             Bind::For(f) => f.ident(),
             Bind::Fn(function) => function.ident().to_string(),
+            Bind::Class(items) => items
+                .first()
+                .map(|item| Ident::fmt_idents(&item.ident))
+                .unwrap_or_default(),
+            Bind::Style(items) => items
+                .first()
+                .map(|item| Ident::fmt_idents(&item.ident))
+                .unwrap_or_default(),
+            Bind::Expr(expr) => expr.to_string(),
+        }
+    }
+    /// all idents of the bind, class map, style map and expression can bind more than one ident (without repeat)
+    /// - class map and style map: root idents (`!state.visible` => `state`), the setter of the field applies them
+    pub fn idents(&self) -> Vec<String> {
+        let roots = |mut idents: Vec<String>, ident: &Vec<Ident>| {
+            if !idents.contains(&ident[0].name) {
+                idents.push(ident[0].name.to_string());
+            }
+            idents
+        };
+        match self {
+            Bind::Class(items) => items.iter().map(|item| &item.ident).fold(vec![], roots),
+            Bind::Style(items) => items.iter().map(|item| &item.ident).fold(vec![], roots),
+            Bind::Expr(expr) => expr.idents(),
+            _ => vec![self.ident()],
         }
     }
     pub fn is(&self, s: &str) -> bool {
@@ -48,6 +86,38 @@ impl Bind {
             Bind::Normal(n) => n[0].name == s,
             Bind::For(f) => f.iter_ident[0].name == s,
             Bind::Fn(function) => function.name == s,
+            Bind::Class(items) => items.iter().any(|item| item.ident[0].name == s),
+            Bind::Style(items) => items.iter().any(|item| item.ident[0].name == s),
+            Bind::Expr(expr) => expr.idents().iter().any(|ident| ident == s),
+        }
+    }
+    pub fn get_class(&self) -> Option<&Vec<ClassItem>> {
+        match self {
+            Bind::Class(items) => Some(items),
+            _ => None,
+        }
+    }
+    pub fn get_style(&self) -> Option<&Vec<StyleItem>> {
+        match self {
+            Bind::Style(items) => Some(items),
+            _ => None,
+        }
+    }
+    /// ## convert the bind of `:style`
+    /// `{...}` is parsed as class map at first, it is converted to style map here, field bind is kept
+    pub fn into_style(self) -> Result<Self, Error> {
+        match self {
+            Bind::Class(items) => items
+                .into_iter()
+                .map(StyleItem::try_from)
+                .collect::<Result<Vec<_>, Error>>()
+                .map(Bind::Style),
+            Bind::Normal(_) | Bind::Style(_) => Ok(self),
+            _ => Err(ParseError::template(&format!(
+                "`:style` should bind a field or a style map, but get: {}",
+                self
+            ))
+            .into()),
         }
     }
     pub fn get_for(&self) -> Option<&For> {
@@ -81,6 +151,25 @@ impl Display for Bind {
             Bind::Normal(n) => f.write_str(&n.iter().map(|i| i.to_string()).collect::<String>()),
             Bind::For(for_bind) => for_bind.fmt(f),
             Bind::Fn(function) => function.fmt(f),
+            Bind::Class(items) => write!(
+                f,
+                "{{{}}}",
+                items
+                    .iter()
+                    .map(|item| item.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Bind::Style(items) => write!(
+                f,
+                "{{{}}}",
+                items
+                    .iter()
+                    .map(|item| item.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Bind::Expr(expr) => expr.fmt(f),
         }
     }
 }
@@ -89,6 +178,9 @@ impl FromStr for Bind {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(map) = s.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+            return ClassItem::parse_map(map).map(Bind::Class);
+        }
         match s.parse::<For>() {
             Ok(f) => {
                 return Ok(Bind::For(f));
@@ -102,12 +194,114 @@ impl FromStr for Bind {
                     return Ok(Bind::Normal(normal));
                 }
 
-                return Err(ParseError::template(&format!("parse bind: {} failed", s)).into());
+                return Expr::parse(s).map(Bind::Expr);
             }
         }
     }
 }
 
+/// # Class Item
+/// item of class map bind: `:class="{ active: is_active, hidden: !visible }"`
+/// - `active: is_active` => the class `active` is on when `is_active` is true
+/// - `hidden: !visible` => the class `hidden` is on when `visible` is false
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassItem {
+    pub class: String,
+    /// `!` before the bind ident
+    pub not: bool,
+    pub ident: Vec<Ident>,
+}
+
+impl ClassItem {
+    /// parse the inner of `{...}`, items are split by `,`
+    pub fn parse_map(s: &str) -> Result<Vec<Self>, Error> {
+        let items = s
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(ClassItem::from_str)
+            .collect::<Result<Vec<_>, Error>>()?;
+        if items.is_empty() {
+            return Err(ParseError::template("class map bind can not be empty").into());
+        }
+        Ok(items)
+    }
+}
+
+impl FromStr for ClassItem {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || -> Error {
+            ParseError::template(&format!(
+                "parse class bind: `{}` failed, expect `class_name: bool_ident`",
+                s
+            ))
+            .into()
+        };
+        let (class, expr) = s.split_once(':').ok_or_else(err)?;
+        let class = class.trim();
+        if class.is_empty() || !class.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            return Err(err());
+        }
+        let expr = expr.trim();
+        let (not, expr) = match expr.strip_prefix('!') {
+            Some(expr) => (true, expr.trim()),
+            None => (false, expr),
+        };
+        Ok(Self {
+            class: class.to_string(),
+            not,
+            ident: Ident::parse_idents(expr).map_err(|_| err())?,
+        })
+    }
+}
+
+impl Display for ClassItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {}{}",
+            self.class,
+            if self.not { "!" } else { "" },
+            Ident::fmt_idents(&self.ident)
+        )
+    }
+}
+
+/// # Style Item
+/// item of style map bind: `:style="{ color: text_color, font_size: size }"`
+/// - `color: text_color` => the prop `color` of the widget is set by `text_color`
+#[derive(Debug, Clone, PartialEq)]
+pub struct StyleItem {
+    pub prop: String,
+    pub ident: Vec<Ident>,
+}
+
+impl TryFrom<ClassItem> for StyleItem {
+    type Error = Error;
+
+    fn try_from(value: ClassItem) -> Result<Self, Self::Error> {
+        if value.not {
+            return Err(ParseError::template(&format!(
+                "style bind: `{}` can not use `!`, expect `prop: ident`",
+                value
+            ))
+            .into());
+        }
+        Ok(Self {
+            prop: value.class,
+            ident: value.ident,
+        })
+    }
+}
+
+impl Display for StyleItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.prop, Ident::fmt_idents(&self.ident))
+    }
+}
+
 /// # For loop bind
 /// ## format:
 /// 1. `:for="(index, item) in iter_ident"`
@@ -0,0 +1,312 @@
+use std::fmt::Display;
+
+use gen_utils::{
+    error::{Error, ParseError},
+    parser::trim,
+};
+use nom::{
+    branch::alt,
+    bytes::complete::{take_until, take_while, take_while1},
+    character::complete::{char, digit1},
+    combinator::{map, opt, recognize},
+    multi::{many0, separated_list0},
+    sequence::{delimited, pair, preceded},
+    IResult,
+};
+
+use super::Ident;
+
+/// # Bind Expression
+/// restricted inline expression in `:bind` and `:if`, the fields used in the expression are tracked
+/// (See [`Expr::idents`]) so that the setters of them update the widget
+/// - literal: `1`, `0.5`, `true`, `'text'`
+/// - field and method access: `user.name`, `items.len().to_string()`
+/// - unary: `!loading`, `-offset`
+/// - arithmetic: `+ - * / %`
+/// - comparison: `== != < <= > >=`
+/// - boolean logic: `&& ||`
+/// - parentheses: `(a + b) * 2`
+///
+/// free function calls are not expressions, use `:bind="do_fn()"` (See [`super::Function`]) instead
+/// ## Test
+/// See [test_expr](expr.rs)
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    /// literal in rust form, template string `'text'` => `"text"`
+    Lit(String),
+    /// field or variable: `count`, `user.name`
+    Path(Vec<Ident>),
+    /// field of the method call result: `user.get().name`
+    Field(Box<Expr>, String),
+    /// method call: `items.len()`, `name.starts_with('a')`
+    Method(Box<Expr>, String, Vec<Expr>),
+    Unary(UnaryOp, Box<Expr>),
+    Binary(Box<Expr>, BinaryOp, Box<Expr>),
+    Paren(Box<Expr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UnaryOp {
+    Not,
+    Neg,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BinaryOp {
+    Or,
+    And,
+    Eq,
+    Ne,
+    Le,
+    Ge,
+    Lt,
+    Gt,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+impl BinaryOp {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BinaryOp::Or => "||",
+            BinaryOp::And => "&&",
+            BinaryOp::Eq => "==",
+            BinaryOp::Ne => "!=",
+            BinaryOp::Le => "<=",
+            BinaryOp::Ge => ">=",
+            BinaryOp::Lt => "<",
+            BinaryOp::Gt => ">",
+            BinaryOp::Add => "+",
+            BinaryOp::Sub => "-",
+            BinaryOp::Mul => "*",
+            BinaryOp::Div => "/",
+            BinaryOp::Rem => "%",
+        }
+    }
+}
+
+impl Expr {
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        match trim(or)(s) {
+            Ok((remain, expr)) if remain.is_empty() => Ok(expr),
+            _ => Err(ParseError::template(&format!(
+                "parse bind expression: `{}` failed, only literals, fields, methods and operators are supported",
+                s
+            ))
+            .into()),
+        }
+    }
+    /// root idents (fields or loop variables) used in the expression without repeat, `a.b > c` => `[a, c]`
+    pub fn idents(&self) -> Vec<String> {
+        let mut idents = vec![];
+        self.fmt_with(&mut |path: &Vec<Ident>| {
+            if !idents.contains(&path[0].name) {
+                idents.push(path[0].name.to_string());
+            }
+            String::new()
+        });
+        idents
+    }
+    /// ## format the expression as rust code
+    /// `path` converts the field or variable, such as `count` => `self.count` in the setter
+    pub fn fmt_with<F>(&self, path: &mut F) -> String
+    where
+        F: FnMut(&Vec<Ident>) -> String,
+    {
+        match self {
+            Expr::Lit(lit) => lit.to_string(),
+            Expr::Path(idents) => path(idents),
+            Expr::Field(expr, name) => format!("{}.{}", expr.fmt_with(path), name),
+            Expr::Method(expr, name, args) => {
+                let expr = expr.fmt_with(path);
+                let args = args
+                    .iter()
+                    .map(|arg| arg.fmt_with(path))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("{}.{}({})", expr, name, args)
+            }
+            Expr::Unary(op, expr) => {
+                let op = match op {
+                    UnaryOp::Not => "!",
+                    UnaryOp::Neg => "-",
+                };
+                format!("{}{}", op, expr.fmt_with(path))
+            }
+            Expr::Binary(left, op, right) => format!(
+                "{} {} {}",
+                left.fmt_with(path),
+                op.as_str(),
+                right.fmt_with(path)
+            ),
+            Expr::Paren(expr) => format!("({})", expr.fmt_with(path)),
+        }
+    }
+}
+
+impl Display for Expr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.fmt_with(&mut Ident::fmt_idents))
+    }
+}
+
+// [parser] 优先级从低到高: `||` < `&&` < 比较 < 加减 < 乘除 < 一元 < 后缀(字段, 方法) ---------------------------------
+
+/// left associative binary expression, `next` is the operand with higher precedence
+fn binary<'a>(
+    input: &'a str,
+    next: fn(&str) -> IResult<&str, Expr>,
+    ops: &[BinaryOp],
+) -> IResult<&'a str, Expr> {
+    let (mut input, mut expr) = next(input)?;
+    loop {
+        let rest = input.trim_start();
+        let Some(op) = ops.iter().find(|op| rest.starts_with(op.as_str())) else {
+            return Ok((input, expr));
+        };
+        match next(&rest[op.as_str().len()..]) {
+            Ok((remain, right)) => {
+                expr = Expr::Binary(Box::new(expr), *op, Box::new(right));
+                input = remain;
+            }
+            Err(_) => return Ok((input, expr)),
+        }
+    }
+}
+
+fn or(input: &str) -> IResult<&str, Expr> {
+    binary(input, and, &[BinaryOp::Or])
+}
+
+fn and(input: &str) -> IResult<&str, Expr> {
+    binary(input, comparison, &[BinaryOp::And])
+}
+
+/// comparison is not chained: `a < b < c` is invalid
+fn comparison(input: &str) -> IResult<&str, Expr> {
+    let ops = [
+        BinaryOp::Eq,
+        BinaryOp::Ne,
+        BinaryOp::Le,
+        BinaryOp::Ge,
+        BinaryOp::Lt,
+        BinaryOp::Gt,
+    ];
+    let (input, left) = additive(input)?;
+    let rest = input.trim_start();
+    match ops.iter().find(|op| rest.starts_with(op.as_str())) {
+        Some(op) => {
+            let (input, right) = additive(&rest[op.as_str().len()..])?;
+            Ok((input, Expr::Binary(Box::new(left), *op, Box::new(right))))
+        }
+        None => Ok((input, left)),
+    }
+}
+
+fn additive(input: &str) -> IResult<&str, Expr> {
+    binary(input, multiplicative, &[BinaryOp::Add, BinaryOp::Sub])
+}
+
+fn multiplicative(input: &str) -> IResult<&str, Expr> {
+    binary(input, unary, &[BinaryOp::Mul, BinaryOp::Div, BinaryOp::Rem])
+}
+
+fn unary(input: &str) -> IResult<&str, Expr> {
+    alt((
+        map(preceded(trim(char('!')), unary), |expr| {
+            Expr::Unary(UnaryOp::Not, Box::new(expr))
+        }),
+        map(preceded(trim(char('-')), unary), |expr| {
+            Expr::Unary(UnaryOp::Neg, Box::new(expr))
+        }),
+        postfix,
+    ))(input)
+}
+
+/// field access and method call after the primary expression
+fn postfix(input: &str) -> IResult<&str, Expr> {
+    let (input, expr) = primary(input)?;
+    let (input, calls) = many0(pair(
+        preceded(char('.'), name),
+        opt(delimited(
+            trim(char('(')),
+            separated_list0(trim(char(',')), or),
+            trim(char(')')),
+        )),
+    ))(input)?;
+
+    let expr = calls
+        .into_iter()
+        .fold(expr, |expr, (name, args)| match (expr, args) {
+            (expr, Some(args)) => Expr::Method(Box::new(expr), name.to_string(), args),
+            // `user.name` is still a path, the root ident is the field
+            (Expr::Path(mut idents), None) => {
+                idents.push(Ident::dot(name));
+                Expr::Path(idents)
+            }
+            (expr, None) => Expr::Field(Box::new(expr), name.to_string()),
+        });
+    Ok((input, expr))
+}
+
+fn primary(input: &str) -> IResult<&str, Expr> {
+    trim(alt((
+        map(delimited(char('('), or, char(')')), |expr| {
+            Expr::Paren(Box::new(expr))
+        }),
+        map(
+            delimited(char('\''), take_until("'"), char('\'')),
+            |s: &str| Expr::Lit(format!("{:?}", s)),
+        ),
+        map(
+            recognize(pair(digit1, opt(pair(char('.'), digit1)))),
+            |n: &str| Expr::Lit(n.to_string()),
+        ),
+        map(name, |name| match name {
+            "true" | "false" => Expr::Lit(name.to_string()),
+            _ => Expr::Path(vec![Ident::new(name)]),
+        }),
+    )))(input)
+}
+
+/// ident of field, variable or method, can not start with digit
+fn name(input: &str) -> IResult<&str, &str> {
+    recognize(pair(
+        take_while1(|c: char| c == '_' || c.is_alphabetic()),
+        take_while(|c: char| c == '_' || c.is_alphanumeric()),
+    ))(input)
+}
+
+#[cfg(test)]
+mod test_expr {
+    use super::{BinaryOp, Expr};
+
+    #[test]
+    fn parse() {
+        let expr = Expr::parse("count > 0 && !loading").unwrap();
+        assert!(matches!(expr, Expr::Binary(_, BinaryOp::And, _)));
+        assert_eq!(expr.to_string(), "count > 0 && !loading");
+        assert_eq!(expr.idents(), vec!["count", "loading"]);
+
+        let expr = Expr::parse("items.len().to_string()").unwrap();
+        assert!(matches!(expr, Expr::Method(..)));
+        assert_eq!(expr.idents(), vec!["items"]);
+
+        // precedence and paths
+        let expr = Expr::parse("(a.b + 2) * c - d % 3 >= 1.5 || name == 'x'").unwrap();
+        assert_eq!(
+            expr.to_string(),
+            "(a.b + 2) * c - d % 3 >= 1.5 || name == \"x\""
+        );
+        assert_eq!(expr.idents(), vec!["a", "c", "d", "name"]);
+        let expr = expr.fmt_with(&mut |path| format!("self.{}", super::Ident::fmt_idents(path)));
+        assert!(expr.starts_with("(self.a.b + 2) * self.c"));
+
+        for input in ["a < b < c", "a +", "do_fn(a) && b", "a & b", "(a", "1a"] {
+            assert!(Expr::parse(input).is_err(), "{}", input);
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use std::fmt::Display;
+
+use gen_utils::error::{Error, ParseError};
+
+use super::Ident;
+
+/// # Interpolation
+/// mustache-style interpolation in normal string props and text children, the string is rebuilt when any
+/// of the bound idents changes
+/// - prop: `text="'Welcome, {{ name }}!'"`
+/// - text child: `<label>You have {{ inbox.count }} messages</label>`
+/// ## Test
+/// See [test_interpolation](interpolation.rs)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Interpolation(pub Vec<Segment>);
+
+/// segment of [`Interpolation`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment {
+    /// literal text
+    Text(String),
+    /// bound ident in `{{ }}`, such as `a.b` or `list[0]`
+    Bind(Vec<Ident>),
+}
+
+impl Interpolation {
+    pub const START: &'static str = "{{";
+    pub const END: &'static str = "}}";
+
+    /// the string has bound segments or not
+    pub fn is(s: &str) -> bool {
+        s.contains(Self::START)
+    }
+
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        let err = |msg: String| -> Error { ParseError::template(&msg).into() };
+        let mut segments = vec![];
+        let mut remain = s;
+        while let Some(start) = remain.find(Self::START) {
+            if start > 0 {
+                segments.push(Segment::Text(remain[..start].to_string()));
+            }
+            let bind = &remain[start + Self::START.len()..];
+            let end = bind.find(Self::END).ok_or_else(|| {
+                err(format!(
+                    "interpolation `{}` is not closed, expect `}}}}`",
+                    s
+                ))
+            })?;
+            let ident = bind[..end].trim();
+            if ident.is_empty() {
+                return Err(err(format!("empty interpolation in `{}`", s)));
+            }
+            segments.push(Segment::Bind(Ident::parse_idents(ident).map_err(|_| {
+                err(format!(
+                    "`{}` in interpolation should be an ident, such as `{{{{ user.name }}}}`",
+                    ident
+                ))
+            })?));
+            remain = &bind[end + Self::END.len()..];
+        }
+        if !remain.is_empty() {
+            segments.push(Segment::Text(remain.to_string()));
+        }
+        Ok(Self(segments))
+    }
+
+    /// bound idents in order
+    pub fn binds(&self) -> impl Iterator<Item = &Vec<Ident>> {
+        self.0.iter().filter_map(|segment| match segment {
+            Segment::Bind(idents) => Some(idents),
+            Segment::Text(_) => None,
+        })
+    }
+
+    /// root idents (fields) of the bound segments without repeat, `a.b` => `a`
+    pub fn idents(&self) -> Vec<String> {
+        self.binds().fold(vec![], |mut idents, ident| {
+            if !idents.contains(&ident[0].name) {
+                idents.push(ident[0].name.to_string());
+            }
+            idents
+        })
+    }
+}
+
+impl Display for Interpolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for segment in &self.0 {
+            match segment {
+                Segment::Text(text) => f.write_str(text)?,
+                Segment::Bind(idents) => write!(f, "{{{{ {} }}}}", Ident::fmt_idents(idents))?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_interpolation {
+    use super::{Interpolation, Segment};
+
+    #[test]
+    fn parse() {
+        let interp =
+            Interpolation::parse("Welcome, {{ user.name }}! You have {{n}} messages").unwrap();
+        assert_eq!(interp.0.len(), 5);
+        assert_eq!(interp.0[0], Segment::Text("Welcome, ".to_string()));
+        assert_eq!(interp.idents(), vec!["user", "n"]);
+        assert_eq!(
+            interp.to_string(),
+            "Welcome, {{ user.name }}! You have {{ n }} messages"
+        );
+        assert_eq!(
+            Interpolation::parse("{{ a }}{{ a }}").unwrap().idents(),
+            vec!["a"]
+        );
+
+        for input in ["Hi {{ name", "Hi {{ }}", "Hi {{ a + b }}"] {
+            assert!(Interpolation::parse(input).is_err());
+        }
+    }
+}
@@ -0,0 +1,537 @@
+use std::{fmt::Display, str::FromStr};
+
+use gen_utils::error::{ConvertError, Error, ParseError};
+
+use super::Value;
+
+/// css units which are not supported, they are rejected when parsing instead of falling back to `UnKnown`
+const UNSUPPORTED_UNITS: [&str; 12] = [
+    "rem", "vh", "vw", "vmin", "vmax", "pt", "pc", "cm", "mm", "in", "ex", "ch",
+];
+
+/// props which can be relative to the parent, See [`Length::relative`]
+const RELATIVE_PROPS: [&str; 2] = ["width", "height"];
+
+/// # Length
+/// unit-aware length of size (`width`, `height`), `margin` and `padding` in `<style>`, See [`Length::parse_prop`]
+/// - `12px` | `12`: fixed length
+/// - `1.5em`: relative to the font size of the widget, See [`Length::resolve_em`]
+/// - `fill` | `100%` | `fit`: only for size
+/// - `50%`: relative to the size of the parent, only for `width` and `height`
+/// - `calc(100% - 20px)`: `+ - * /` of lengths, multiplier and divisor should be numbers
+///
+/// Makepad has no relative length, `50%` and `calc(100% - 20px)` are `Fill` in live design and resized by the parent when drawing,
+/// See [`Length::relative`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Length {
+    Px(f64),
+    Em(f64),
+    /// percentage of the parent except `100%` (`Fill`)
+    Percent(f64),
+    Fill,
+    Fit,
+    /// `percent% + px + em * font_size`, only when it can not be simplified
+    Calc {
+        percent: f64,
+        px: f64,
+        em: f64,
+    },
+}
+
+impl Length {
+    /// font size (px) of `1em` if the widget has no `font_size`
+    pub const EM: f64 = 16.0;
+    /// props of size: `Fill`, `Fit` or fixed length
+    pub const SIZE_PROPS: [&str; 4] = ["width", "height", "icon_width", "icon_height"];
+    /// props of spacing: 1 ~ 4 fixed lengths, such as `10px 1em`
+    pub const SPACING_PROPS: [&str; 4] = ["margin", "padding", "icon_margin", "icon_padding"];
+
+    /// ## parse the value of a length prop in style
+    /// - `Ok(None)`: not a length prop or not a length (such as `12`, `10 20`, `{top: 10}`), parsed as other values
+    /// - size props: `Value::Length`, percentages are only supported in `width` and `height`
+    /// - spacing props: `Value::Length` or `Value::Vec` of `Value::Length` (`10px 1em`), `fill`, `fit` and percentages are rejected
+    /// - `Err`: unsupported unit (such as `12vh`), percentage out of `width` and `height` or invalid `calc(...)`
+    pub fn parse_prop(prop: &str, s: &str) -> Result<Option<Value>, Error> {
+        let s = s.trim();
+        if Self::SIZE_PROPS.contains(&prop) {
+            let length = Length::parse_style(s)?;
+            if let Some(length) = length.as_ref() {
+                if length.relative().is_some() && !RELATIVE_PROPS.contains(&prop) {
+                    return Err(err(&format!(
+                        "`{}` can not be used in {}, percentages are only supported in width and height",
+                        length, prop
+                    )));
+                }
+            }
+            return Ok(length.map(Value::Length));
+        }
+        if !Self::SPACING_PROPS.contains(&prop) {
+            return Ok(None);
+        }
+        let mut lengths = vec![];
+        let mut has_unit = false;
+        for item in split_items(s) {
+            let length = match Length::parse_style(item)? {
+                Some(length) => {
+                    has_unit = true;
+                    length
+                }
+                None => match item.parse::<f64>() {
+                    Ok(px) => Length::Px(px),
+                    Err(_) => return Ok(None),
+                },
+            };
+            if length.fixed().is_none() {
+                return Err(err(&format!(
+                    "`{}` can not be used in {}, only fixed lengths (px, em, calc(...)) are supported",
+                    length, prop
+                )));
+            }
+            lengths.push(Value::Length(length));
+        }
+        // numbers without unit are kept as numbers
+        if !has_unit {
+            return Ok(None);
+        }
+        Ok(Some(if lengths.len() == 1 {
+            lengths.remove(0)
+        } else {
+            Value::Vec(lengths)
+        }))
+    }
+    /// ## parse a single length
+    /// - `Ok(None)`: not a length, such as `12`, `90deg`, `0.2s`, numbers are kept as numbers
+    /// - `Err`: unsupported unit (such as `12vh`) or invalid `calc(...)`
+    pub fn parse_style(s: &str) -> Result<Option<Length>, Error> {
+        let s = s.trim();
+        match s {
+            "fill" => return Ok(Some(Length::Fill)),
+            "fit" => return Ok(Some(Length::Fit)),
+            _ => {}
+        }
+        if let Some(expr) = s.strip_prefix("calc(").and_then(|s| s.strip_suffix(')')) {
+            return Calc::new(expr)
+                .parse()
+                .and_then(Length::try_from)
+                .map(Some)
+                .map_err(|e| err(&format!("{} in `{}`", e, s)));
+        }
+        for token in s.split_whitespace() {
+            if let Some((_, unit)) = split_unit(token) {
+                if UNSUPPORTED_UNITS.contains(&unit) {
+                    return Err(err(&format!(
+                        "unsupported unit `{}` in `{}`, expected one of: px, em, %, fill, fit, calc(...)",
+                        unit, token
+                    )));
+                }
+            }
+        }
+        match split_unit(s) {
+            Some((num, "px")) => Ok(Some(Length::Px(num))),
+            Some((num, "em")) => Ok(Some(Length::Em(num))),
+            Some((100.0, "%")) => Ok(Some(Length::Fill)),
+            Some((num, "%")) => Ok(Some(Length::Percent(num))),
+            _ => Ok(None),
+        }
+    }
+    /// ## fixed length in px
+    /// return None for `fill`, `fit` and percentages, `em` which is not resolved is [`Length::EM`], See [`Length::resolve_em`]
+    pub fn fixed(&self) -> Option<f64> {
+        match self {
+            Length::Px(px) => Some(*px),
+            Length::Em(em) => Some(em * Length::EM),
+            Length::Calc { percent, px, em } if *percent == 0.0 => Some(px + em * Length::EM),
+            _ => None,
+        }
+    }
+    /// ## length relative to the parent
+    /// `(ratio, px)`: the length is `parent * ratio + px`, such as `calc(100% - 20px)` => `(1.0, -20.0)`
+    ///
+    /// return None if the length does not depend on the size of the parent (`fill` and `fit` are handled by Makepad)
+    pub fn relative(&self) -> Option<(f64, f64)> {
+        match self {
+            Length::Percent(percent) => Some((percent / 100.0, 0.0)),
+            Length::Calc { percent, px, em } if *percent != 0.0 => {
+                Some((percent / 100.0, px + em * Length::EM))
+            }
+            _ => None,
+        }
+    }
+    /// ## resolve `em` by the font size of the widget
+    /// `em` is kept after parsing, it is resolved when the props of the widget are converted
+    pub fn resolve_em(&mut self, font_size: f64) {
+        *self = match *self {
+            Length::Em(em) => Length::Px(em * font_size),
+            Length::Calc {
+                percent: 0.0,
+                px,
+                em,
+            } => Length::Px(px + em * font_size),
+            Length::Calc { percent, px, em } => Length::Calc {
+                percent,
+                px: px + em * font_size,
+                em: 0.0,
+            },
+            _ => return,
+        };
+    }
+}
+
+/// split by whitespace out of `calc(...)`: `calc(2em - 2px) 4px` => [`calc(2em - 2px)`, `4px`]
+fn split_items(s: &str) -> Vec<&str> {
+    let mut res = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (index, c) in s.char_indices().chain(std::iter::once((s.len(), ' '))) {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            c if c.is_whitespace() && depth == 0 => {
+                let item = s[start..index].trim();
+                if !item.is_empty() {
+                    res.push(item);
+                }
+                start = index;
+            }
+            _ => {}
+        }
+    }
+    res
+}
+
+/// `12px` => (12, "px"), `50%` => (50, "%"), None if it is not a number with unit
+fn split_unit(s: &str) -> Option<(f64, &str)> {
+    let index = s.find(|c: char| c.is_alphabetic() || c == '%')?;
+    let (num, unit) = s.split_at(index);
+    if num.is_empty() || !unit.chars().all(|c| c.is_alphabetic() || c == '%') {
+        return None;
+    }
+    num.parse::<f64>().ok().map(|num| (num, unit))
+}
+
+fn err(msg: &str) -> Error {
+    ParseError::other(msg, "Length").into()
+}
+
+impl FromStr for Length {
+    type Err = Error;
+
+    /// numbers without unit are px
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(px) = s.trim().parse::<f64>() {
+            return Ok(Length::Px(px));
+        }
+        Length::parse_style(s)?.ok_or_else(|| {
+            err(&format!(
+                "`{}` is not a length, expected one of: px, %, em, fill, fit, calc(...)",
+                s
+            ))
+        })
+    }
+}
+
+impl TryFrom<&Value> for Length {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Length(length) => Ok(length.clone()),
+            Value::USize(num) => Ok(Length::Px(*num as f64)),
+            Value::ISize(num) => Ok(Length::Px(*num as f64)),
+            Value::Double(num) => Ok(Length::Px(*num)),
+            Value::UnKnown(s) | Value::String(s) => s.parse(),
+            _ => Err(ConvertError::FromTo {
+                from: value.to_string(),
+                to: "Length".to_string(),
+            }
+            .into()),
+        }
+    }
+}
+
+impl Display for Length {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Length::Px(px) => write!(f, "{}px", px),
+            Length::Em(em) => write!(f, "{}em", em),
+            Length::Percent(percent) => write!(f, "{}%", percent),
+            Length::Fill => f.write_str("fill"),
+            Length::Fit => f.write_str("fit"),
+            Length::Calc { percent, px, em } => {
+                let mut res = String::new();
+                for (num, unit) in [(*percent, "%"), (*px, "px"), (*em, "em")] {
+                    if num == 0.0 {
+                        continue;
+                    }
+                    let sign = if num < 0.0 { "-" } else { "+" };
+                    if res.is_empty() {
+                        if num < 0.0 {
+                            res.push('-');
+                        }
+                    } else {
+                        res.push_str(&format!(" {} ", sign));
+                    }
+                    res.push_str(&format!("{}{}", num.abs(), unit));
+                }
+                write!(f, "calc({})", res)
+            }
+        }
+    }
+}
+
+/// `percent% + px + em`, number is a length without unit (`unit` is false)
+#[derive(Debug, Clone, Copy, Default)]
+struct Linear {
+    percent: f64,
+    px: f64,
+    em: f64,
+    unit: bool,
+}
+
+impl Linear {
+    fn scale(self, by: f64) -> Self {
+        Linear {
+            percent: self.percent * by,
+            px: self.px * by,
+            em: self.em * by,
+            unit: self.unit,
+        }
+    }
+    /// number without unit
+    fn num(&self) -> Option<f64> {
+        (!self.unit).then_some(self.px)
+    }
+}
+
+/// `100%` is `Fill`, other percentages are relative to the parent, See [`Length::relative`]
+impl TryFrom<Linear> for Length {
+    type Error = String;
+
+    fn try_from(value: Linear) -> Result<Self, Self::Error> {
+        let Linear {
+            percent, px, em, ..
+        } = value;
+        match (percent != 0.0, px != 0.0, em != 0.0) {
+            (true, false, false) if percent == 100.0 => Ok(Length::Fill),
+            (true, false, false) => Ok(Length::Percent(percent)),
+            (false, _, false) => Ok(Length::Px(px)),
+            (false, false, true) => Ok(Length::Em(em)),
+            _ => Ok(Length::Calc { percent, px, em }),
+        }
+    }
+}
+
+/// parser of the expression in `calc(...)`
+struct Calc<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Calc<'a> {
+    fn new(expr: &'a str) -> Self {
+        Self { rest: expr }
+    }
+    fn parse(mut self) -> Result<Linear, String> {
+        let res = self.expr()?;
+        if !self.rest.trim().is_empty() {
+            return Err(format!("unexpected `{}`", self.rest.trim()));
+        }
+        Ok(res)
+    }
+    fn peek(&mut self) -> Option<char> {
+        self.rest = self.rest.trim_start();
+        self.rest.chars().next()
+    }
+    fn bump(&mut self) {
+        self.rest = &self.rest[1..];
+    }
+    /// term (`+` | `-` term)*
+    fn expr(&mut self) -> Result<Linear, String> {
+        let mut res = self.term()?;
+        while let Some(op @ ('+' | '-')) = self.peek() {
+            self.bump();
+            let term = self.term()?;
+            let term = if op == '-' { term.scale(-1.0) } else { term };
+            res = Linear {
+                percent: res.percent + term.percent,
+                px: res.px + term.px,
+                em: res.em + term.em,
+                unit: res.unit || term.unit,
+            };
+        }
+        Ok(res)
+    }
+    /// factor (`*` | `/` factor)*
+    fn term(&mut self) -> Result<Linear, String> {
+        let mut res = self.factor()?;
+        while let Some(op @ ('*' | '/')) = self.peek() {
+            self.bump();
+            let factor = self.factor()?;
+            res = match (op, res.num(), factor.num()) {
+                ('*', Some(num), _) => factor.scale(num),
+                ('*', _, Some(num)) => res.scale(num),
+                ('/', _, Some(num)) if num != 0.0 => res.scale(1.0 / num),
+                ('/', _, Some(_)) => return Err("division by zero".to_string()),
+                _ => return Err(format!("`{}` needs a number without unit", op)),
+            };
+        }
+        Ok(res)
+    }
+    /// `(` expr `)` | number with unit
+    fn factor(&mut self) -> Result<Linear, String> {
+        if self.peek() == Some('(') {
+            self.bump();
+            let res = self.expr()?;
+            if self.peek() != Some(')') {
+                return Err("unclosed `(`".to_string());
+            }
+            self.bump();
+            return Ok(res);
+        }
+        let end = self
+            .rest
+            .find(|c: char| c.is_whitespace() || "+*/()".contains(c))
+            .unwrap_or(self.rest.len());
+        let (token, rest) = self.rest.split_at(end);
+        if token.is_empty() {
+            return Err("missing length".to_string());
+        }
+        self.rest = rest;
+        if let Ok(num) = token.parse::<f64>() {
+            return Ok(Linear {
+                px: num,
+                ..Default::default()
+            });
+        }
+        let (num, unit) = split_unit(token)
+            .ok_or_else(|| format!("`{}` should be a number with px, % or em", token))?;
+        let mut res = Linear {
+            unit: true,
+            ..Default::default()
+        };
+        match unit {
+            "px" => res.px = num,
+            "%" => res.percent = num,
+            "em" => res.em = num,
+            _ => return Err(format!("`{}` should be a number with px, % or em", token)),
+        }
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod test_length {
+    use crate::value::Value;
+
+    use super::Length;
+
+    #[test]
+    fn parse() {
+        let parse = |s: &str| Length::parse_style(s).unwrap();
+        assert_eq!(parse("12px"), Some(Length::Px(12.0)));
+        assert_eq!(parse("1.5em").unwrap().fixed(), Some(24.0));
+        assert_eq!(parse("fill"), Some(Length::Fill));
+        assert_eq!(parse("100%"), Some(Length::Fill));
+        assert_eq!(parse("fit"), Some(Length::Fit));
+        assert_eq!(parse("12"), None);
+        assert_eq!(parse("90deg"), None);
+
+        let calc = parse("calc(2em - 2px)").unwrap();
+        assert_eq!(
+            calc,
+            Length::Calc {
+                percent: 0.0,
+                px: -2.0,
+                em: 2.0
+            }
+        );
+        assert_eq!(calc.to_string(), "calc(-2px + 2em)");
+        assert_eq!(parse("calc((10px + 1em) * 2)").unwrap().fixed(), Some(52.0));
+        assert_eq!(parse("calc(200% / 2)"), Some(Length::Fill));
+
+        // relative to the parent
+        let percent = parse("50%").unwrap();
+        assert_eq!(percent, Length::Percent(50.0));
+        assert_eq!(percent.fixed(), None);
+        assert_eq!(percent.relative(), Some((0.5, 0.0)));
+        let calc = parse("calc(100% - 20px)").unwrap();
+        assert_eq!(calc.to_string(), "calc(100% - 20px)");
+        assert_eq!(calc.fixed(), None);
+        assert_eq!(calc.relative(), Some((1.0, -20.0)));
+        assert_eq!(
+            parse("calc(50% + 1em)").unwrap().relative(),
+            Some((0.5, 16.0))
+        );
+        assert_eq!(Length::Fill.relative(), None);
+
+        assert!(Length::parse_style("12vh").is_err());
+        assert!(Length::parse_style("10px 2rem").is_err());
+        assert!(Length::parse_style("calc(10px * 2px)").is_err());
+        assert!(Length::parse_style("calc(10px / 0)").is_err());
+        assert!(Length::parse_style("calc(10px + 2dp)").is_err());
+        assert_eq!("10".parse::<Length>().unwrap(), Length::Px(10.0));
+        assert!("auto".parse::<Length>().is_err());
+    }
+
+    #[test]
+    fn parse_prop() {
+        let parse = |prop: &str, s: &str| Value::parse_style_prop(prop, s);
+        assert_eq!(
+            parse("width", "12px").unwrap(),
+            Value::Length(Length::Px(12.0))
+        );
+        assert_eq!(
+            parse("height", "fill").unwrap(),
+            Value::Length(Length::Fill)
+        );
+        assert!(parse("width", "12vh").is_err());
+        assert_eq!(
+            parse("width", "50%").unwrap(),
+            Value::Length(Length::Percent(50.0))
+        );
+        assert!(parse("icon_width", "50%").is_err());
+        assert_eq!(
+            parse("margin", "calc(1em + 2px) 4px").unwrap(),
+            Value::Vec(vec![
+                Value::Length(Length::Calc {
+                    percent: 0.0,
+                    px: 2.0,
+                    em: 1.0
+                }),
+                Value::Length(Length::Px(4.0))
+            ])
+        );
+        assert_eq!(
+            parse("padding", "10 20").unwrap(),
+            Value::UnKnown("10 20".to_string())
+        );
+        assert!(parse("padding", "fill").is_err());
+        assert!(parse("margin", "10px 50%").is_err());
+        // only size, margin and padding are lengths
+        assert!(!matches!(parse("color", "fill").unwrap(), Value::Length(_)));
+        assert!(!matches!(
+            parse("font_size", "12px").unwrap(),
+            Value::Length(_)
+        ));
+    }
+
+    #[test]
+    fn resolve_em() {
+        let mut length = Length::Calc {
+            percent: 0.0,
+            px: -2.0,
+            em: 2.0,
+        };
+        length.resolve_em(10.0);
+        assert_eq!(length, Length::Px(18.0));
+        let mut length = Length::Calc {
+            percent: 100.0,
+            px: -2.0,
+            em: 2.0,
+        };
+        length.resolve_em(10.0);
+        assert_eq!(length.relative(), Some((1.0, 18.0)));
+        let mut length = Length::Fit;
+        length.resolve_em(10.0);
+        assert_eq!(length, Length::Fit);
+    }
+}
@@ -1,15 +1,21 @@
 mod bind;
 mod r#enum;
 mod function;
+mod length;
 mod r#struct;
 mod color;
 mod shader;
 mod special;
+mod interpolation;
+mod expr;
 
 use std::{collections::HashMap, fmt::Display, str::FromStr};
 pub use color::*;
 pub use bind::*;
+pub use interpolation::*;
+pub use expr::*;
 pub use function::Function;
+pub use length::Length;
 pub use shader::*;
 use gen_utils::{
     common::format_float,
@@ -75,6 +81,12 @@ pub enum Value {
     // Dep(String),
     /// animation value
     Animation(HashMap<PropKey, Value>),
+    /// length of size, margin and padding in <style>: `12px`, `1.5em`, `fill`, `fit`, `calc(2em - 2px)`, See [`Length`]
+    Length(Length),
+    /// string with `{{ }}` interpolation in <template>, See [`Interpolation`]
+    /// <label text="'Hello {{ name }}'"></label>
+    /// <label>Hello {{ name }}</label>
+    Interpolation(Interpolation),
 }
 
 impl Value {
@@ -90,6 +102,14 @@ impl Value {
         bind.ident()
     }
 
+    /// parse the value of the prop in <style>
+    /// value of size, margin and padding is parsed as [`Length`], See [`Length::parse_prop`]
+    pub fn parse_style_prop(prop: &str, s: &str) -> Result<Value, Error> {
+        match Length::parse_prop(prop, s)? {
+            Some(length) => Ok(length),
+            None => Value::parse_style(s),
+        }
+    }
     /// parse the value in <style>
     pub fn parse_style(s: &str) -> Result<Value, Error> {
         Bind::parse_style(s)
@@ -135,6 +155,13 @@ impl Value {
             .into())
         }
 
+        // `{{ }}` in string is interpolation, errors in it should not fall back to other types
+        if let Ok(Value::String(s)) = value_string(s) {
+            if Interpolation::is(&s) {
+                return Interpolation::parse(&s).map(Value::Interpolation);
+            }
+        }
+
         s.parse()
             .or_else(|_| value_vec(s))
             .or_else(|_| value_string(s))
@@ -360,6 +387,8 @@ impl Display for Value {
                     .collect::<Vec<String>>()
             ),
             Value::UnKnown(s) => s.to_string(),
+            Value::Length(length) => length.to_string(),
+            Value::Interpolation(interp) => format!("\"{}\"", interp),
         };
 
         f.write_str(&res)
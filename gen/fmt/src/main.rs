@@ -0,0 +1,139 @@
+//! # GenUI Formatter
+//! format `.gen` files with [`gen_analyzer::Formatter`], `<script>` is formatted by `rustfmt`
+//! ## Usage
+//! - `gen-fmt [paths...]`: format files in place, path can be file or dir (default: current dir)
+//! - `gen-fmt --check [paths...]`: only check, exit with code 1 if any file is not formatted
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    process::{ExitCode, Stdio},
+};
+
+use gen_analyzer::Formatter;
+use gen_utils::{
+    common::{exec_cmd, fs},
+    error::Error,
+};
+
+/// dirs which never contain source `.gen` files
+const IGNORE_DIRS: [&str; 2] = ["target", "node_modules"];
+
+fn main() -> ExitCode {
+    let mut check = false;
+    let mut paths = vec![];
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--check" => check = true,
+            "-h" | "--help" => {
+                println!("Usage: gen-fmt [--check] [paths...]");
+                return ExitCode::SUCCESS;
+            }
+            _ => paths.push(PathBuf::from(arg)),
+        }
+    }
+    if paths.is_empty() {
+        paths.push(PathBuf::from("."));
+    }
+
+    let mut files = vec![];
+    for path in paths.iter() {
+        collect(path, &mut files);
+    }
+
+    let formatter = Formatter::default();
+    let mut failed = false;
+    for file in files.iter() {
+        match format_file(&formatter, file, check) {
+            Ok(true) => {}
+            Ok(false) => {
+                failed = true;
+                println!("not formatted: {}", file.display());
+            }
+            Err(e) => {
+                failed = true;
+                eprintln!("error: {}: {}", file.display(), e);
+            }
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// collect all `.gen` files in path
+fn collect(path: &Path, files: &mut Vec<PathBuf>) {
+    if path.is_dir() {
+        let Ok(entries) = std::fs::read_dir(path) else {
+            return;
+        };
+        let mut entries = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .collect::<Vec<_>>();
+        entries.sort();
+        for entry in entries {
+            let ignore = entry.is_dir()
+                && entry
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with('.') || IGNORE_DIRS.contains(&name));
+            if !ignore {
+                collect(&entry, files);
+            }
+        }
+    } else if path.extension().is_some_and(|ext| ext == "gen") {
+        files.push(path.to_path_buf());
+    }
+}
+
+/// ## format a file
+/// - check: do not write the file
+/// ### return
+/// - `true`: file is formatted (or is written in place)
+/// - `false`: file is not formatted (only in check mode)
+fn format_file(formatter: &Formatter, path: &Path, check: bool) -> Result<bool, Error> {
+    let content = fs::read(path)?;
+    let with_file = |mut e: Error| {
+        if let Some(span) = e.span_mut() {
+            span.set_file(path);
+        }
+        e
+    };
+    // same as `Formatter::check_with`, so that the check result of cli and lib are always the same
+    if check {
+        return formatter.check_with(&content, rustfmt).map_err(with_file);
+    }
+
+    let formatted = formatter
+        .format_with(&content, rustfmt)
+        .map_err(with_file)?;
+    if formatted == content {
+        return Ok(true);
+    }
+    fs::write(path, &formatted)?;
+    println!("formatted: {}", path.display());
+    Ok(true)
+}
+
+/// format script by rustfmt, return None if rustfmt is not installed or the script can not be formatted
+fn rustfmt(script: &str) -> Option<String> {
+    let mut child = exec_cmd(
+        "rustfmt",
+        ["--emit", "stdout", "--edition", "2021"],
+        None::<&Path>,
+    )
+    .stdin(Stdio::piped())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::null())
+    .spawn()
+    .ok()?;
+    child.stdin.take()?.write_all(script.as_bytes()).ok()?;
+    let output = child.wait_with_output().ok()?;
+    if output.status.success() {
+        String::from_utf8(output.stdout).ok()
+    } else {
+        None
+    }
+}
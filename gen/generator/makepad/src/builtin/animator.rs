@@ -0,0 +1,506 @@
+use std::{collections::HashMap, str::FromStr};
+
+use gen_analyzer::{
+    style::{keyframes_key, STYLE_ANIMATION, STYLE_TRANSITION, TRANSITION_ALL},
+    value::{BuiltinColor, Value},
+    Cascade, PropKey, Props, Style, StyleVal,
+};
+use gen_utils::{
+    common::{ident, snake_to_camel},
+    error::{CompilerError, Error},
+};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+use crate::{from_gen::MakepadColor, traits::ToTokensExt};
+
+use super::{prop::F64, BuiltinWidgetType, PseudoState};
+
+/// `Play::Loop` of infinite animation ends at
+const LOOP_END: f64 = 1000000000.0;
+
+/// easing names of Makepad (snake case), See `makepad_platform::Ease`
+const EASES: [&str; 33] = [
+    "linear",
+    "none",
+    "constant",
+    "in_quad",
+    "out_quad",
+    "in_out_quad",
+    "in_cubic",
+    "out_cubic",
+    "in_out_cubic",
+    "in_quart",
+    "out_quart",
+    "in_out_quart",
+    "in_quint",
+    "out_quint",
+    "in_out_quint",
+    "in_sine",
+    "out_sine",
+    "in_out_sine",
+    "in_exp",
+    "out_exp",
+    "in_out_exp",
+    "in_circ",
+    "out_circ",
+    "in_out_circ",
+    "in_elastic",
+    "out_elastic",
+    "in_out_elastic",
+    "in_back",
+    "out_back",
+    "in_out_back",
+    "in_bounce",
+    "out_bounce",
+    "in_out_bounce",
+];
+
+/// # Animator
+/// `animator` block of builtin widget, generated from `transition` and `animation` in `<style>`
+///
+/// ## transition
+/// props in pseudo state which are listed in `transition` are animated between the base value (`off`)
+/// and the value in the state (`on`), props of the same state use the timing of the first matched transition
+/// ```txt
+/// .btn {
+///     opacity: 0.6;
+///     transition: opacity 0.2s ease_out;
+///     &::hover {
+///         opacity: 1;
+///     }
+/// }
+/// // ----- after ---------
+/// animator: {
+///     hover = {
+///         default: off,
+///         off = { from: {all: Forward {duration: 0.2}}, ease: OutQuad, apply: { opacity: 0.6 } }
+///         on = { from: {all: Forward {duration: 0.2}}, ease: OutQuad, apply: { opacity: 1.0 } }
+///     }
+/// }
+/// ```
+/// ## animation
+/// `animation: name duration [ease] [infinite]` plays `@keyframes name` as the default state of the track `name`
+/// ```txt
+/// @keyframes fade { from { opacity: 0; } to { opacity: 1; } }
+/// .card { animation: fade 1s linear infinite; }
+/// // ----- after ---------
+/// animator: {
+///     fade = {
+///         default: on,
+///         on = {
+///             from: {all: Loop {duration: 1.0, end: 1000000000.0}}, ease: Linear,
+///             apply: { opacity: [{time: 0.0, value: 0.0}, {time: 1.0, value: 1.0}] }
+///         }
+///     }
+/// }
+/// ```
+/// only numbers and colors (hex, rgb, rgba) can be animated
+#[derive(Debug, Clone, Default)]
+pub struct Animator {
+    pub tracks: Vec<Track>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Track {
+    pub name: String,
+    pub default: String,
+    pub states: Vec<TrackState>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TrackState {
+    pub name: String,
+    /// `Forward {duration: 0.2}` | `Loop {duration: 1.0, end: ...}`
+    pub play: TokenStream,
+    pub ease: Option<TokenStream>,
+    pub apply: TokenStream,
+}
+
+impl Animator {
+    /// ## merge cascaded style into props of the widget
+    /// `transition` and `animation` are taken out of the cascade and converted to the animator,
+    /// then the rest is merged by [`PseudoState::merge`]
+    pub fn merge(
+        name: &str,
+        styles: &Style,
+        mut cascade: Cascade,
+        props: &mut Option<Props>,
+    ) -> Result<Option<Self>, Error> {
+        let animator = Self::new(name, styles, &mut cascade, props.as_ref())?;
+        PseudoState::merge(name, cascade, props)?;
+        Ok(animator)
+    }
+
+    fn new(
+        name: &str,
+        styles: &Style,
+        cascade: &mut Cascade,
+        props: Option<&Props>,
+    ) -> Result<Option<Self>, Error> {
+        let transition = take(&mut cascade.props, STYLE_TRANSITION);
+        let animation = take(&mut cascade.props, STYLE_ANIMATION);
+        if transition.is_none() && animation.is_none() {
+            return Ok(None);
+        }
+        let widget = BuiltinWidgetType::from_str(name).map_err(|_| {
+            err(&format!(
+                "`{}` and `{}` are only supported by builtin widgets, `{}` is not",
+                STYLE_TRANSITION, STYLE_ANIMATION, name
+            ))
+        })?;
+
+        let mut tracks = vec![];
+        if let Some(transition) = transition.as_ref() {
+            tracks.extend(transition_tracks(name, widget, transition, cascade, props)?);
+        }
+        if let Some(animation) = animation.as_ref() {
+            tracks.push(animation_track(styles, animation)?);
+        }
+        Ok(Some(Self { tracks }))
+    }
+}
+
+impl ToTokens for Animator {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let tracks = self.tracks.iter().map(|track| {
+            let name = ident(&track.name);
+            let default = ident(&track.default);
+            let states = track.states.iter().map(|state| {
+                let TrackState {
+                    name,
+                    play,
+                    ease,
+                    apply,
+                } = state;
+                let name = ident(name);
+                let ease = ease.as_ref().map(|ease| quote! { ease: #ease, });
+                quote! {
+                    #name = {
+                        from: {all: #play},
+                        #ease
+                        apply: { #apply }
+                    }
+                }
+            });
+            quote! {
+                #name = {
+                    default: #default,
+                    #(#states)*
+                }
+            }
+        });
+        tokens.extend(quote! {
+            animator: {
+                #(#tracks)*
+            },
+        });
+    }
+}
+
+/// tracks of pseudo states, the transitioned props are removed from [`Cascade::states`]
+fn transition_tracks(
+    name: &str,
+    widget: BuiltinWidgetType,
+    transition: &Value,
+    cascade: &mut Cascade,
+    props: Option<&Props>,
+) -> Result<Vec<Track>, Error> {
+    let transitions = as_animation(transition, STYLE_TRANSITION)?;
+    let timing = |prop: &str| {
+        transitions
+            .iter()
+            .find(|(k, _)| k.name == prop)
+            .or_else(|| transitions.iter().find(|(k, _)| k.name == TRANSITION_ALL))
+            .map(|(_, v)| v)
+    };
+
+    let mut tracks = vec![];
+    for (state, style) in cascade.states.iter_mut() {
+        let mut transitioned = style
+            .keys()
+            .filter(|k| timing(&k.name).is_some())
+            .cloned()
+            .collect::<Vec<_>>();
+        if transitioned.is_empty() {
+            continue;
+        }
+        transitioned.sort_by(|a, b| a.name.cmp(&b.name));
+        let state = state.parse::<PseudoState>()?;
+        let track = widget
+            .animator_state(state)
+            .ok_or_else(|| err(&format!("widget `{}` has no `{}` state", name, state)))?;
+
+        let (mut off, mut on) = (TokenStream::new(), TokenStream::new());
+        let mut first = None;
+        for key in transitioned {
+            let value = style.remove(&key).expect("transitioned prop should exist");
+            let base = props
+                .and_then(|props| find(props, &key.name))
+                .or_else(|| find(&cascade.props, &key.name))
+                .ok_or_else(|| {
+                    err(&format!(
+                        "`{}` in `&::{}` of widget `{}` needs a base value to transition from",
+                        key.name, state, name
+                    ))
+                })?;
+            let prop = ident(&key.name);
+            let (base, value) = (
+                animate_value(&key.name, base)?,
+                animate_value(&key.name, &value)?,
+            );
+            off.extend(quote! { #prop: #base, });
+            on.extend(quote! { #prop: #value, });
+            if first.is_none() {
+                first = timing(&key.name);
+            }
+        }
+        let timing = as_animation(first.expect("timing should exist"), STYLE_TRANSITION)?;
+        let (play, ease) = (forward(timing)?, ease(timing)?);
+        tracks.push(Track {
+            name: track.to_string(),
+            default: "off".to_string(),
+            states: vec![
+                TrackState {
+                    name: "off".to_string(),
+                    play: play.clone(),
+                    ease: ease.clone(),
+                    apply: off,
+                },
+                TrackState {
+                    name: "on".to_string(),
+                    play,
+                    ease,
+                    apply: on,
+                },
+            ],
+        });
+    }
+    Ok(tracks)
+}
+
+/// track of `@keyframes`
+fn animation_track(styles: &Style, animation: &Value) -> Result<Track, Error> {
+    let animation = as_animation(animation, STYLE_ANIMATION)?;
+    let name = find(animation, "name")
+        .ok_or_else(|| err("animation needs the name of `@keyframes`"))?
+        .as_string()?;
+    let keyframes = styles
+        .get(&keyframes_key(&name))
+        .ok_or_else(|| err(&format!("`@keyframes {}` is not defined", name)))?;
+
+    let mut frames = keyframes
+        .iter()
+        .map(|(time, frame)| {
+            let time = time.name.parse::<f64>().map_err(|e| err(&e.to_string()))?;
+            Ok((time, as_animation(frame, "keyframe")?))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    frames.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+    // prop => [(time, value)]
+    let mut values: Vec<(String, Vec<(f64, TokenStream)>)> = vec![];
+    for (time, frame) in frames {
+        let mut frame = frame.iter().collect::<Vec<_>>();
+        frame.sort_by(|(a, _), (b, _)| a.name.cmp(&b.name));
+        for (key, value) in frame {
+            let value = animate_value(&key.name, value)?;
+            match values.iter_mut().find(|(prop, _)| prop == &key.name) {
+                Some((_, frames)) => frames.push((time, value)),
+                None => values.push((key.name.to_string(), vec![(time, value)])),
+            }
+        }
+    }
+    let apply = values
+        .iter()
+        .fold(TokenStream::new(), |mut tk, (prop, frames)| {
+            let prop = ident(prop);
+            let frames = frames.iter().map(|(time, value)| {
+                let time = F64(*time);
+                quote! { {time: #time, value: #value} }
+            });
+            tk.extend(quote! { #prop: [#(#frames),*], });
+            tk
+        });
+
+    let infinite = find(animation, "infinite")
+        .map(|v| v.as_bool())
+        .transpose()?
+        .unwrap_or_default();
+    let play = if infinite {
+        let duration = F64(duration(animation)?);
+        let end = F64(LOOP_END);
+        quote! { Loop {duration: #duration, end: #end} }
+    } else {
+        forward(animation)?
+    };
+    Ok(Track {
+        name,
+        default: "on".to_string(),
+        states: vec![TrackState {
+            name: "on".to_string(),
+            play,
+            ease: ease(animation)?,
+            apply,
+        }],
+    })
+}
+
+/// value in animator, only numbers and colors can be animated
+fn animate_value(prop: &str, value: &Value) -> Result<TokenStream, Error> {
+    match value {
+        Value::USize(n) => Ok(F64(*n as f64).to_token_stream()),
+        Value::ISize(n) => Ok(F64(*n as f64).to_token_stream()),
+        Value::Double(n) => Ok(F64(*n).to_token_stream()),
+        _ => match MakepadColor::try_from((value, None)) {
+            Ok(color)
+                if matches!(
+                    color.color,
+                    BuiltinColor::Hex(_) | BuiltinColor::Rgb(_) | BuiltinColor::Rgba(_)
+                ) =>
+            {
+                ToTokensExt::to_token_stream(&color)
+            }
+            _ => Err(err(&format!(
+                "`{}: {}` can not be animated, only numbers and colors can be animated",
+                prop, value
+            ))),
+        },
+    }
+}
+
+/// `Forward {duration: 0.2}`
+fn forward(timing: &HashMap<PropKey, Value>) -> Result<TokenStream, Error> {
+    let duration = F64(duration(timing)?);
+    Ok(quote! { Forward {duration: #duration} })
+}
+
+fn duration(timing: &HashMap<PropKey, Value>) -> Result<f64, Error> {
+    find(timing, "duration")
+        .ok_or_else(|| err("animation needs a duration"))?
+        .as_f64()
+}
+
+/// ## easing of Makepad
+/// - css like: `ease` | `ease_in` | `ease_out` | `ease_in_out`
+/// - Makepad `Ease` in snake case: `linear`, `out_exp`, `in_out_back` ...
+fn ease(timing: &HashMap<PropKey, Value>) -> Result<Option<TokenStream>, Error> {
+    let Some(ease) = find(timing, "ease") else {
+        return Ok(None);
+    };
+    let ease = ease.to_string();
+    let makepad = match ease.as_str() {
+        "ease" | "ease_in_out" => "in_out_quad",
+        "ease_in" => "in_quad",
+        "ease_out" => "out_quad",
+        ease if EASES.contains(&ease) => ease,
+        _ => {
+            return Err(err(&format!(
+                "unknown ease `{}`, expected `ease`, `ease_in`, `ease_out`, `ease_in_out` or Makepad ease, such as `out_exp`",
+                ease
+            )))
+        }
+    };
+    Ok(Some(ident(&snake_to_camel(makepad)).to_token_stream()))
+}
+
+fn as_animation<'a>(value: &'a Value, prop: &str) -> Result<&'a HashMap<PropKey, Value>, Error> {
+    value
+        .is_animation_and_get()
+        .ok_or_else(|| err(&format!("`{}: {}` is invalid", prop, value)))
+}
+
+fn find<'a>(props: &'a HashMap<PropKey, Value>, name: &str) -> Option<&'a Value> {
+    props.iter().find(|(k, _)| k.name == name).map(|(_, v)| v)
+}
+
+fn take(props: &mut StyleVal, name: &str) -> Option<Value> {
+    let key = props.keys().find(|k| k.name == name).cloned()?;
+    props.remove(&key)
+}
+
+fn err(msg: &str) -> Error {
+    CompilerError::runtime("Makepad Compiler - Animation", msg).into()
+}
+
+#[cfg(test)]
+mod test_animator {
+    use gen_analyzer::{value::Value, Model, StyleVisitor};
+    use quote::ToTokens;
+
+    use super::Animator;
+
+    fn merge(name: &str, style: &str) -> Result<Option<String>, gen_utils::error::Error> {
+        let model = format!("<style>\n{}\n</style>", style)
+            .parse::<Model>()
+            .unwrap();
+        let styles = model.style.unwrap();
+        let class = Value::parse_template("a").unwrap();
+        let cascade = StyleVisitor::visit(&styles, None, Some(&class), &[]).unwrap();
+        let mut props = None;
+        let animator = Animator::merge(name, &styles, cascade, &mut props)?;
+        // transition and animation are not props of the widget
+        assert!(props
+            .unwrap_or_default()
+            .keys()
+            .all(|k| k.name != "transition" && k.name != "animation"));
+        Ok(animator.map(|animator| animator.to_token_stream().to_string()))
+    }
+
+    #[test]
+    fn transition() {
+        let animator = merge(
+            "button",
+            ".a { opacity: 0.6; transition: opacity 0.2s ease_out; &::hover { opacity: 1; background_color: #f00; } }",
+        )
+        .unwrap()
+        .unwrap();
+        assert!(animator.contains("hover = { default : off"));
+        assert!(animator.contains("Forward { duration : 0.2 }"));
+        assert!(animator.contains("ease : OutQuad"));
+        assert!(animator.contains("apply : { opacity : 0.6 , }"));
+        assert!(animator.contains("apply : { opacity : 1.0 , }"));
+        // not transitioned, mapped by pseudo state
+        assert!(!animator.contains("background_color"));
+        assert!(merge("button", ".a { opacity: 1; }").unwrap().is_none());
+    }
+
+    #[test]
+    fn keyframes() {
+        let animator = merge(
+            "view",
+            "@keyframes fade { from { opacity: 0; } to { opacity: 1; } }\n.a { animation: fade 1s linear infinite; }",
+        )
+        .unwrap()
+        .unwrap();
+        assert!(animator.contains("fade = { default : on"));
+        assert!(animator.contains("Loop { duration : 1.0"));
+        assert!(animator
+            .contains("opacity : [{ time : 0.0 , value : 0.0 } , { time : 1.0 , value : 1.0 }]"));
+    }
+
+    #[test]
+    fn errors() {
+        // custom widget
+        assert!(merge("my_card", ".a { transition: opacity 1s; }").is_err());
+        assert!(merge("view", ".a { animation: unknown 1s; }").is_err());
+        let err = merge(
+            "view",
+            ".a { opacity: 0; transition: opacity 1s bouncy; &::hover { opacity: 1; } }",
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("unknown ease `bouncy`"));
+        // no base value
+        assert!(merge(
+            "view",
+            ".a { transition: opacity 1s; &::hover { opacity: 1; } }"
+        )
+        .is_err());
+        // only numbers and colors
+        assert!(merge(
+            "view",
+            "@keyframes a { to { flow: Down; } }\n.a { animation: a 1s; }"
+        )
+        .is_err());
+    }
+}
@@ -4,6 +4,8 @@ use gen_utils::{
     error::{CompilerError, Error},
 };
 
+pub use animator::Animator;
+pub use pseudo::PseudoState;
 pub use ty::{BuiltinWidgetType, InheritWidgetType};
 use widget::{
     Button, Checkbox, CheckboxGroup, Collapse, Dialog, Divider, Drawer, DropDown, Image, Input, Label, Link, Loading, Popup, PopupContainer, Radio, RadioGroup, Root, ScrollBars, Svg, Tag, Toggle, ToolTip, View, Window
@@ -11,10 +13,12 @@ use widget::{
 
 use crate::{from_builtin_widget, token::ToLiveDesign};
 
+mod animator;
 pub mod event;
 // mod handler;
 mod macros;
 pub mod prop;
+mod pseudo;
 mod shader;
 mod ty;
 pub mod widget;
@@ -1,9 +1,9 @@
-use std::{num::ParseFloatError, str::FromStr};
+use std::str::FromStr;
 
 use gen_analyzer::value::{Struct, Value};
 use gen_utils::{err_from_to, error::Error};
 
-use crate::{builtin::prop::convert_str_to_vec, struct_float_to_tokens, try_from_value_ref_struct};
+use crate::{struct_float_to_tokens, try_from_value_ref_length};
 
 #[derive(Debug, Clone, Default)]
 pub struct Margin {
@@ -147,8 +147,8 @@ impl TryFrom<f64> for Margin {
     }
 }
 
-try_from_value_ref_struct! {
-    Margin, "Margin", f64
+try_from_value_ref_length! {
+    Margin, "Margin"
 }
 
 struct_float_to_tokens! {
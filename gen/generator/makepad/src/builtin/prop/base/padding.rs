@@ -1,9 +1,9 @@
-use std::{num::ParseFloatError, str::FromStr};
+use std::str::FromStr;
 
 use gen_analyzer::value::{Struct, Value};
 use gen_utils::{err_from_to, error::Error};
 
-use crate::{builtin::prop::convert_str_to_vec, struct_float_to_tokens, try_from_value_ref_struct};
+use crate::{struct_float_to_tokens, try_from_value_ref_length};
 
 /// Convert padding to Makepad Padding
 /// ## single
@@ -157,8 +157,8 @@ impl TryFrom<f64> for Padding {
     }
 }
 
-try_from_value_ref_struct! {
-    Padding, "Padding", f64
+try_from_value_ref_length! {
+    Padding, "Padding"
 }
 
 struct_float_to_tokens! {
@@ -1,5 +1,5 @@
 use super::DVec2;
-use gen_analyzer::value::{Enum, EnumItem, Value};
+use gen_analyzer::value::{Enum, EnumItem, Length, Value};
 use gen_utils::{
     common::format_float,
     err_from_to,
@@ -174,6 +174,12 @@ impl TryFrom<&Value> for Size {
             value.parse()
         } else if let Value::Double(value) = value {
             Ok(Size::Fixed(*value))
+        } else if let Value::ISize(value) = value {
+            Ok(Size::Fixed(*value as f64))
+        } else if let Value::USize(value) = value {
+            Ok(Size::Fixed(*value as f64))
+        } else if let Value::Length(value) = value {
+            Ok(value.into())
         } else {
             Err(ConvertError::FromTo {
                 from: value.to_string(),
@@ -184,6 +190,21 @@ impl TryFrom<&Value> for Size {
     }
 }
 
+/// ## Length => Size
+/// - `fill` | `100%` => `Fill`
+/// - `fit` => `Fit`
+/// - `px` | `em` | `calc(...)` => `Fixed`
+/// - `50%` | `calc(100% - 20px)` => `Fill`, resized by the parent after drawing, See [`crate::model::RelativeSize`]
+impl From<&Length> for Size {
+    fn from(value: &Length) -> Self {
+        match value.fixed() {
+            Some(fixed) => Size::Fixed(fixed),
+            None if matches!(value, Length::Fit) => Size::Fit,
+            None => Size::Fill,
+        }
+    }
+}
+
 impl ToTokens for Size {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         let res = match self {
@@ -195,3 +216,25 @@ impl ToTokens for Size {
         tokens.extend(proc_macro2::TokenStream::from_str(&res).unwrap());
     }
 }
+
+#[cfg(test)]
+mod test_size {
+    use gen_analyzer::value::Value;
+
+    use super::Size;
+
+    #[test]
+    fn from_length() {
+        let size = |s: &str| Size::try_from(&Value::parse_style_prop("width", s).unwrap());
+        assert!(matches!(size("fill").unwrap(), Size::Fill));
+        assert!(matches!(size("100%").unwrap(), Size::Fill));
+        assert!(matches!(size("fit").unwrap(), Size::Fit));
+        assert!(matches!(size("12px").unwrap(), Size::Fixed(v) if v == 12.0));
+        assert!(matches!(size("1.5em").unwrap(), Size::Fixed(v) if v == 24.0));
+        assert!(matches!(size("calc(2em - 2px)").unwrap(), Size::Fixed(v) if v == 30.0));
+        assert!(matches!(size("50%").unwrap(), Size::Fill));
+        assert!(matches!(size("calc(100% - 20px)").unwrap(), Size::Fill));
+        assert!(matches!(Size::try_from(&Value::ISize(30)).unwrap(), Size::Fixed(v) if v == 30.0));
+        assert!(matches!(Size::try_from(&Value::USize(30)).unwrap(), Size::Fixed(v) if v == 30.0));
+    }
+}
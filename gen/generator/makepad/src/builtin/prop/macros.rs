@@ -50,6 +50,39 @@ macro_rules! try_from_value_ref_struct {
     };
 }
 
+/// same as [`try_from_value_ref_struct`], but the value can be lengths with unit: `10px 1.5em`, See [`gen_analyzer::value::Length::parse_prop`]
+#[macro_export]
+macro_rules! try_from_value_ref_length {
+    ($($T: ty, $TStr: expr),*) => {
+        $(
+            impl TryFrom<&Value> for $T {
+                type Error = Error;
+
+                fn try_from(value: &Value) -> Result<Self, Self::Error> {
+                    match value {
+                        Value::Struct(s) => s.try_into(),
+                        Value::UnKnown(s) => s.parse(),
+                        Value::Double(d) => (*d).try_into(),
+                        Value::USize(u) => (*u as f64).try_into(),
+                        Value::ISize(i) => (*i as f64).try_into(),
+                        Value::Length(length) => $crate::builtin::prop::length_to_fixed(length, $TStr)?.try_into(),
+                        Value::Vec(lengths) => $crate::builtin::prop::lengths_to_fixed(lengths, $TStr)?.try_into(),
+                        _ => Err(gen_utils::err_from_to!("Value" => $TStr)),
+                    }
+                }
+            }
+
+            impl FromStr for $T {
+                type Err = Error;
+
+                fn from_str(s: &str) -> Result<Self, Self::Err> {
+                    $crate::builtin::prop::convert_str_to_fixed(s, $TStr).and_then(|vecs| vecs.try_into())
+                }
+            }
+        )*
+    };
+}
+
 #[macro_export]
 macro_rules! try_from_enum_one_leaf {
     ($T: ty, $S: expr ,$($P: path = $I: expr),*) => {
@@ -183,6 +216,25 @@ macro_rules! props_to_tokens {
                 }
             }
         }
+
+        impl $T {
+            /// ## prop keys which can be written in `.gen` file
+            /// props which are deref (such as `walk`, `layout`) are not included, get them from the deref type
+            pub fn keys() -> Vec<&'static str> {
+                Self::key_types().into_iter().map(|(key, _)| key).collect()
+            }
+            /// ## prop keys with the type name of the prop value
+            /// type name is from [`std::any::type_name`], such as `text` => `alloc::string::String`
+            pub fn key_types() -> Vec<(&'static str, &'static str)> {
+                fn ty<T>(_: fn(T) -> $T) -> &'static str {
+                    std::any::type_name::<T>()
+                }
+                vec![$((stringify!($V), ty($P), $Deref)),*]
+                    .into_iter()
+                    .filter_map(|(key, ty, is_deref)| (!is_deref).then_some((key, ty)))
+                    .collect()
+            }
+        }
     };
 }
 
@@ -1,5 +1,9 @@
 use std::{fmt::Display, str::FromStr};
 
+use gen_analyzer::{
+    value::{Length, Value},
+    Props,
+};
 use gen_utils::{err_from_to, error::Error};
 
 /// convert string to vector
@@ -19,6 +23,77 @@ where
     Ok(res)
 }
 
+/// ## convert lengths to fixed px
+/// `10px 1.5em calc(2em - 2px)` => [10.0, 24.0, 30.0], numbers without unit are px
+pub fn convert_str_to_fixed(s: &str, target: &str) -> Result<Vec<f64>, Error> {
+    let mut res = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    // split by space out of `calc(...)`
+    for (index, c) in s.char_indices().chain(std::iter::once((s.len(), ' '))) {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            c if c.is_whitespace() && depth == 0 => {
+                let item = s[start..index].trim();
+                if !item.is_empty() {
+                    res.push(length_to_fixed(&item.parse()?, target)?);
+                }
+                start = index;
+            }
+            _ => {}
+        }
+    }
+    Ok(res)
+}
+
+/// `fill` and `fit` depend on the parent, they can not be used in margin or padding, See [`Length::fixed`]
+pub fn length_to_fixed(length: &Length, target: &str) -> Result<f64, Error> {
+    length.fixed().ok_or_else(|| {
+        Error::FromDynError(format!(
+            "`{}` can not be used in {}, only fixed lengths (px, em) are supported",
+            length, target
+        ))
+    })
+}
+
+/// lengths of margin or padding: `10px 1.5em` => [10.0, 24.0], See [`Length::parse_prop`]
+pub fn lengths_to_fixed(lengths: &[Value], target: &str) -> Result<Vec<f64>, Error> {
+    lengths
+        .iter()
+        .map(|length| match length {
+            Value::Length(length) => length_to_fixed(length, target),
+            value => value.as_f64(),
+        })
+        .collect()
+}
+
+/// ## resolve `em` of the lengths by the `font_size` of the widget
+/// `1em` is [`Length::EM`] if the widget has no `font_size`, See [`Length::resolve_em`]
+pub fn resolve_em(props: &mut Props) {
+    let font_size = props
+        .iter()
+        .find(|(key, _)| key.name == "font_size")
+        .and_then(|(_, value)| match value {
+            Value::Double(size) => Some(*size),
+            Value::USize(size) => Some(*size as f64),
+            Value::ISize(size) => Some(*size as f64),
+            _ => None,
+        })
+        .unwrap_or(Length::EM);
+    for value in props.values_mut() {
+        match value {
+            Value::Length(length) => length.resolve_em(font_size),
+            Value::Vec(values) => values.iter_mut().for_each(|value| {
+                if let Value::Length(length) = value {
+                    length.resolve_em(font_size);
+                }
+            }),
+            _ => {}
+        }
+    }
+}
+
 pub fn err_field(field: &str) -> Error {
     Error::FromDynError(format!("can not find target field: {} in Struct", field))
 }
@@ -40,3 +115,50 @@ pub fn value_bool(value: &toml_edit::Value) -> Result<bool, Error> {
         |b| Ok(b),
     )
 }
+
+#[cfg(test)]
+mod test_utils {
+    use gen_analyzer::{
+        value::{Length, Value},
+        PropKey, PropKeyType, Props,
+    };
+
+    use super::resolve_em;
+
+    #[test]
+    fn em() {
+        let prop = |name: &str, value: &str| {
+            (
+                PropKey::new(name, true, PropKeyType::Normal),
+                Value::parse_style_prop(name, value).unwrap(),
+            )
+        };
+        let get = |props: &Props, name: &str| {
+            props
+                .iter()
+                .find(|(key, _)| key.name == name)
+                .map(|(_, value)| value.clone())
+        };
+        let mut props: Props = [
+            prop("font_size", "10"),
+            prop("width", "2em"),
+            prop("margin", "calc(1em + 2px) 4px"),
+        ]
+        .into_iter()
+        .collect();
+        resolve_em(&mut props);
+        assert_eq!(get(&props, "width"), Some(Value::Length(Length::Px(20.0))));
+        assert_eq!(
+            get(&props, "margin"),
+            Some(Value::Vec(vec![
+                Value::Length(Length::Px(12.0)),
+                Value::Length(Length::Px(4.0))
+            ]))
+        );
+
+        // default font size
+        let mut props: Props = [prop("height", "1.5em")].into_iter().collect();
+        resolve_em(&mut props);
+        assert_eq!(get(&props, "height"), Some(Value::Length(Length::Px(24.0))));
+    }
+}
@@ -0,0 +1,209 @@
+use std::{fmt::Display, str::FromStr};
+
+use gen_analyzer::{Cascade, PropKey, Props, StyleVal};
+use gen_utils::error::{CompilerError, Error};
+
+use super::BuiltinWidgetType;
+
+/// # Pseudo State
+/// `&::hover`, `&::pressed`, `&::focus`, `&::disabled` in `<style>`
+///
+/// the props of a pseudo state are compiled to the props driven by the animator state of the widget:
+/// ```txt
+/// .btn {
+///     &::hover {
+///         background_color: #f00;  => hover_color: #f00
+///         text_color: #fff;        => text_hover_color: #fff
+///     }
+/// }
+/// ```
+/// See [`BuiltinWidgetType::animator_state`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PseudoState {
+    Hover,
+    Pressed,
+    Focus,
+    Disabled,
+}
+
+impl PseudoState {
+    pub const ALL: [PseudoState; 4] = [
+        PseudoState::Hover,
+        PseudoState::Pressed,
+        PseudoState::Focus,
+        PseudoState::Disabled,
+    ];
+
+    /// ## merge cascaded style into props of the widget
+    /// props of pseudo states are converted by [`PseudoState::prop`], they win over the normal props in style,
+    /// props in template (inline) always win, See [`Cascade::merge`]
+    pub fn merge(name: &str, mut cascade: Cascade, props: &mut Option<Props>) -> Result<(), Error> {
+        let mut state_props = StyleVal::new();
+        for (state, style) in std::mem::take(&mut cascade.states) {
+            let state = state.parse::<PseudoState>()?;
+            let animator = BuiltinWidgetType::from_str(name)
+                .ok()
+                .and_then(|widget| widget.animator_state(state))
+                .ok_or_else(|| {
+                    Error::from(CompilerError::runtime(
+                        "Makepad Compiler - Style",
+                        &format!("widget `{}` has no `{}` state", name, state),
+                    ))
+                })?;
+            for (key, value) in style {
+                let prop = state.prop(&key.name, animator).ok_or_else(|| {
+                    Error::from(CompilerError::runtime(
+                        "Makepad Compiler - Style",
+                        &format!(
+                            "`{}` can not be used in `&::{}` of widget `{}`, only color props are driven by the state, use `transition` to animate it",
+                            key.name, state, name
+                        ),
+                    ))
+                })?;
+                state_props.insert(PropKey { name: prop, ..key }, value);
+            }
+        }
+        cascade.props.extend(state_props);
+        cascade.merge(props);
+        Ok(())
+    }
+
+    /// ## prop driven by the animator state
+    /// - `background_color` => `hover_color`
+    /// - `checkbox_background_color` => `checkbox_hover_color`
+    /// - `text_color` => `text_hover_color`
+    ///
+    /// return None if the prop is not a color
+    pub fn prop(&self, name: &str, animator: &str) -> Option<String> {
+        if name == "background_color" {
+            return Some(format!("{}_color", animator));
+        }
+        name.strip_suffix("_background_color")
+            .or_else(|| name.strip_suffix("_color"))
+            .map(|prefix| format!("{}_{}_color", prefix, animator))
+    }
+}
+
+impl FromStr for PseudoState {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PseudoState::ALL
+            .into_iter()
+            .find(|state| state.to_string() == s)
+            .ok_or_else(|| {
+                CompilerError::runtime(
+                    "Makepad Compiler - Style",
+                    &format!(
+                        "unknown pseudo state `&::{}`, expected one of: hover, pressed, focus, disabled",
+                        s
+                    ),
+                )
+                .into()
+            })
+    }
+}
+
+impl Display for PseudoState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            PseudoState::Hover => "hover",
+            PseudoState::Pressed => "pressed",
+            PseudoState::Focus => "focus",
+            PseudoState::Disabled => "disabled",
+        })
+    }
+}
+
+impl BuiltinWidgetType {
+    /// ## animator state of the widget for the pseudo state
+    /// the name is used as the prefix of the props driven by the state, such as `hover` => `hover_color`,
+    /// return None if the widget has no such state
+    pub fn animator_state(&self, state: PseudoState) -> Option<&'static str> {
+        let clickable = matches!(
+            self,
+            BuiltinWidgetType::Button
+                | BuiltinWidgetType::View
+                | BuiltinWidgetType::Label
+                | BuiltinWidgetType::Link
+                | BuiltinWidgetType::Tag
+                | BuiltinWidgetType::Checkbox
+                | BuiltinWidgetType::Radio
+                | BuiltinWidgetType::Popup
+                | BuiltinWidgetType::Drawer
+                | BuiltinWidgetType::Dialog
+                | BuiltinWidgetType::ToolTip
+        );
+        match (self, state) {
+            (BuiltinWidgetType::Input | BuiltinWidgetType::Toggle, PseudoState::Hover) => {
+                Some("hover")
+            }
+            (_, PseudoState::Hover) if clickable => Some("hover"),
+            // pressed is the focus state of the clickable widgets
+            (_, PseudoState::Pressed) if clickable => Some("focus"),
+            (BuiltinWidgetType::Input, PseudoState::Focus) => Some("focus"),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_pseudo {
+    use gen_analyzer::{value::Value, Model, StyleVisitor};
+
+    use super::PseudoState;
+
+    fn merge(name: &str, style: &str) -> Result<Vec<(String, Value)>, gen_utils::error::Error> {
+        let model = format!("<style>\n{}\n</style>", style)
+            .parse::<Model>()
+            .unwrap();
+        let styles = model.style.unwrap();
+        let class = Value::parse_template("a").unwrap();
+        let cascade = StyleVisitor::visit(&styles, None, Some(&class), &[]).unwrap();
+        let mut props = None;
+        PseudoState::merge(name, cascade, &mut props)?;
+        let mut props = props
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(k, v)| (k.name, v))
+            .collect::<Vec<_>>();
+        props.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(props)
+    }
+
+    #[test]
+    fn states() {
+        let props = merge(
+            "button",
+            ".a { background_color: #000; &::hover { background_color: #f00; text_color: #fff; } &::pressed { border_color: #0f0; } }",
+        )
+        .unwrap();
+        let names = props.iter().map(|(k, _)| k.as_str()).collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                "background_color",
+                "border_focus_color",
+                "hover_color",
+                "text_hover_color"
+            ]
+        );
+        assert_eq!(
+            PseudoState::Hover.prop("checkbox_background_color", "hover"),
+            Some("checkbox_hover_color".to_string())
+        );
+        assert_eq!(PseudoState::Hover.prop("height", "hover"), None);
+    }
+
+    #[test]
+    fn errors() {
+        let err = merge("button", ".a { &::disabled { background_color: #f00; } }").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("widget `button` has no `disabled` state"));
+        let err = merge("my_card", ".a { &::hover { background_color: #f00; } }").unwrap_err();
+        assert!(err.to_string().contains("`my_card`"));
+        assert!(merge("button", ".a { &::hover { height: 10; } }").is_err());
+        assert!(merge("button", ".a { &::active { color: #fff; } }").is_err());
+    }
+}
@@ -5,8 +5,13 @@ use gen_utils::error::{ConvertError, Error};
 use crate::two_way_binding::TwoWayBindImpl;
 
 use super::{
+    prop::{Layout, Walk},
     widget::{
-        Button, Checkbox, CheckboxGroup, Collapse, Divider, DropDown, Image, Input, Label, Link, Loading, Popup, PopupContainer, Radio, RadioGroup, Root, Svg, Tag, Toggle, View, WidgetImpl
+        Button, ButtonProps, Checkbox, CheckboxGroup, CheckboxGroupProps, CheckboxProps, Collapse,
+        CollapseProps, Divider, DividerProps, DropDown, DropDownProps, Image, ImageProps, Input,
+        InputProps, Label, LabelProps, Link, LinkProps, Loading, LoadingProps, Popup, PopupContainer,
+        PopupProps, Radio, RadioGroup, RadioGroupProps, RadioProps, Root, ScrollBarsProps, Svg,
+        SvgProps, Tag, TagProps, Toggle, ToggleProps, View, ViewProps, WidgetImpl, WindowProps,
     },
     BuiltinWidget,
 };
@@ -47,6 +52,112 @@ pub enum BuiltinWidgetType {
 }
 
 impl BuiltinWidgetType {
+    /// all builtin widgets
+    pub const ALL: [BuiltinWidgetType; 25] = [
+        BuiltinWidgetType::Root,
+        BuiltinWidgetType::Button,
+        BuiltinWidgetType::Label,
+        BuiltinWidgetType::View,
+        BuiltinWidgetType::Divider,
+        BuiltinWidgetType::Image,
+        BuiltinWidgetType::Svg,
+        BuiltinWidgetType::Link,
+        BuiltinWidgetType::ScrollBars,
+        BuiltinWidgetType::Window,
+        BuiltinWidgetType::Radio,
+        BuiltinWidgetType::RadioGroup,
+        BuiltinWidgetType::Checkbox,
+        BuiltinWidgetType::CheckboxGroup,
+        BuiltinWidgetType::Input,
+        BuiltinWidgetType::Toggle,
+        BuiltinWidgetType::Tag,
+        BuiltinWidgetType::Collapse,
+        BuiltinWidgetType::Loading,
+        BuiltinWidgetType::DropDown,
+        BuiltinWidgetType::Popup,
+        BuiltinWidgetType::PopupContainer,
+        BuiltinWidgetType::Drawer,
+        BuiltinWidgetType::Dialog,
+        BuiltinWidgetType::ToolTip,
+    ];
+    /// tag name in `.gen` file, see [`FromStr`]
+    pub fn tag_name(&self) -> &str {
+        match self {
+            BuiltinWidgetType::Root => "root",
+            BuiltinWidgetType::Button => "button",
+            BuiltinWidgetType::Label => "label",
+            BuiltinWidgetType::View => "view",
+            BuiltinWidgetType::Divider => "divider",
+            BuiltinWidgetType::Image => "image",
+            BuiltinWidgetType::Svg => "svg",
+            BuiltinWidgetType::Link => "link",
+            BuiltinWidgetType::ScrollBars => "scroll_bars",
+            BuiltinWidgetType::Window => "window",
+            BuiltinWidgetType::Radio => "radio",
+            BuiltinWidgetType::RadioGroup => "radio_group",
+            BuiltinWidgetType::Checkbox => "checkbox",
+            BuiltinWidgetType::CheckboxGroup => "checkbox_group",
+            BuiltinWidgetType::Input => "input",
+            BuiltinWidgetType::Toggle => "toggle",
+            BuiltinWidgetType::Tag => "tag",
+            BuiltinWidgetType::Collapse => "collapse",
+            BuiltinWidgetType::Loading => "loading",
+            BuiltinWidgetType::DropDown => "drop_down",
+            BuiltinWidgetType::Popup => "popup",
+            BuiltinWidgetType::PopupContainer => "popup_container",
+            BuiltinWidgetType::Drawer => "drawer",
+            BuiltinWidgetType::Dialog => "dialog",
+            BuiltinWidgetType::ToolTip => "tool_tip",
+        }
+    }
+    /// ## all prop keys of the widget which can be written in `.gen` file
+    /// include the props from deref widget (`walk`, `layout` and `View`)
+    pub fn prop_keys(&self) -> Vec<&'static str> {
+        self.prop_types().into_iter().map(|(key, _)| key).collect()
+    }
+    /// ## all prop keys with the type name of the prop value
+    /// See [`BuiltinWidgetType::prop_keys`]
+    pub fn prop_types(&self) -> Vec<(&'static str, &'static str)> {
+        type KeyTypes = Vec<(&'static str, &'static str)>;
+        fn with_walk_layout(mut keys: KeyTypes) -> KeyTypes {
+            keys.extend(Walk::key_types());
+            keys.extend(Layout::key_types());
+            keys
+        }
+        fn with_view(mut keys: KeyTypes) -> KeyTypes {
+            keys.extend(with_walk_layout(ViewProps::key_types()));
+            keys
+        }
+
+        match self {
+            BuiltinWidgetType::Root => vec![],
+            BuiltinWidgetType::Button => with_walk_layout(ButtonProps::key_types()),
+            BuiltinWidgetType::Label => LabelProps::key_types(),
+            BuiltinWidgetType::View | BuiltinWidgetType::PopupContainer => {
+                with_walk_layout(ViewProps::key_types())
+            }
+            BuiltinWidgetType::Divider => with_view(DividerProps::key_types()),
+            BuiltinWidgetType::Image => with_walk_layout(ImageProps::key_types()),
+            BuiltinWidgetType::Svg => with_walk_layout(SvgProps::key_types()),
+            BuiltinWidgetType::Link => with_walk_layout(LinkProps::key_types()),
+            BuiltinWidgetType::ScrollBars => ScrollBarsProps::key_types(),
+            BuiltinWidgetType::Window => with_view(WindowProps::key_types()),
+            BuiltinWidgetType::Radio => with_walk_layout(RadioProps::key_types()),
+            BuiltinWidgetType::RadioGroup => with_view(RadioGroupProps::key_types()),
+            BuiltinWidgetType::Checkbox => with_walk_layout(CheckboxProps::key_types()),
+            BuiltinWidgetType::CheckboxGroup => with_view(CheckboxGroupProps::key_types()),
+            BuiltinWidgetType::Input => with_walk_layout(InputProps::key_types()),
+            BuiltinWidgetType::Toggle => with_walk_layout(ToggleProps::key_types()),
+            BuiltinWidgetType::Tag => with_walk_layout(TagProps::key_types()),
+            BuiltinWidgetType::Collapse => with_walk_layout(CollapseProps::key_types()),
+            BuiltinWidgetType::Loading => with_walk_layout(LoadingProps::key_types()),
+            BuiltinWidgetType::DropDown => DropDownProps::key_types(),
+            BuiltinWidgetType::Popup
+            | BuiltinWidgetType::Drawer
+            | BuiltinWidgetType::Dialog
+            | BuiltinWidgetType::ToolTip => with_walk_layout(PopupProps::key_types()),
+        }
+    }
     pub fn name(&self) -> &str {
         match self {
             BuiltinWidgetType::Root => "Root",
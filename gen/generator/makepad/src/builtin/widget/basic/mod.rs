@@ -8,10 +8,10 @@ mod view;
 mod scrollbar;
 
 pub use scrollbar::*;
-pub use button::Button;
-pub use label::Label;
+pub use button::{Button, ButtonProps};
+pub use label::{Label, LabelProps};
 pub use view::{View, ViewProps};
-pub use divider::Divider;
-pub use image::Image;
-pub use svg::Svg;
-pub use link::Link;
\ No newline at end of file
+pub use divider::{Divider, DividerProps};
+pub use image::{Image, ImageProps};
+pub use svg::{Svg, SvgProps};
+pub use link::{Link, LinkProps};
\ No newline at end of file
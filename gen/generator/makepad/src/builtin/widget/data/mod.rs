@@ -2,6 +2,6 @@ mod tag;
 mod collapse;
 mod loading;
 
-pub use tag::Tag;
-pub use collapse::Collapse;
-pub use loading::Loading;
\ No newline at end of file
+pub use tag::{Tag, TagProps};
+pub use collapse::{Collapse, CollapseProps};
+pub use loading::{Loading, LoadingProps};
\ No newline at end of file
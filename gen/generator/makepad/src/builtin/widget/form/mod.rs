@@ -5,5 +5,5 @@ mod toggle;
 
 pub use checkbox::*;
 pub use radio::*;
-pub use input::Input;
+pub use input::{Input, InputProps};
 pub use toggle::*;
\ No newline at end of file
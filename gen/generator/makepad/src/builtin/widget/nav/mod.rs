@@ -3,5 +3,5 @@ mod window;
 mod router;
 
 pub use root::{Root, RootConf};
-pub use window::Window;
+pub use window::{Window, WindowProps};
 pub use router::*;
\ No newline at end of file
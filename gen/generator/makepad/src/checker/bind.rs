@@ -0,0 +1,320 @@
+use std::{collections::HashMap, str::FromStr};
+
+use gen_analyzer::{
+    value::{Bind, ForItem, StyleItem, Value},
+    SugarIf, SugarProps, Template,
+};
+use quote::ToTokens;
+use syn::Type;
+
+use crate::builtin::BuiltinWidgetType;
+
+use super::Checker;
+
+/// kind of value which can be compared between the prop of builtin widget and the field of `#[component]` struct
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    String,
+    Bool,
+    Number,
+    /// can not be checked (such as struct, enum, generic ...)
+    Other,
+}
+
+impl Kind {
+    /// ## from the last segment of a type name (without generics)
+    /// - `alloc::string::String` => `String`
+    /// - `makepad_gen_plugin::builtin::prop::F64` => `F64`
+    fn from_name(name: &str) -> Self {
+        let name = name.split('<').next().unwrap_or_default();
+        match name.rsplit("::").next().unwrap_or_default().trim() {
+            "String" | "str" => Kind::String,
+            "bool" => Kind::Bool,
+            "f32" | "f64" | "F32" | "F64" | "I32" | "i8" | "i16" | "i32" | "i64" | "i128"
+            | "isize" | "u8" | "u16" | "u32" | "u64" | "u128" | "usize" => Kind::Number,
+            _ => Kind::Other,
+        }
+    }
+
+    fn from_ty(ty: &Type) -> Self {
+        match ty {
+            Type::Path(path) => path
+                .path
+                .segments
+                .last()
+                .map_or(Kind::Other, |seg| Kind::from_name(&seg.ident.to_string())),
+            Type::Reference(reference) => Kind::from_ty(&reference.elem),
+            Type::Paren(paren) => Kind::from_ty(&paren.elem),
+            _ => Kind::Other,
+        }
+    }
+}
+
+impl Checker<'_> {
+    /// ## check `:bind` props
+    /// - the root ident of each bind (`:prop="a.b"` => `a`) must be a field of `#[component]` struct
+    ///   or a variable of the `:for` loop outside (index and item)
+    /// - `:for`, `:key` and `:if`/`:else_if` binds and each ident of `:class` and `:style` map are checked in the same way
+    /// - `:key` must use the item or index of its own `:for`, otherwise all the items have the same key
+    /// - each prop of `:style` map must be a prop of the builtin widget
+    /// - bind of builtin widget prop is type checked if both the type of the prop and the field are known
+    ///   (string, bool, number), See [`BuiltinWidgetType::prop_types`]
+    /// - function binds (`:prop="get_value()"`) are not checked
+    pub fn check_binds(&mut self) {
+        let Some(template) = self.template else {
+            return;
+        };
+        let fields = self.fields();
+        self.check_template_binds(template, fields.as_ref(), &mut vec![]);
+    }
+
+    fn check_template_binds(
+        &mut self,
+        template: &Template,
+        fields: Option<&HashMap<String, Type>>,
+        scope: &mut Vec<String>,
+    ) {
+        let len = scope.len();
+        match &template.sugar_props {
+            SugarProps::For(Value::Bind(Bind::For(for_bind))) => {
+                let iter = for_bind.ident();
+                self.check_ident(template, "for", &iter, fields, scope);
+                scope.push(for_bind.index.clone().unwrap_or("index".to_string()));
+                for_item_idents(&for_bind.item, scope);
+                if let Some(key) = template.key.as_ref() {
+                    self.check_expr(template, "key", key, fields, scope);
+                    self.check_key(template, key, fields, scope, len);
+                }
+            }
+            SugarProps::If(SugarIf::If(sugar_if)) => {
+                self.check_expr(template, "if", &sugar_if.expr, fields, scope);
+            }
+            SugarProps::If(SugarIf::ElseIf(else_if)) => {
+                self.check_expr(template, "else_if", &else_if.expr, fields, scope);
+            }
+            _ => {}
+        }
+        // data of scoped slot, `scope.title` is checked by the slot checker, See [`Checker::check_slots`]
+        if let Some(slot_scope) = template.slot_scope.as_ref() {
+            scope.push(slot_scope.to_string());
+        }
+
+        if let Some(binds) = template.binds.as_ref() {
+            let mut binds = binds.iter().collect::<Vec<_>>();
+            binds.sort_by(|(a, _), (b, _)| a.name.cmp(&b.name));
+            for (key, value) in binds {
+                let idents = match value {
+                    Value::Bind(Bind::Normal(idents)) => idents,
+                    Value::Bind(Bind::Class(items)) => {
+                        for item in items {
+                            self.check_ident(
+                                template,
+                                &key.name,
+                                &item.ident[0].name,
+                                fields,
+                                scope,
+                            );
+                        }
+                        continue;
+                    }
+                    Value::Bind(Bind::Style(items)) => {
+                        for item in items {
+                            self.check_style_item(template, item, fields, scope);
+                        }
+                        continue;
+                    }
+                    // any type which implements `Display` can be interpolated, only idents are checked
+                    Value::Interpolation(interp) => {
+                        for ident in interp.idents() {
+                            self.check_ident(template, &key.name, &ident, fields, scope);
+                        }
+                        continue;
+                    }
+                    // the type of expression is checked by rustc, only idents are checked
+                    Value::Bind(Bind::Expr(expr)) => {
+                        for ident in expr.idents() {
+                            self.check_ident(template, &key.name, &ident, fields, scope);
+                        }
+                        continue;
+                    }
+                    _ => continue,
+                };
+                let Some(ident) = idents.first() else {
+                    continue;
+                };
+                if !self.check_ident(template, &key.name, &ident.name, fields, scope) {
+                    continue;
+                }
+                // only the field itself (not `a.b` or a loop variable) can be type checked
+                if idents.len() == 1 && !scope.contains(&ident.name) {
+                    if let Some(ty) = fields.and_then(|fields| fields.get(&ident.name)) {
+                        self.check_type(template, &key.name, &ident.name, ty);
+                    }
+                }
+            }
+        }
+
+        if let Some(children) = template.children.as_ref() {
+            for child in children {
+                self.check_template_binds(child, fields, scope);
+            }
+        }
+        scope.truncate(len);
+    }
+
+    fn check_style_item(
+        &mut self,
+        template: &Template,
+        item: &StyleItem,
+        fields: Option<&HashMap<String, Type>>,
+        scope: &[String],
+    ) {
+        if let Ok(widget) = BuiltinWidgetType::from_str(&template.name) {
+            if !widget.prop_keys().contains(&item.prop.as_str()) {
+                self.error(
+                    template,
+                    &format!(
+                        "unknown prop `{}` in `:style` of <{}>, it is not a prop of the widget",
+                        item.prop, template.name
+                    ),
+                );
+                return;
+            }
+        }
+        let ident = &item.ident[0].name;
+        if self.check_ident(template, "style", ident, fields, scope)
+            && item.ident.len() == 1
+            && !scope.contains(ident)
+        {
+            if let Some(ty) = fields.and_then(|fields| fields.get(ident)) {
+                self.check_type(template, &item.prop, ident, ty);
+            }
+        }
+    }
+
+    /// the key is a field or a variable of the outer loop, it is the same for every item of the `:for`
+    fn check_key(
+        &mut self,
+        template: &Template,
+        key: &Value,
+        fields: Option<&HashMap<String, Type>>,
+        scope: &[String],
+        len: usize,
+    ) {
+        let Value::Bind(Bind::Normal(idents)) = key else {
+            return;
+        };
+        let Some(ident) = idents.first() else {
+            return;
+        };
+        let is_known = scope[..len].contains(&ident.name)
+            || fields.is_some_and(|fields| fields.contains_key(&ident.name));
+        if is_known && !scope[len..].contains(&ident.name) {
+            self.error(
+                template,
+                &format!(
+                    "duplicate `:key` of <{}>, `{}` is the same for every item, use the item or index of `:for`",
+                    template.name, key
+                ),
+            );
+        }
+    }
+
+    fn check_expr(
+        &mut self,
+        template: &Template,
+        key: &str,
+        expr: &Value,
+        fields: Option<&HashMap<String, Type>>,
+        scope: &[String],
+    ) {
+        match expr {
+            Value::Bind(Bind::Normal(idents)) => {
+                if let Some(ident) = idents.first() {
+                    self.check_ident(template, key, &ident.name, fields, scope);
+                }
+            }
+            Value::Bind(Bind::Expr(expr)) => {
+                for ident in expr.idents() {
+                    self.check_ident(template, key, &ident, fields, scope);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// check the ident is a field or in scope, return false if it is unknown
+    fn check_ident(
+        &mut self,
+        template: &Template,
+        key: &str,
+        ident: &str,
+        fields: Option<&HashMap<String, Type>>,
+        scope: &[String],
+    ) -> bool {
+        if scope.iter().any(|var| var == ident) {
+            return true;
+        }
+        match fields {
+            Some(fields) if fields.contains_key(ident) => true,
+            Some(_) => {
+                self.error(
+                    template,
+                    &format!(
+                        "unknown binding `{}` in `:{}` of <{}>, it is neither a field of the `#[component]` struct nor a `:for` variable",
+                        ident, key, template.name
+                    ),
+                );
+                false
+            }
+            None => {
+                self.error(
+                    template,
+                    &format!(
+                        "unknown binding `{}` in `:{}` of <{}>, no `#[component]` struct is found in <script>",
+                        ident, key, template.name
+                    ),
+                );
+                false
+            }
+        }
+    }
+
+    fn check_type(&mut self, template: &Template, key: &str, ident: &str, ty: &Type) {
+        let Ok(widget) = BuiltinWidgetType::from_str(&template.name) else {
+            return;
+        };
+        let Some((_, prop_ty)) = widget
+            .prop_types()
+            .into_iter()
+            .find(|(prop, _)| *prop == key)
+        else {
+            return;
+        };
+
+        let (expect, found) = (Kind::from_name(prop_ty), Kind::from_ty(ty));
+        if expect != Kind::Other && found != Kind::Other && expect != found {
+            self.error(
+                template,
+                &format!(
+                    "type mismatch of `:{}=\"{}\"` in <{}>, expect `{}`, but field `{}` is `{}`",
+                    key,
+                    ident,
+                    template.name,
+                    prop_ty.rsplit("::").next().unwrap_or(prop_ty),
+                    ident,
+                    ty.to_token_stream()
+                ),
+            );
+        }
+    }
+}
+
+/// variables of `:for` item, `_` and `..` are ignored
+fn for_item_idents(item: &ForItem, scope: &mut Vec<String>) {
+    match item {
+        ForItem::Tuple(items) => items.iter().for_each(|item| for_item_idents(item, scope)),
+        ForItem::Ident(ident) => scope.push(ident.to_string()),
+        ForItem::More | ForItem::None => {}
+    }
+}
@@ -0,0 +1,124 @@
+use std::str::FromStr;
+
+use gen_analyzer::{value::Value, Template};
+use gen_utils::common::camel_to_snake;
+use syn::ImplItem;
+
+use crate::{builtin::BuiltinWidgetType, model::AbsWidget};
+
+use super::Checker;
+
+impl Checker<'_> {
+    /// ## check `@event` callbacks
+    /// - event of builtin widget must be in the event enum of the widget, See [`BuiltinWidgetType::event_ty_map`]
+    /// - event of custom component must be a variant of its `#[event]` enum
+    ///   (only when the component has been compiled and pushed into the define widget poll)
+    /// - the callback method must be defined in `impl` of `#[component]` struct
+    pub fn check_events(&mut self) {
+        let Some(template) = self.template else {
+            return;
+        };
+        let methods = self.methods();
+        self.check_template_events(template, methods.as_deref());
+    }
+
+    fn check_template_events(&mut self, template: &Template, methods: Option<&[String]>) {
+        if let Some(callbacks) = template.callbacks.as_ref() {
+            let mut callbacks = callbacks.iter().collect::<Vec<_>>();
+            callbacks.sort_by(|(a, _), (b, _)| a.name.cmp(&b.name));
+            for (key, value) in callbacks {
+                self.check_event(template, &key.name);
+                if let Value::Function(func) = value {
+                    self.check_method(template, &key.name, &func.name, methods);
+                }
+            }
+        }
+
+        if let Some(children) = template.children.as_ref() {
+            for child in children {
+                self.check_template_events(child, methods);
+            }
+        }
+    }
+
+    /// check the event is declared by the widget
+    fn check_event(&mut self, template: &Template, event: &str) {
+        let events = if let Ok(builtin) = BuiltinWidgetType::from_str(&template.name) {
+            builtin
+                .event_ty_map()
+                .map(|map| map.into_keys().collect::<Vec<_>>())
+                .unwrap_or_default()
+        } else {
+            // custom component which is not compiled yet can not be checked
+            match self.widgets.get(&camel_to_snake(&template.name)) {
+                Some(AbsWidget::Define { events, .. }) => events
+                    .as_ref()
+                    .map(|events| events.keys().map(|event| camel_to_snake(event)).collect())
+                    .unwrap_or_default(),
+                _ => return,
+            }
+        };
+
+        if events.iter().any(|e| e == event) {
+            return;
+        }
+
+        let msg = if events.is_empty() {
+            format!(
+                "<{}> has no event, `@{}` can not be used",
+                template.name, event
+            )
+        } else {
+            let mut events = events;
+            events.sort();
+            format!(
+                "unknown event `@{}` of <{}>, expect one of: {}",
+                event,
+                template.name,
+                events
+                    .iter()
+                    .map(|e| format!("`{}`", e))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        };
+        self.error(template, &msg);
+    }
+
+    /// check the callback method is defined in `impl` of `#[component]` struct
+    fn check_method(
+        &mut self,
+        template: &Template,
+        event: &str,
+        name: &str,
+        methods: Option<&[String]>,
+    ) {
+        let msg = match methods {
+            Some(methods) if methods.iter().any(|method| method == name) => return,
+            Some(_) => format!(
+                "callback `{}` of `@{}` in <{}> is not found in `impl` of `#[component]` struct",
+                name, event, template.name
+            ),
+            None => format!(
+                "callback `{}` of `@{}` in <{}> is not found, no `impl` of `#[component]` struct in <script>",
+                name, event, template.name
+            ),
+        };
+        self.error(template, &msg);
+    }
+
+    /// methods of `impl` of `#[component]` struct, None if no `impl` in script
+    fn methods(&self) -> Option<Vec<String>> {
+        let impl_component = self.bridger.as_ref()?.impl_component.as_ref()?;
+        Some(
+            impl_component
+                .items
+                .iter()
+                .filter_map(|item| match item {
+                    ImplItem::Fn(item_fn) => Some(item_fn.sig.ident.to_string()),
+                    _ => None,
+                })
+                .collect(),
+        )
+    }
+}
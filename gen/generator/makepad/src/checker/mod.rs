@@ -0,0 +1,462 @@
+mod bind;
+mod event;
+mod slot;
+mod style;
+
+use std::{collections::HashMap, path::PathBuf};
+
+use gen_analyzer::{Model, Style, Template};
+use gen_utils::error::{CompilerError, Error, ParseError, ParseType, Span};
+use rssyin::{analyzer::ScriptAnalyzer, bridger::ScriptBridger};
+use syn::{Fields, Type};
+
+use crate::compiler::WidgetPoll;
+
+/// # Checker
+/// static check of the model between analyzer and generator,
+/// the mistakes in `.gen` file are reported with location, instead of letting rustc fail on the generated code
+/// ## Check
+/// - `:bind` props: See [`Checker::check_binds`]
+/// - `@event` callbacks: See [`Checker::check_events`]
+/// - `slot` and `slot_scope` of custom component children: See [`Checker::check_slots`]
+/// ## Warning
+/// - props overridden by more specific style rules (opt-in): See [`Checker::overrides`]
+/// ## Example
+/// ```rust,ignore
+/// let model = Model::new(source, false)?;
+/// Checker::new(&model, &ctx.define_widget_poll).check()?;
+/// ```
+pub struct Checker<'a> {
+    template: Option<&'a Template>,
+    style: Option<&'a Style>,
+    /// `<script>` analyzed by rssyin, None if no script or the script can not be analyzed
+    bridger: Option<ScriptBridger>,
+    /// error of analyzing `<script>`, reported once instead of checking binds and events without the script
+    script_error: Option<String>,
+    /// custom components which have been compiled, used to check the events and slots of custom components
+    widgets: &'a WidgetPoll,
+    /// file of the model, set to the span of errors
+    file: PathBuf,
+    errors: Vec<Error>,
+}
+
+impl<'a> Checker<'a> {
+    pub fn new(model: &'a Model, widgets: &'a WidgetPoll) -> Self {
+        let (bridger, script_error) =
+            match model.script.as_ref().map(|sc| ScriptAnalyzer::analyze(sc)) {
+                Some(Ok(bridger)) => (Some(bridger), None),
+                Some(Err(e)) => (None, Some(e.to_string())),
+                None => (None, None),
+            };
+
+        Self {
+            template: model.template.as_ref(),
+            style: model.style.as_ref(),
+            bridger,
+            script_error,
+            widgets,
+            file: model.special.from_path(),
+            errors: vec![],
+        }
+    }
+    /// ## run all checks
+    /// return all errors as one error (each with location), See [`Checker::errors`]
+    pub fn check(self) -> Result<(), Error> {
+        let mut errors = self.errors();
+        match errors.len() {
+            0 => Ok(()),
+            1 => Err(errors.remove(0)),
+            len => Err(CompilerError::runtime(
+                "Makepad Compiler - Checker",
+                &format!(
+                    "{} errors found:\n{}",
+                    len,
+                    errors
+                        .iter()
+                        .map(|e| e.to_string())
+                        .collect::<Vec<_>>()
+                        .join("\n\n")
+                ),
+            )
+            .into()),
+        }
+    }
+    /// ## run all checks and return all errors, errors are sorted by location
+    /// if `<script>` can not be analyzed, only the script error is reported for binds and events
+    pub fn errors(mut self) -> Vec<Error> {
+        if let Some(e) = self.script_error.take() {
+            self.errors.push(
+                CompilerError::runtime(
+                    "Makepad Compiler - Checker",
+                    &format!(
+                        "<script> can not be analyzed, binds and events are not checked: {}",
+                        e
+                    ),
+                )
+                .into(),
+            );
+        } else {
+            self.check_binds();
+            self.check_events();
+        }
+        self.check_slots();
+        self.errors
+            .sort_by_key(|e| e.span().map_or(usize::MAX, |span| span.offset));
+        self.errors
+    }
+    /// fields of `#[component]` struct, None if no `#[component]` struct in script
+    fn fields(&self) -> Option<HashMap<String, Type>> {
+        let component = self.bridger.as_ref()?.component.as_ref()?;
+        let fields = match &component.fields {
+            Fields::Named(fields) => fields
+                .named
+                .iter()
+                .filter_map(|field| {
+                    field
+                        .ident
+                        .as_ref()
+                        .map(|ident| (ident.to_string(), field.ty.clone()))
+                })
+                .collect(),
+            _ => HashMap::new(),
+        };
+        Some(fields)
+    }
+    /// ## entries of `import!` in script (path segments)
+    /// `crate::components::hello::*` => `["crate", "components", "hello", "*"]`
+    pub fn imports(&self) -> Vec<Vec<String>> {
+        self.bridger
+            .as_ref()
+            .and_then(|bridger| bridger.imports.as_ref())
+            .map(|imports| imports.0.iter().map(|import| import.0.clone()).collect())
+            .unwrap_or_default()
+    }
+    /// record an error at the tag of `template`
+    fn error(&mut self, template: &Template, msg: &str) {
+        let mut err = ParseError::new(msg, ParseType::DSLBind);
+        if let Some(span) = template.span.as_ref() {
+            let mut span: Span = span.clone();
+            span.set_file(&self.file);
+            err.set_span(span);
+        }
+        self.errors.push(err.into());
+    }
+}
+
+#[cfg(test)]
+mod test_checker {
+    use std::collections::HashMap;
+
+    use gen_analyzer::Model;
+
+    use crate::model::AbsWidget;
+
+    use super::Checker;
+
+    const HELLO: &str = r#"
+<template>
+    <component name="Hello">
+        <label :text="name" :visible="count" :font_size="size"></label>
+        <checkbox :selected="unknown"></checkbox>
+        <view :for="(index, item) in list" :key="index">
+            <label :text="item"></label>
+        </view>
+        <view :for="item in list" :key="uid"></view>
+    </component>
+</template>
+
+<script>
+#[component]
+pub struct Hello {
+    name: String,
+    count: u32,
+    size: f64,
+    list: Vec<String>,
+}
+</script>
+"#;
+
+    #[test]
+    fn binds() {
+        let model = HELLO.parse::<Model>().unwrap();
+        let errors = Checker::new(&model, &HashMap::new()).errors();
+        assert_eq!(errors.len(), 3);
+        assert!(errors[0].to_string().contains("`count`"));
+        assert_eq!(errors[0].span().map(|span| span.line), Some(4));
+        assert!(errors[1].to_string().contains("`unknown`"));
+        assert_eq!(errors[1].span().map(|span| span.line), Some(5));
+        assert!(errors[2].to_string().contains("`uid` in `:key`"));
+    }
+
+    #[test]
+    fn class_binds() {
+        let input = r#"
+<template>
+    <component name="Hello">
+        <button id="btn" :class="{ active: is_active, off: !unknown }"></button>
+    </component>
+</template>
+
+<script>
+#[component]
+pub struct Hello {
+    is_active: bool,
+}
+</script>
+"#;
+        let model = input.parse::<Model>().unwrap();
+        let errors = Checker::new(&model, &HashMap::new()).errors();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].to_string().contains("`unknown`"));
+    }
+
+    #[test]
+    fn script_error() {
+        let input = r#"
+<template>
+    <component name="Hello">
+        <label :text="name" :visible="show"></label>
+    </component>
+</template>
+
+<script>
+#[component]
+pub struct Hello {
+    name: String,
+    show: bool
+</script>
+"#;
+        let model = input.parse::<Model>().unwrap();
+        let errors = Checker::new(&model, &HashMap::new()).errors();
+        // the script error is reported once, binds are not reported as unknown
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0]
+            .to_string()
+            .contains("<script> can not be analyzed"));
+    }
+
+    #[test]
+    fn duplicate_key() {
+        let input = r#"
+<template>
+    <component name="Hello">
+        <view :for="item in list" :key="item.id"></view>
+        <view :for="(index, item) in list" :key="name"></view>
+    </component>
+</template>
+
+<script>
+#[component]
+pub struct Hello {
+    name: String,
+    list: Vec<Item>,
+}
+</script>
+"#;
+        let model = input.parse::<Model>().unwrap();
+        let errors = Checker::new(&model, &HashMap::new()).errors();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].to_string().contains("`:key` of <view>, `name`"));
+    }
+
+    #[test]
+    fn interpolation() {
+        let input = r#"
+<template>
+    <component name="Hello">
+        <view :for="(index, item) in list">
+            <label>{{ index }}. {{ item }} from {{ name }}</label>
+        </view>
+        <label text="'Hi {{ user.name }}'"></label>
+    </component>
+</template>
+
+<script>
+#[component]
+pub struct Hello {
+    name: String,
+    list: Vec<String>,
+}
+</script>
+"#;
+        let model = input.parse::<Model>().unwrap();
+        let errors = Checker::new(&model, &HashMap::new()).errors();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].to_string().contains("`user` in `:text`"));
+    }
+
+    #[test]
+    fn expr() {
+        let input = r#"
+<template>
+    <component name="Hello">
+        <label :if="count > 0 && !loading" :text="count.to_string()"></label>
+        <label :else_if="items.len() > 0" :text="'empty'"></label>
+        <label :else="true" :text="name.len().to_string() + unknown"></label>
+    </component>
+</template>
+
+<script>
+#[component]
+pub struct Hello {
+    count: u32,
+    loading: bool,
+    name: String,
+}
+</script>
+"#;
+        let model = input.parse::<Model>().unwrap();
+        let errors = Checker::new(&model, &HashMap::new()).errors();
+        assert_eq!(errors.len(), 2);
+        let errors = errors.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+        assert!(errors.iter().any(|e| e.contains("`items`")));
+        assert!(errors.iter().any(|e| e.contains("`unknown` in `:text`")));
+    }
+
+    #[test]
+    fn style_binds() {
+        let input = r#"
+<template>
+    <component name="Hello">
+        <label id="lb" :style="{ color: text_color, font_size: text_color, colour: text_color }"></label>
+        <view id="v" :style="view_style"></view>
+    </component>
+</template>
+
+<script>
+#[component]
+pub struct Hello {
+    text_color: String,
+}
+</script>
+"#;
+        let model = input.parse::<Model>().unwrap();
+        let errors = Checker::new(&model, &HashMap::new()).errors();
+        assert_eq!(errors.len(), 3);
+        assert!(errors[0]
+            .to_string()
+            .contains("type mismatch of `:font_size"));
+        assert!(errors[1].to_string().contains("unknown prop `colour`"));
+        assert!(errors[2].to_string().contains("`view_style`"));
+    }
+
+    #[test]
+    fn events() {
+        let input = r#"
+<template>
+    <component name="Hello">
+        <button id="btn" @clicked="on_click()"></button>
+        <button id="btn2" @click="on_click()"></button>
+        <label id="lb" @hover_in="on_hover()"></label>
+    </component>
+</template>
+
+<script>
+#[component]
+pub struct Hello {}
+
+impl Hello {
+    fn on_click(&self) {}
+}
+</script>
+"#;
+        let model = input.parse::<Model>().unwrap();
+        let errors = Checker::new(&model, &HashMap::new()).errors();
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].to_string().contains("`@click`"));
+        assert_eq!(errors[0].span().map(|span| span.line), Some(5));
+        assert!(errors[1].to_string().contains("`on_hover`"));
+        assert_eq!(errors[1].span().map(|span| span.line), Some(6));
+    }
+
+    #[test]
+    fn slots() {
+        let input = r#"
+<template>
+    <component name="Hello">
+        <my_card id="card">
+            <label id="lb" slot="header" slot_scope="scope" :text="scope.title"></label>
+            <label id="lb2" slot="footer"></label>
+            <label id="lb3" slot_scope="scope"></label>
+            <label id="lb4" slot="header" slot_scope="data" :text="data.count"></label>
+        </my_card>
+        <my_list id="list">
+            <label id="lb5" slot="header"></label>
+        </my_list>
+    </component>
+</template>
+"#;
+        let model = input.parse::<Model>().unwrap();
+        let widgets = HashMap::from([
+            (
+                "my_card".to_string(),
+                AbsWidget::Define {
+                    name: "MyCard".to_string(),
+                    props: None,
+                    events: None,
+                    slots: Some(HashMap::from([
+                        ("default".to_string(), vec![]),
+                        ("header".to_string(), vec!["title".to_string()]),
+                    ])),
+                },
+            ),
+            (
+                "my_list".to_string(),
+                AbsWidget::Define {
+                    name: "MyList".to_string(),
+                    props: None,
+                    events: None,
+                    slots: None,
+                },
+            ),
+        ]);
+        let errors = Checker::new(&model, &widgets).errors();
+        assert_eq!(errors.len(), 4);
+        assert!(errors[0]
+            .to_string()
+            .contains("unknown slot `footer` of <my_card>, expect one of: `default`, `header`"));
+        assert_eq!(errors[0].span().map(|span| span.line), Some(6));
+        assert!(errors[1]
+            .to_string()
+            .contains("slot `default` of <my_card> has no scope"));
+        assert!(errors[2].to_string().contains("`data.count` in `:text`"));
+        assert!(errors[3].to_string().contains("<my_list> has no slot"));
+    }
+
+    #[test]
+    fn overrides() {
+        let input = r#"
+<template>
+    <component name="Hello">
+        <view class="box">
+            <view id="title" class="item"></view>
+            <view class="item"></view>
+        </view>
+    </component>
+</template>
+
+<style>
+#title {
+    height: 10;
+}
+.box {
+    .item {
+        height: 20;
+        width: 20;
+    }
+}
+.item {
+    width: 30;
+}
+</style>
+"#;
+        let model = input.parse::<Model>().unwrap();
+        let warnings = Checker::new(&model, &HashMap::new()).overrides();
+        assert_eq!(warnings.len(), 3);
+        assert!(warnings[0].starts_with("`width` in `.item` is overridden by `.box-.item`"));
+        assert!(warnings[1].starts_with("`height` in `.box-.item` is overridden by `#title`"));
+        assert!(warnings[1].contains(":5:"));
+        assert!(warnings[2].starts_with("`width` in `.item` is overridden by `.box-.item`"));
+        assert!(warnings[2].contains(":6:"));
+    }
+}
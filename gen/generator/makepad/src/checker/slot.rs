@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+
+use gen_analyzer::{
+    value::{Bind, Value},
+    Template,
+};
+use gen_utils::common::camel_to_snake;
+
+use crate::model::{AbsWidget, DEFAULT_SLOT};
+
+use super::Checker;
+
+impl Checker<'_> {
+    /// ## check `slot` and `slot_scope` of the children of custom components
+    /// (only when the component has been compiled and pushed into the define widget poll)
+    /// - `slot` must be a slot declared by `<slot>` of the component
+    /// - `slot_scope` can only be used on a scoped slot (`<slot>` with binds)
+    /// - `scope.prop` must be a bind of the scoped slot
+    pub fn check_slots(&mut self) {
+        let Some(template) = self.template else {
+            return;
+        };
+        self.check_template_slots(template);
+    }
+
+    fn check_template_slots(&mut self, template: &Template) {
+        let Some(children) = template.children.as_ref() else {
+            return;
+        };
+        let slots = if template.root {
+            None
+        } else {
+            match self.widgets.get(&camel_to_snake(&template.name)) {
+                Some(AbsWidget::Define { slots, .. }) => Some(slots.clone().unwrap_or_default()),
+                _ => None,
+            }
+        };
+
+        for child in children {
+            if let Some(slots) = slots.as_ref() {
+                self.check_slot(template, child, slots);
+            }
+            self.check_template_slots(child);
+        }
+    }
+
+    fn check_slot(
+        &mut self,
+        template: &Template,
+        child: &Template,
+        slots: &HashMap<String, Vec<String>>,
+    ) {
+        if child.slot.is_none() && child.slot_scope.is_none() {
+            return;
+        }
+        let slot = child.slot.as_deref().unwrap_or(DEFAULT_SLOT);
+        let Some(scope) = slots.get(slot) else {
+            let msg = if slots.is_empty() {
+                format!(
+                    "<{}> has no slot, `slot=\"{}\"` of <{}> can not be used",
+                    template.name, slot, child.name
+                )
+            } else {
+                let mut names = slots.keys().collect::<Vec<_>>();
+                names.sort();
+                format!(
+                    "unknown slot `{}` of <{}>, expect one of: {}",
+                    slot,
+                    template.name,
+                    names
+                        .iter()
+                        .map(|name| format!("`{}`", name))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            };
+            self.error(child, &msg);
+            return;
+        };
+        let Some(slot_scope) = child.slot_scope.as_ref() else {
+            return;
+        };
+        if scope.is_empty() {
+            self.error(
+                child,
+                &format!(
+                    "slot `{}` of <{}> has no scope, `slot_scope` of <{}> can not be used",
+                    slot, template.name, child.name
+                ),
+            );
+            return;
+        }
+        self.check_scope_props(child, slot, slot_scope, scope);
+    }
+
+    /// check `scope.prop` in the binds of the widget and its children
+    fn check_scope_props(
+        &mut self,
+        template: &Template,
+        slot: &str,
+        slot_scope: &str,
+        scope: &[String],
+    ) {
+        if let Some(binds) = template.binds.as_ref() {
+            let mut binds = binds.iter().collect::<Vec<_>>();
+            binds.sort_by(|(a, _), (b, _)| a.name.cmp(&b.name));
+            for (key, value) in binds {
+                let Value::Bind(Bind::Normal(idents)) = value else {
+                    continue;
+                };
+                let (Some(ident), Some(prop)) = (idents.first(), idents.get(1)) else {
+                    continue;
+                };
+                if ident.name != slot_scope || scope.contains(&prop.name) {
+                    continue;
+                }
+                let mut props = scope.to_vec();
+                props.sort();
+                self.error(
+                    template,
+                    &format!(
+                        "`{}.{}` in `:{}` is not in the scope of slot `{}`, expect one of: {}",
+                        slot_scope,
+                        prop.name,
+                        key.name,
+                        slot,
+                        props
+                            .iter()
+                            .map(|prop| format!("`{}`", prop))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                );
+            }
+        }
+        for child in template.children.iter().flatten() {
+            self.check_scope_props(child, slot, slot_scope, scope);
+        }
+    }
+}
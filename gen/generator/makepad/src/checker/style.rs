@@ -0,0 +1,53 @@
+use gen_analyzer::{IdClass, Style, StyleVisitor, Template};
+use gen_utils::error::Span;
+
+use super::Checker;
+
+impl Checker<'_> {
+    /// ## props overridden by more specific style rules
+    /// the same cascade as the generator, See [`StyleVisitor::visit`], each warning is located at the tag of `template`,
+    /// it is opt-in (`warn_override` in `[makepad]`), overriding is always allowed
+    pub fn overrides(&self) -> Vec<String> {
+        let (Some(template), Some(style)) = (self.template, self.style) else {
+            return vec![];
+        };
+        let mut warnings = vec![];
+        self.template_overrides(template, style, &mut vec![], &mut warnings);
+        warnings
+    }
+
+    fn template_overrides(
+        &self,
+        template: &Template,
+        style: &Style,
+        chain: &mut Vec<IdClass>,
+        warnings: &mut Vec<String>,
+    ) {
+        // class which can not be converted is reported when generating
+        if let Ok(cascade) =
+            StyleVisitor::visit(style, template.id.as_ref(), template.class.as_ref(), chain)
+        {
+            for overridden in cascade.overridden {
+                match template.span.as_ref() {
+                    Some(span) => {
+                        let mut span: Span = span.clone();
+                        span.set_file(&self.file);
+                        warnings.push(format!("{}\n{}", overridden, span));
+                    }
+                    None => warnings.push(overridden.to_string()),
+                }
+            }
+        }
+
+        if let Some(children) = template.children.as_ref() {
+            chain.push(IdClass {
+                id: template.id.clone(),
+                class: template.class.clone(),
+            });
+            for child in children {
+                self.template_overrides(child, style, chain, warnings);
+            }
+            chain.pop();
+        }
+    }
+}
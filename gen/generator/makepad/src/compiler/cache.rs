@@ -0,0 +1,490 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use gen_analyzer::Template;
+use gen_utils::{
+    common::{camel_to_snake, fs},
+    error::Error,
+};
+use toml_edit::{value, DocumentMut, InlineTable, Item, Table};
+
+use crate::{builtin::BuiltinWidgetType, model::AbsWidget};
+
+use super::{DepNode, WidgetPoll};
+
+/// version of the compiler, cache of other version is dropped
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// # Compile Cache
+/// incremental compile cache, stored in `.gen_cache/` of the project
+/// ```txt
+/// .gen_cache
+/// ├── cache.toml  (entries of all compiled `.gen` files)
+/// └── output
+///     └── $hash.rs (generated rust code)
+/// ```
+/// a `.gen` file is skipped if:
+/// 1. the hash of the file content (with compiler version) is not changed
+/// 2. custom components used by the file are not changed (compare by fingerprint of the define widget)
+///
+/// so when a custom component changes its props or events, all dependants are rebuilt
+#[derive(Debug, Clone, Default)]
+pub struct Cache {
+    /// dir of cache
+    pub path: PathBuf,
+    /// key: path of `.gen` file (relative to source project)
+    pub files: HashMap<String, CacheEntry>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CacheEntry {
+    /// hash of the file content, See [`Cache::hash`]
+    pub hash: String,
+    /// custom components used by the file, (snake name, fingerprint when compiled)
+    pub deps: HashMap<String, String>,
+    /// files imported by `import!` (key in cache), See [`super::DepGraph::resolve`]
+    pub imports: Vec<String>,
+    /// files imported by `@import` in style (key in cache), their contents are part of the hash
+    pub styles: Vec<String>,
+    /// the generated code has `apply_theme` or not, See [`crate::model::ThemeApply`]
+    pub themed: bool,
+    /// the generated code has `apply_responsive` or not, See [`crate::model::ResponsiveApply`]
+    pub responsive: bool,
+    /// define widgets pushed into `define_widget_poll` when the file is compiled
+    pub widgets: WidgetPoll,
+}
+
+impl Cache {
+    pub const DIR: &'static str = ".gen_cache";
+    /// ## load cache from `.gen_cache/cache.toml`
+    /// cache is always optional, if it can not be read or the version is changed, an empty cache is returned
+    pub fn load<P>(path: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref().join(Self::DIR);
+        let files = fs::read(path.join("cache.toml"))
+            .ok()
+            .and_then(|content| content.parse::<DocumentMut>().ok())
+            .filter(|doc| doc.get("version").and_then(|v| v.as_str()) == Some(VERSION))
+            .and_then(|doc| {
+                doc.get("files")
+                    .and_then(|files| files.as_table())
+                    .map(|files| {
+                        files
+                            .iter()
+                            .filter_map(|(key, item)| {
+                                CacheEntry::try_from(item)
+                                    .ok()
+                                    .map(|entry| (key.to_string(), entry))
+                            })
+                            .collect()
+                    })
+            })
+            .unwrap_or_default();
+
+        Self { path, files }
+    }
+    /// ## write cache to `.gen_cache/cache.toml`
+    /// outputs which are not used by any entry are removed
+    pub fn save(&self) -> Result<(), Error> {
+        let mut files = Table::new();
+        let mut keys = self.files.keys().collect::<Vec<_>>();
+        keys.sort();
+        for key in keys {
+            files.insert(key, (&self.files[key]).into());
+        }
+        let mut doc = DocumentMut::new();
+        doc.insert("version", value(VERSION));
+        doc.insert("files", Item::Table(files));
+        fs::write(self.path.join("cache.toml"), &doc.to_string())?;
+
+        // [clean outputs] -----------------------------------------------------------------------
+        if let Ok(outputs) = std::fs::read_dir(self.output_dir()) {
+            for output in outputs.filter_map(|entry| entry.ok()) {
+                let used = output
+                    .path()
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .is_some_and(|stem| self.files.values().any(|entry| entry.hash == stem));
+                if !used {
+                    let _ = fs::delete(output.path());
+                }
+            }
+        }
+        Ok(())
+    }
+    /// ## hash of the file
+    /// FNV-1a (64 bit) of the compiler version and all parts, the result is stable across runs and platforms
+    pub fn hash(parts: &[&str]) -> String {
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for part in std::iter::once(VERSION).chain(parts.iter().copied()) {
+            // split each part, `["ab", "c"]` and `["a", "bc"]` are different
+            for byte in part.bytes().chain(std::iter::once(0xff)) {
+                hash ^= byte as u64;
+                hash = hash.wrapping_mul(0x100000001b3);
+            }
+        }
+        format!("{:016x}", hash)
+    }
+    /// ## get the cached output of the file
+    /// return None if the file is changed or any custom component used by the file is changed
+    pub fn get(&self, key: &str, hash: &str, poll: &WidgetPoll) -> Option<(&CacheEntry, String)> {
+        let entry = self.files.get(key)?;
+        if entry.hash != hash
+            || entry
+                .deps
+                .iter()
+                .any(|(dep, fingerprint)| &fingerprint_of(poll.get(dep)) != fingerprint)
+        {
+            return None;
+        }
+        let output = fs::read(self.output(hash)).ok()?;
+        Some((entry, output))
+    }
+    /// ## insert the compiled result of the file
+    /// - `node`: custom components used and files imported by the file, See [`DepNode`]
+    /// - `poll`: `define_widget_poll` before compile, used to get the fingerprint of deps
+    /// - `widgets`: define widgets pushed by the file
+    /// - `themed`: the output has `apply_theme` or not
+    /// - `responsive`: the output has `apply_responsive` or not
+    #[allow(clippy::too_many_arguments)]
+    pub fn insert(
+        &mut self,
+        key: String,
+        hash: String,
+        node: &DepNode,
+        poll: &WidgetPoll,
+        widgets: WidgetPoll,
+        output: &str,
+        themed: bool,
+        responsive: bool,
+    ) -> Result<(), Error> {
+        let deps = node
+            .uses
+            .iter()
+            .map(|name| (name.to_string(), fingerprint_of(poll.get(name))))
+            .collect();
+
+        fs::write(self.output(&hash), output)?;
+        self.files.insert(
+            key,
+            CacheEntry {
+                hash,
+                deps,
+                imports: node.imports.clone(),
+                styles: node.styles.clone(),
+                themed,
+                responsive,
+                widgets,
+            },
+        );
+        Ok(())
+    }
+    /// ## custom components (snake name) used in the template of the file
+    pub fn deps(template: Option<&Template>) -> Vec<String> {
+        let mut names = vec![];
+        if let Some(template) = template {
+            custom_components(template, &mut names);
+        }
+        names
+    }
+    /// ## remove the cache of the file or all files in the dir
+    pub fn remove(&mut self, key: &str) {
+        let dir = format!("{}/", key.trim_end_matches('/'));
+        self.files
+            .retain(|file, _| file != key && !file.starts_with(&dir));
+    }
+    fn output_dir(&self) -> PathBuf {
+        self.path.join("output")
+    }
+    fn output(&self, hash: &str) -> PathBuf {
+        self.output_dir().join(format!("{}.rs", hash))
+    }
+}
+
+/// snake names of custom components used in template (not builtin widget)
+fn custom_components(template: &Template, names: &mut Vec<String>) {
+    if !template.is_component() && BuiltinWidgetType::from_str(&template.name).is_err() {
+        let name = camel_to_snake(&template.name);
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    if let Some(children) = template.children.as_ref() {
+        for child in children {
+            custom_components(child, names);
+        }
+    }
+}
+
+/// ## fingerprint of widget in `define_widget_poll`
+/// props and events are sorted, so the fingerprint is stable, empty if the widget is not in poll
+fn fingerprint_of(widget: Option<&AbsWidget>) -> String {
+    fn sorted(map: Option<&HashMap<String, String>>) -> String {
+        let mut items = map
+            .map(|map| {
+                map.iter()
+                    .map(|(k, v)| format!("{}:{}", k, v))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        items.sort();
+        items.join(",")
+    }
+
+    match widget {
+        Some(AbsWidget::Builtin(builtin)) => builtin.snake_name().to_string(),
+        Some(AbsWidget::Define {
+            name,
+            props,
+            events,
+            slots,
+        }) => {
+            let slots = slots.as_ref().map(|slots| {
+                slots
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.join(" ")))
+                    .collect::<HashMap<_, _>>()
+            });
+            Cache::hash(&[
+                name.as_str(),
+                sorted(props.as_ref()).as_str(),
+                sorted(events.as_ref()).as_str(),
+                sorted(slots.as_ref()).as_str(),
+            ])
+        }
+        None => String::new(),
+    }
+}
+
+impl From<&CacheEntry> for Item {
+    fn from(entry: &CacheEntry) -> Self {
+        fn inline(map: &HashMap<String, String>) -> InlineTable {
+            let mut keys = map.keys().collect::<Vec<_>>();
+            keys.sort();
+            keys.into_iter()
+                .map(|key| (key.to_string(), toml_edit::Value::from(map[key].as_str())))
+                .collect()
+        }
+
+        let mut table = Table::new();
+        table.insert("hash", value(entry.hash.as_str()));
+        table.insert("deps", value(inline(&entry.deps)));
+        table.insert(
+            "imports",
+            value(entry.imports.iter().collect::<toml_edit::Array>()),
+        );
+        if !entry.styles.is_empty() {
+            table.insert(
+                "styles",
+                value(entry.styles.iter().collect::<toml_edit::Array>()),
+            );
+        }
+        if entry.themed {
+            table.insert("themed", value(true));
+        }
+        if entry.responsive {
+            table.insert("responsive", value(true));
+        }
+        let mut widgets = Table::new();
+        let mut names = entry.widgets.keys().collect::<Vec<_>>();
+        names.sort();
+        for name in names {
+            if let AbsWidget::Define {
+                name: widget_name,
+                props,
+                events,
+                slots,
+            } = &entry.widgets[name]
+            {
+                let mut widget = Table::new();
+                widget.insert("name", value(widget_name.as_str()));
+                if let Some(props) = props.as_ref() {
+                    widget.insert("props", value(inline(props)));
+                }
+                if let Some(events) = events.as_ref() {
+                    widget.insert("events", value(inline(events)));
+                }
+                if let Some(slots) = slots.as_ref() {
+                    let mut keys = slots.keys().collect::<Vec<_>>();
+                    keys.sort();
+                    let slots = keys
+                        .into_iter()
+                        .map(|key| {
+                            let scope = slots[key].iter().collect::<toml_edit::Array>();
+                            (key.to_string(), toml_edit::Value::from(scope))
+                        })
+                        .collect::<InlineTable>();
+                    widget.insert("slots", value(slots));
+                }
+                widgets.insert(name, Item::Table(widget));
+            }
+        }
+        table.insert("widgets", Item::Table(widgets));
+        Item::Table(table)
+    }
+}
+
+impl TryFrom<&Item> for CacheEntry {
+    type Error = Error;
+
+    fn try_from(item: &Item) -> Result<Self, Self::Error> {
+        fn map(item: Option<&Item>) -> Option<HashMap<String, String>> {
+            item.and_then(|item| item.as_inline_table()).map(|table| {
+                table
+                    .iter()
+                    .filter_map(|(k, v)| v.as_str().map(|v| (k.to_string(), v.to_string())))
+                    .collect()
+            })
+        }
+
+        fn slots(item: Option<&Item>) -> Option<HashMap<String, Vec<String>>> {
+            item.and_then(|item| item.as_inline_table()).map(|table| {
+                table
+                    .iter()
+                    .filter_map(|(k, v)| {
+                        let scope = v
+                            .as_array()?
+                            .iter()
+                            .filter_map(|v| v.as_str().map(|v| v.to_string()))
+                            .collect();
+                        Some((k.to_string(), scope))
+                    })
+                    .collect()
+            })
+        }
+
+        fn array(item: Option<&Item>) -> Vec<String> {
+            item.and_then(|item| item.as_array())
+                .map(|array| {
+                    array
+                        .iter()
+                        .filter_map(|v| v.as_str().map(|v| v.to_string()))
+                        .collect()
+                })
+                .unwrap_or_default()
+        }
+
+        let table = item
+            .as_table()
+            .ok_or_else(|| Error::from("cache entry should be a table"))?;
+        let hash = table
+            .get("hash")
+            .and_then(|hash| hash.as_str())
+            .ok_or_else(|| Error::from("cache entry has no hash"))?
+            .to_string();
+        let deps = map(table.get("deps")).unwrap_or_default();
+        let imports = array(table.get("imports"));
+        let styles = array(table.get("styles"));
+        let themed = table
+            .get("themed")
+            .and_then(|themed| themed.as_bool())
+            .unwrap_or_default();
+        let responsive = table
+            .get("responsive")
+            .and_then(|responsive| responsive.as_bool())
+            .unwrap_or_default();
+        let widgets = table
+            .get("widgets")
+            .and_then(|widgets| widgets.as_table())
+            .map(|widgets| {
+                widgets
+                    .iter()
+                    .filter_map(|(key, widget)| {
+                        let name = widget.get("name")?.as_str()?.to_string();
+                        Some((
+                            key.to_string(),
+                            AbsWidget::Define {
+                                name,
+                                props: map(widget.get("props")),
+                                events: map(widget.get("events")),
+                                slots: slots(widget.get("slots")),
+                            },
+                        ))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(Self {
+            hash,
+            deps,
+            imports,
+            styles,
+            themed,
+            responsive,
+            widgets,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test_cache {
+    use std::collections::HashMap;
+
+    use crate::model::AbsWidget;
+
+    use super::{Cache, CacheEntry};
+
+    #[test]
+    fn hash() {
+        assert_eq!(Cache::hash(&["a", "b"]), Cache::hash(&["a", "b"]));
+        assert_ne!(Cache::hash(&["ab", "c"]), Cache::hash(&["a", "bc"]));
+    }
+
+    #[test]
+    fn entry_to_toml() {
+        let entry = CacheEntry {
+            hash: Cache::hash(&["hello"]),
+            deps: vec![("button_a".to_string(), String::new())]
+                .into_iter()
+                .collect(),
+            imports: vec!["components/hello.gen".to_string()],
+            styles: vec!["components/theme.gen".to_string()],
+            themed: true,
+            responsive: false,
+            widgets: vec![(
+                "hello".to_string(),
+                AbsWidget::Define {
+                    name: "Hello".to_string(),
+                    props: None,
+                    events: Some(
+                        vec![("Clicked".to_string(), "(String)".to_string())]
+                            .into_iter()
+                            .collect::<HashMap<_, _>>(),
+                    ),
+                    slots: Some(
+                        vec![
+                            ("header".to_string(), vec!["title".to_string()]),
+                            ("default".to_string(), vec![]),
+                        ]
+                        .into_iter()
+                        .collect(),
+                    ),
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+        let item: toml_edit::Item = (&entry).into();
+        let back = CacheEntry::try_from(&item).unwrap();
+        assert_eq!(back.hash, entry.hash);
+        assert_eq!(back.deps, entry.deps);
+        assert_eq!(back.imports, entry.imports);
+        assert_eq!(back.styles, entry.styles);
+        assert!(back.themed);
+        assert!(!back.responsive);
+        assert_eq!(
+            super::fingerprint_of(back.widgets.get("hello")),
+            super::fingerprint_of(entry.widgets.get("hello"))
+        );
+        let Some(AbsWidget::Define { slots, .. }) = back.widgets.get("hello") else {
+            panic!("hello should be a define widget");
+        };
+        assert_eq!(slots.as_ref().unwrap()["header"], vec!["title".to_string()]);
+    }
+}
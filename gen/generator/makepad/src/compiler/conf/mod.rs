@@ -1,4 +1,5 @@
 mod router;
+mod theme;
 mod wasm;
 // mod mini_test;
 
@@ -10,13 +11,14 @@ use gen_utils::{
 };
 pub use router::*;
 use std::{fmt::Display, path::PathBuf, str::FromStr};
+pub use theme::*;
 
 use toml_edit::{value, Array, Formatted, Item, Table, Value};
 use wasm::WasmConf;
 
 use crate::builtin::widget::RootConf;
 
-pub const CONF_FORMAT_SUGGESTION: &str = r#"
+pub const CONF_FORMAT_SUGGESTION: &str = r##"
 ## Easy Format for Makepad Config
 ```toml
 [makepad.root]
@@ -26,6 +28,7 @@ path = "/path/to/root.gen"
 ```toml
 [makepad]
 entry = "app"
+warn_override = true
 [makepad.root]
 path = "/path/to/root.gen"
 [makepad.dependencies]
@@ -33,8 +36,14 @@ makepad-widgets = {path = "/path/to/makepad-widgets"}
 [makepad.wasm]
 fresh = true
 port = 8016
+[makepad.theme]
+default = "light"
+[makepad.theme.light]
+primary_500 = "#3B82F6"
+[makepad.theme.dark]
+primary_500 = "#1D4ED8"
 ```
-"#;
+"##;
 
 /// # Makepad Config
 /// See [FORMAT]
@@ -53,6 +62,10 @@ pub struct Config {
     /// makepad wasm
     pub wasm: Option<WasmConf>,
     pub router: Option<PathBuf>,
+    /// report props which are overridden by more specific style rules as warnings, default is false
+    pub warn_override: bool,
+    /// design tokens used by `token(name)` in `<style>`, See [`ThemeConf`]
+    pub theme: Option<ThemeConf>,
 }
 
 impl Config {
@@ -63,6 +76,8 @@ impl Config {
             dependencies: None,
             wasm: None,
             router: None,
+            warn_override: false,
+            theme: None,
         }
     }
     pub fn push_dep(&mut self, dep: RustDependence) {
@@ -112,6 +127,14 @@ impl From<&Config> for Item {
             table.insert("routers", value(arr));
         }
 
+        if conf.warn_override {
+            table.insert("warn_override", value(true));
+        }
+
+        if let Some(theme) = conf.theme.as_ref() {
+            table.insert("theme", theme.into());
+        }
+
         // here need to wrap a new table outside key is makepad
         let mut makepad_table = Table::new();
         makepad_table.insert("makepad", Item::Table(table));
@@ -178,12 +201,27 @@ impl TryFrom<Option<&Table>> for Config {
                 None
             };
 
+            // [warn_override] ------------------------------------------------------------------------------------------
+            let warn_override = table
+                .get("warn_override")
+                .and_then(|v| v.as_bool())
+                .unwrap_or_default();
+
+            // [theme] --------------------------------------------------------------------------------------------------
+            let theme = if let Some(theme) = table.get("theme") {
+                Some(ThemeConf::try_from(theme)?)
+            } else {
+                None
+            };
+
             return Ok(Self {
                 entry,
                 root,
                 dependencies,
                 wasm,
                 router,
+                warn_override,
+                theme,
             });
         }
 
@@ -228,6 +266,8 @@ mod test_conf {
             ]),
             wasm: None,
             router: None,
+            warn_override: false,
+            theme: None,
         };
 
         let toml = conf.to_string();
@@ -236,7 +276,7 @@ mod test_conf {
 
     #[test]
     fn deserde() {
-        let input = r#"
+        let input = r##"
 [makepad]
 entry = "app1"
 root = "/path/to/root.gen"
@@ -246,11 +286,14 @@ gen_ui = {path = "/path/to/gen_ui"}
 [makepad.wasm]
 fresh = true
 port = 8016
-        "#;
+[makepad.theme.light]
+primary_500 = "#3B82F6"
+        "##;
 
         let table = input.parse::<toml_edit::DocumentMut>().unwrap();
 
         let config = Config::try_from(&table["makepad"]).unwrap();
+        assert!(matches!(config.theme, Some(super::ThemeConf::Inline(_))));
 
         println!("{}", config);
     }
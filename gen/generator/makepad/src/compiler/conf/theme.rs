@@ -0,0 +1,233 @@
+use std::{collections::BTreeMap, fmt::Display, path::Path, path::PathBuf};
+
+use gen_utils::{
+    common::{fs, fs::path_to_str},
+    err_from_to,
+    error::{ConvertError, Error, ParseError, ParseType},
+};
+use toml_edit::{value, DocumentMut, Item, Table};
+
+/// # Theme Config
+/// design tokens can be written in `[makepad.theme]` or an independent toml file:
+/// ```toml
+/// [makepad]
+/// theme = "theme.toml"
+/// ```
+/// the path is relative to the source project, the format of the file is the same as `[makepad.theme]`,
+/// See [`DesignTokens`]
+#[derive(Debug, Clone)]
+pub enum ThemeConf {
+    Path(PathBuf),
+    Inline(DesignTokens),
+}
+
+impl ThemeConf {
+    /// ## load design tokens
+    /// - `from_path`: path of the source project
+    pub fn load<P>(&self, from_path: P) -> Result<DesignTokens, Error>
+    where
+        P: AsRef<Path>,
+    {
+        match self {
+            ThemeConf::Path(path) => {
+                let content = fs::read(from_path.as_ref().join(path))?;
+                let doc = content.parse::<DocumentMut>().map_err(|e| {
+                    Error::Parse(ParseError::new(
+                        &format!("theme `{}` is invalid: {}", path_to_str(path), e),
+                        ParseType::Toml,
+                    ))
+                })?;
+                DesignTokens::try_from(doc.as_table())
+            }
+            ThemeConf::Inline(tokens) => Ok(tokens.clone()),
+        }
+    }
+}
+
+impl TryFrom<&Item> for ThemeConf {
+    type Error = Error;
+
+    fn try_from(value: &Item) -> Result<Self, Self::Error> {
+        if let Some(path) = value.as_str() {
+            return Ok(ThemeConf::Path(PathBuf::from(path)));
+        }
+        value.as_table().map_or_else(
+            || Err(err_from_to!("toml::Item" => "Theme, theme should be a path or a table")),
+            |table| DesignTokens::try_from(table).map(ThemeConf::Inline),
+        )
+    }
+}
+
+impl From<&ThemeConf> for Item {
+    fn from(conf: &ThemeConf) -> Self {
+        match conf {
+            ThemeConf::Path(path) => value(path_to_str(path)),
+            ThemeConf::Inline(tokens) => tokens.into(),
+        }
+    }
+}
+
+/// # Design Tokens
+/// each sub table is a theme, tokens are named values (color, size, font ...) used by `token(name)` in `<style>`
+/// ```toml
+/// [makepad.theme]
+/// default = "light"
+/// [makepad.theme.light]
+/// primary_500 = "#3B82F6"
+/// font_size_md = 14
+/// [makepad.theme.dark]
+/// primary_500 = "#1D4ED8"
+/// ```
+/// - `default`: the theme used when the app starts, default is the first theme
+/// - a token which is not defined in a theme falls back to the default theme
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DesignTokens {
+    pub default: String,
+    /// themes in the order of definition
+    pub themes: Vec<Theme>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Theme {
+    pub name: String,
+    /// token name => value in style, such as `#3B82F6`, `14`
+    pub tokens: BTreeMap<String, String>,
+}
+
+impl DesignTokens {
+    pub fn default_theme(&self) -> Option<&Theme> {
+        self.theme(&self.default)
+    }
+    pub fn theme(&self, name: &str) -> Option<&Theme> {
+        self.themes.iter().find(|theme| theme.name == name)
+    }
+    /// ## value of the token in the theme
+    /// fall back to the default theme if the token is not defined in the theme
+    pub fn token(&self, theme: &str, name: &str) -> Option<&str> {
+        self.theme(theme)
+            .and_then(|theme| theme.tokens.get(name))
+            .or_else(|| self.default_theme()?.tokens.get(name))
+            .map(|value| value.as_str())
+    }
+}
+
+impl TryFrom<&Table> for DesignTokens {
+    type Error = Error;
+
+    fn try_from(table: &Table) -> Result<Self, Self::Error> {
+        let mut themes = vec![];
+        for (name, item) in table.iter().filter(|(name, _)| *name != "default") {
+            let tokens = item.as_table_like().ok_or_else(|| {
+                err_from_to!("toml::Item" => &format!("Theme, theme `{}` should be a table", name))
+            })?;
+            let mut theme = Theme {
+                name: name.to_string(),
+                tokens: BTreeMap::new(),
+            };
+            for (token, v) in tokens.iter() {
+                let v = v.as_value().and_then(|v| match v {
+                    toml_edit::Value::String(s) => Some(s.value().to_string()),
+                    toml_edit::Value::Integer(i) => Some(i.value().to_string()),
+                    toml_edit::Value::Float(f) => Some(f.value().to_string()),
+                    _ => None,
+                });
+                let v = v.ok_or_else(|| {
+                    err_from_to!("toml::Item" => &format!("Theme, token `{}` of `{}` should be a string or number", token, name))
+                })?;
+                theme.tokens.insert(token.to_string(), v);
+            }
+            themes.push(theme);
+        }
+
+        let default = match table.get("default") {
+            Some(default) => default
+                .as_str()
+                .ok_or_else(|| err_from_to!("toml::Value" => "String"))?
+                .to_string(),
+            None => themes
+                .first()
+                .map(|theme| theme.name.to_string())
+                .unwrap_or_default(),
+        };
+
+        if !themes.iter().any(|theme| theme.name == default) {
+            return Err(ConvertError::FromTo {
+                from: "toml::Table".to_string(),
+                to: format!("Theme, default theme `{}` is not defined", default),
+            }
+            .into());
+        }
+
+        Ok(Self { default, themes })
+    }
+}
+
+impl From<&DesignTokens> for Item {
+    fn from(tokens: &DesignTokens) -> Self {
+        let mut table = Table::new();
+        table.insert("default", value(tokens.default.as_str()));
+        for theme in &tokens.themes {
+            let mut theme_table = Table::new();
+            for (name, v) in &theme.tokens {
+                let v = match v.parse::<i64>() {
+                    Ok(i) => value(i),
+                    Err(_) => match v.parse::<f64>() {
+                        Ok(f) => value(f),
+                        Err(_) => value(v.as_str()),
+                    },
+                };
+                theme_table.insert(name, v);
+            }
+            table.insert(&theme.name, Item::Table(theme_table));
+        }
+        Item::Table(table)
+    }
+}
+
+impl Display for DesignTokens {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // sub tables are only displayed in document
+        let mut doc = DocumentMut::new();
+        if let Item::Table(table) = Item::from(self) {
+            *doc.as_table_mut() = table;
+        }
+        f.write_str(doc.to_string().as_str())
+    }
+}
+
+#[cfg(test)]
+mod test_theme {
+    use super::DesignTokens;
+
+    #[test]
+    fn tokens() {
+        let input = r##"
+[makepad.theme]
+default = "light"
+[makepad.theme.light]
+primary_500 = "#3B82F6"
+font_size_md = 14
+[makepad.theme.dark]
+primary_500 = "#1D4ED8"
+        "##;
+        let doc = input.parse::<toml_edit::DocumentMut>().unwrap();
+        let tokens = DesignTokens::try_from(doc["makepad"]["theme"].as_table().unwrap()).unwrap();
+        assert_eq!(tokens.default, "light");
+        assert_eq!(tokens.token("dark", "primary_500"), Some("#1D4ED8"));
+        // fall back to the default theme
+        assert_eq!(tokens.token("dark", "font_size_md"), Some("14"));
+        assert_eq!(tokens.token("dark", "unknown"), None);
+
+        // round trip
+        let doc = tokens
+            .to_string()
+            .parse::<toml_edit::DocumentMut>()
+            .unwrap();
+        assert_eq!(DesignTokens::try_from(doc.as_table()).unwrap(), tokens);
+
+        let doc = "default = \"blue\"\n[light]\na = 1"
+            .parse::<toml_edit::DocumentMut>()
+            .unwrap();
+        assert!(DesignTokens::try_from(doc.as_table()).is_err());
+    }
+}
@@ -10,7 +10,7 @@ use gen_utils::error::Error;
 
 use crate::model::{AbsWidget, SimpleAppMain};
 
-use super::RouterBuilder;
+use super::{DesignTokens, RouterBuilder, ThemeConf};
 
 /// in other: Key: WidgetID, Value: AbsWidget
 /// in ctx(define widget poll): Key: WidgetName, Value: AbsWidget
@@ -38,6 +38,8 @@ pub struct Context {
     pub dyn_processor: Option<DynProcessor>,
     /// routers
     pub router: Option<RouterBuilder>,
+    /// design tokens, See [`DesignTokens`]
+    pub theme: Option<DesignTokens>,
     // /// global active router
     // pub active_router: Option<RouterBuilder>
 }
@@ -52,6 +54,7 @@ impl Default for Context {
             dyn_processor: None,
             lib_content: None,
             router: None,
+            theme: None,
             // active_router: None
         }
     }
@@ -70,4 +73,11 @@ impl Context {
         self.router = Some(router);
         Ok(())
     }
+    pub fn load_theme<P>(&mut self, theme: &ThemeConf, from_path: P) -> Result<(), Error>
+    where
+        P: AsRef<Path>,
+    {
+        self.theme = Some(theme.load(from_path)?);
+        Ok(())
+    }
 }
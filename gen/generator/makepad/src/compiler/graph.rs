@@ -0,0 +1,420 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use gen_utils::common::fs::path_to_str;
+
+use super::{CacheEntry, WidgetPoll};
+
+/// # Dependency Graph
+/// component usage graph between `.gen` files, each node is a file (key in cache, See [`super::Cache`])
+/// ```txt
+/// views/home.gen ──import!──► components/hello.gen
+///       │                           ▲
+///       └────────<Hello>────────────┘ (defines `hello`)
+/// ```
+/// a file depends on:
+/// - files imported by `import!` in `<script>`
+/// - files imported by `@import` in `<style>`
+/// - files which define the custom components used as tags in `<template>`
+///
+/// so when a file changes, only the file and its dependants need to be recompiled, See [`DepGraph::dependants`]
+#[derive(Debug, Clone, Default)]
+pub struct DepGraph {
+    pub nodes: HashMap<String, DepNode>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DepNode {
+    /// files imported by `import!` (key in cache), See [`DepGraph::resolve`]
+    pub imports: Vec<String>,
+    /// files imported by `@import` in style (key in cache), See [`gen_analyzer::Model::style_imports`]
+    pub styles: Vec<String>,
+    /// custom components used in template (snake name)
+    pub uses: Vec<String>,
+    /// define widgets of the file (snake name)
+    pub defines: Vec<String>,
+}
+
+impl DepNode {
+    pub fn new(
+        imports: Vec<String>,
+        styles: Vec<String>,
+        uses: Vec<String>,
+        widgets: &WidgetPoll,
+    ) -> Self {
+        let mut defines = widgets.keys().cloned().collect::<Vec<_>>();
+        defines.sort();
+        Self {
+            imports,
+            styles,
+            uses,
+            defines,
+        }
+    }
+}
+
+impl From<&CacheEntry> for DepNode {
+    fn from(entry: &CacheEntry) -> Self {
+        let mut uses = entry.deps.keys().cloned().collect::<Vec<_>>();
+        uses.sort();
+        Self::new(
+            entry.imports.clone(),
+            entry.styles.clone(),
+            uses,
+            &entry.widgets,
+        )
+    }
+}
+
+impl DepGraph {
+    /// ## insert or replace the node of the file
+    pub fn insert(&mut self, key: String, node: DepNode) {
+        self.nodes.insert(key, node);
+    }
+    /// ## remove the file or all files in the dir, return the removed nodes (sorted by key)
+    pub fn remove(&mut self, key: &str) -> Vec<(String, DepNode)> {
+        self.keys_of(key)
+            .into_iter()
+            .filter_map(|key| self.nodes.remove(&key).map(|node| (key, node)))
+            .collect()
+    }
+    /// ## keys of the file or all files in the dir (sorted)
+    pub fn keys_of(&self, key: &str) -> Vec<String> {
+        let dir = format!("{}/", key.trim_end_matches('/'));
+        let mut keys = self
+            .nodes
+            .keys()
+            .filter(|file| *file == key || file.starts_with(&dir))
+            .cloned()
+            .collect::<Vec<_>>();
+        keys.sort();
+        keys
+    }
+    /// ## resolve `import!` entry to the key of the imported file
+    /// `crate::components::hello::*` or `crate::components::hello::Hello` => `components/hello.gen`,
+    /// the longest path which `exists` is used, return None if the import is not from current crate
+    pub fn resolve<F>(import: &[String], exists: F) -> Option<String>
+    where
+        F: Fn(&str) -> bool,
+    {
+        let (first, segments) = import.split_first()?;
+        if first != "crate" {
+            return None;
+        }
+        (1..=segments.len()).rev().find_map(|len| {
+            let key = path_to_str(
+                segments[..len]
+                    .iter()
+                    .collect::<std::path::PathBuf>()
+                    .with_extension("gen"),
+            );
+            exists(&key).then_some(key)
+        })
+    }
+    /// ## files which the file depends on directly (sorted)
+    pub fn dependencies(&self, key: &str) -> Vec<String> {
+        let Some(node) = self.nodes.get(key) else {
+            return vec![];
+        };
+        let mut deps = node
+            .imports
+            .iter()
+            .chain(node.styles.iter())
+            .filter(|dep| *dep != key)
+            .cloned()
+            .chain(self.uses(key))
+            .collect::<BTreeSet<_>>();
+        // self import is a cycle
+        if node
+            .imports
+            .iter()
+            .chain(&node.styles)
+            .any(|dep| dep == key)
+        {
+            deps.insert(key.to_string());
+        }
+        deps.into_iter().collect()
+    }
+    /// ## files which define the custom components used as tags in the file (sorted)
+    /// self usage is included, the component instantiates itself
+    pub fn uses(&self, key: &str) -> Vec<String> {
+        let Some(node) = self.nodes.get(key) else {
+            return vec![];
+        };
+        let mut uses = node
+            .uses
+            .iter()
+            .flat_map(|name| self.definers(name))
+            .filter(|dep| dep != key)
+            .collect::<BTreeSet<_>>();
+        if node.uses.iter().any(|name| node.defines.contains(name)) {
+            uses.insert(key.to_string());
+        }
+        uses.into_iter().collect()
+    }
+    /// ## files which depend on the file (or files in the dir) directly or indirectly
+    /// the files themselves are not included, the result is in compile order:
+    /// a file is always after the files it depends on (files in cycle are sorted by key at the end)
+    pub fn dependants(&self, key: &str) -> Vec<String> {
+        let changed = self.keys_of(key);
+        let reverse = self.reverse();
+        let mut dependants = BTreeSet::new();
+        let mut stack = changed.clone();
+        while let Some(key) = stack.pop() {
+            for dependant in reverse.get(&key).into_iter().flatten() {
+                if !changed.contains(dependant) && dependants.insert(dependant.to_string()) {
+                    stack.push(dependant.to_string());
+                }
+            }
+        }
+
+        // [topological sort] ----------------------------------------------------------------------
+        let mut degrees = dependants
+            .iter()
+            .map(|key| {
+                let degree = self
+                    .dependencies(key)
+                    .iter()
+                    .filter(|dep| dependants.contains(*dep))
+                    .count();
+                (key.to_string(), degree)
+            })
+            .collect::<HashMap<_, _>>();
+        let mut ready = degrees
+            .iter()
+            .filter(|(_, degree)| **degree == 0)
+            .map(|(key, _)| key.to_string())
+            .collect::<BTreeSet<_>>();
+        let mut sorted = vec![];
+        while let Some(key) = ready.pop_first() {
+            degrees.remove(&key);
+            for dependant in reverse.get(&key).into_iter().flatten() {
+                if let Some(degree) = degrees.get_mut(dependant) {
+                    *degree -= 1;
+                    if *degree == 0 {
+                        ready.insert(dependant.to_string());
+                    }
+                }
+            }
+            sorted.push(key);
+        }
+        let mut rest = degrees.into_keys().collect::<Vec<_>>();
+        rest.sort();
+        sorted.extend(rest);
+        sorted
+    }
+    /// ## all component cycles in the graph
+    /// each cycle is a path starts and ends with the smallest key, such as `[a, b, a]`, See [`DepGraph::cycle_of`]
+    pub fn cycles(&self) -> Vec<Vec<String>> {
+        let mut keys = self.nodes.keys().collect::<Vec<_>>();
+        keys.sort();
+        let mut visited = HashSet::new();
+        let mut cycles = vec![];
+        for key in keys {
+            if visited.contains(key) {
+                continue;
+            }
+            if let Some(cycle) = self.cycle_of(key) {
+                visited.extend(cycle.iter().cloned());
+                cycles.push(cycle);
+            }
+        }
+        cycles
+    }
+    /// ## the shortest component cycle through the file, None if the file is not in a cycle
+    /// only the custom components used as tags are followed (components instantiate each other),
+    /// cycles of `import!` (allowed by rust modules) and `@import` (each stylesheet is merged once,
+    /// See [`gen_analyzer::Model::style_imports`]) are not errors
+    pub fn cycle_of(&self, key: &str) -> Option<Vec<String>> {
+        // bfs from the file, the first path back to the file is the shortest cycle
+        let mut parents: HashMap<String, String> = HashMap::new();
+        let mut queue = std::collections::VecDeque::from([key.to_string()]);
+        while let Some(current) = queue.pop_front() {
+            for dep in self.uses(&current) {
+                if dep == key {
+                    let mut cycle = vec![dep, current.clone()];
+                    let mut node = current;
+                    while let Some(parent) = parents.get(&node) {
+                        cycle.push(parent.to_string());
+                        node = parent.to_string();
+                    }
+                    cycle.reverse();
+                    // cycle: [key, ..., current, key]
+                    return Some(cycle);
+                }
+                if dep != current && !parents.contains_key(&dep) && self.nodes.contains_key(&dep) {
+                    parents.insert(dep.clone(), current.clone());
+                    queue.push_back(dep);
+                }
+            }
+        }
+        None
+    }
+    /// files which define the component
+    fn definers(&self, name: &str) -> Vec<String> {
+        self.nodes
+            .iter()
+            .filter(|(_, node)| node.defines.iter().any(|define| define == name))
+            .map(|(key, _)| key.to_string())
+            .collect()
+    }
+    /// key => files depend on it directly
+    fn reverse(&self) -> HashMap<String, Vec<String>> {
+        let mut reverse: HashMap<String, Vec<String>> = HashMap::new();
+        for key in self.nodes.keys() {
+            for dep in self.dependencies(key) {
+                reverse.entry(dep).or_default().push(key.to_string());
+            }
+        }
+        reverse
+    }
+}
+
+#[cfg(test)]
+mod test_graph {
+    use super::{DepGraph, DepNode};
+
+    fn node(imports: &[&str], uses: &[&str], defines: &[&str]) -> DepNode {
+        let strings = |items: &[&str]| items.iter().map(|s| s.to_string()).collect();
+        DepNode {
+            imports: strings(imports),
+            uses: strings(uses),
+            defines: strings(defines),
+            ..Default::default()
+        }
+    }
+
+    fn graph() -> DepGraph {
+        let mut graph = DepGraph::default();
+        graph.insert(
+            "root.gen".to_string(),
+            node(&["views/home.gen"], &["home"], &[]),
+        );
+        graph.insert(
+            "views/home.gen".to_string(),
+            node(&[], &["hello", "my_button"], &["home"]),
+        );
+        graph.insert(
+            "components/hello.gen".to_string(),
+            node(&[], &[], &["hello"]),
+        );
+        graph.insert(
+            "components/button.gen".to_string(),
+            node(&[], &["hello"], &["my_button"]),
+        );
+        graph
+    }
+
+    #[test]
+    fn resolve() {
+        let import = |s: &str| s.split("::").map(|s| s.to_string()).collect::<Vec<_>>();
+        let exists = |key: &str| key == "components/hello.gen";
+        assert_eq!(
+            DepGraph::resolve(&import("crate::components::hello::*"), exists),
+            Some("components/hello.gen".to_string())
+        );
+        assert_eq!(
+            DepGraph::resolve(&import("crate::components::hello::Hello"), exists),
+            Some("components/hello.gen".to_string())
+        );
+        assert_eq!(DepGraph::resolve(&import("std::fmt::Debug"), exists), None);
+    }
+
+    #[test]
+    fn dependants() {
+        let graph = graph();
+        assert_eq!(
+            graph.dependants("components/hello.gen"),
+            vec!["components/button.gen", "views/home.gen", "root.gen"]
+        );
+        assert_eq!(
+            graph.dependants("components"),
+            vec!["views/home.gen", "root.gen"]
+        );
+        assert!(graph.dependants("root.gen").is_empty());
+        assert!(graph.cycles().is_empty());
+    }
+
+    #[test]
+    fn styles() {
+        let mut graph = graph();
+        graph.insert("components/theme.gen".to_string(), node(&[], &[], &[]));
+        graph.insert(
+            "components/button.gen".to_string(),
+            DepNode {
+                styles: vec!["components/theme.gen".to_string()],
+                ..node(&[], &["hello"], &["my_button"])
+            },
+        );
+        assert_eq!(
+            graph.dependencies("components/button.gen"),
+            vec!["components/hello.gen", "components/theme.gen"]
+        );
+        assert_eq!(
+            graph.dependants("components/theme.gen"),
+            vec!["components/button.gen", "views/home.gen", "root.gen"]
+        );
+    }
+
+    #[test]
+    fn cycles() {
+        let mut graph = graph();
+        // hello imports home: module cycle is allowed
+        graph.insert(
+            "components/hello.gen".to_string(),
+            node(&["views/home.gen"], &[], &["hello"]),
+        );
+        assert!(graph
+            .dependencies("components/hello.gen")
+            .contains(&"views/home.gen".to_string()));
+        assert!(graph.cycle_of("components/hello.gen").is_none());
+        assert!(graph.cycles().is_empty());
+        // hello uses <home>: components instantiate each other
+        graph.insert(
+            "components/hello.gen".to_string(),
+            node(&[], &["home"], &["hello"]),
+        );
+        assert_eq!(
+            graph.cycle_of("components/hello.gen").unwrap(),
+            vec![
+                "components/hello.gen",
+                "views/home.gen",
+                "components/hello.gen"
+            ]
+        );
+        // button -> hello -> home -> button, hello and home are in the same cycle
+        assert_eq!(
+            graph.cycles(),
+            vec![vec![
+                "components/button.gen",
+                "components/hello.gen",
+                "views/home.gen",
+                "components/button.gen"
+            ]]
+        );
+        assert!(graph.cycle_of("root.gen").is_none());
+        // self usage
+        graph.insert(
+            "components/hello.gen".to_string(),
+            node(&[], &["hello"], &["hello"]),
+        );
+        assert_eq!(
+            graph.cycle_of("components/hello.gen"),
+            Some(vec![
+                "components/hello.gen".to_string(),
+                "components/hello.gen".to_string()
+            ])
+        );
+        // stylesheets import each other
+        let mut graph = DepGraph::default();
+        for (key, style) in [("a.gen", "b.gen"), ("b.gen", "a.gen")] {
+            graph.insert(
+                key.to_string(),
+                DepNode {
+                    styles: vec![style.to_string()],
+                    ..Default::default()
+                },
+            );
+        }
+        assert!(graph.cycles().is_empty());
+    }
+}
@@ -1,31 +1,43 @@
+mod cache;
 mod conf;
 mod context;
+mod graph;
+mod reach;
+mod task;
 mod tree;
 // mod wasm;
 
-use std::{collections::HashSet, path::PathBuf};
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
 
 // pub use wasm::*;
+pub use cache::*;
 pub use conf::*;
 pub use context::*;
-use gen_analyzer::Model;
 use gen_dyn_run::{compile_dyn_lib, dyn_lib_path, extern_c_fn, DynProcessor};
 use gen_plugin::{MacroContext, Repo};
 use gen_utils::{
     common::{
-        fs::{self, GenUIFs},
+        fs::{self, path_to_str, GenUIFs},
         git_download_plugin_from_github, read_to_doc, RustDependence, Source,
     },
     compiler::{CompilerImpl, ToRs, UnderlayerConfImpl},
     err_from_to,
-    error::{ConvertError, Error},
+    error::{CompilerError, ConvertError, Error},
 };
+pub use graph::*;
 use proc_macro2::TokenStream;
+pub use reach::*;
+pub use task::*;
 use toml_edit::{value, Item};
 use tree::ModelTree;
 use walkdir::WalkDir;
 
-use crate::model::{create_lib_rs, create_main_rs, AppMain, Widget};
+use gen_analyzer::Model;
+
+use crate::model::{create_lib_rs, create_main_rs, create_responsive_rs, create_theme_rs, AppMain};
 
 // ----------------------------------------------------------------------------------------------------------------------------------------
 // ----------------------------------------------------------------------------------------------------------------------------------------
@@ -41,6 +53,22 @@ pub struct Compiler {
     pub tree: ModelTree,
     /// 编译器上下文，用于存储需要编译过程中需要传递的信息
     pub context: Context,
+    /// 增量编译缓存, 未修改的文件不会重新编译
+    pub cache: Cache,
+    /// 组件依赖图, 文件变化时只重新编译受影响的文件
+    pub graph: DepGraph,
+    /// 从root及router页面可达的文件, 不可达的文件不会生成代码
+    pub reach: Reach,
+    /// 编译过程中的警告(不可达文件, 未使用的import, 被覆盖的样式), 在after_compile中统一输出
+    pub warnings: Vec<String>,
+}
+
+/// a file waiting to be compiled in [`Compiler::compile_all`]
+enum Pending {
+    /// unchanged file, the cache is checked after the components it uses are compiled
+    Cached(PathBuf),
+    /// changed file and its model (None if the model is empty or does not need to be compiled)
+    Parsed(CompileTask, Option<Box<Model>>),
 }
 
 impl Compiler {
@@ -75,12 +103,22 @@ impl Compiler {
         if let Some(router) = conf.router.as_ref() {
             context.load_router(router, source.from_path())?;
         }
+        if let Some(theme) = conf.theme.as_ref() {
+            context.load_theme(theme, source.from_path())?;
+        }
+
+        // [cache] -------------------------------------------------------------------------------
+        let cache = Cache::load(source.path.as_path());
 
         Ok(Self {
             source,
             conf,
             tree,
             context,
+            cache,
+            graph: DepGraph::default(),
+            reach: Reach::default(),
+            warnings: vec![],
         })
     }
 
@@ -95,7 +133,7 @@ impl Compiler {
             })
         });
 
-        let lib_content = self.context.lib_content.as_ref().map_or_else(
+        let mut lib_content = self.context.lib_content.as_ref().map_or_else(
             || plugin_lib_str.clone(),
             |content| {
                 let mut lib_str = plugin_lib_str.as_ref().cloned().unwrap_or_default();
@@ -103,6 +141,18 @@ impl Compiler {
                 Some(lib_str)
             },
         );
+        // [主题注册表] -------------------------------------------------------------------------
+        if self.context.theme.is_some() {
+            lib_content
+                .get_or_insert_with(String::new)
+                .push_str("pub mod gen_theme;");
+        }
+        // [响应式样式] -------------------------------------------------------------------------
+        if self.is_responsive() {
+            lib_content
+                .get_or_insert_with(String::new)
+                .push_str("pub mod gen_responsive;");
+        }
 
         fs::write(
             lib_rs_path.as_path(),
@@ -123,13 +173,342 @@ impl Compiler {
         )
     }
 
+    /// ## create gen_theme.rs, See [`create_theme_rs`]
+    /// modules are the files which have `apply_theme` in cache
+    fn create_theme_rs(&self) -> Result<(), Error> {
+        let Some(tokens) = self.context.theme.as_ref() else {
+            return Ok(());
+        };
+        let modules = self.cache_modules(|entry| entry.themed);
+        let theme_rs_path = self.source.to_path().join("src").join("gen_theme.rs");
+        fs::write(
+            theme_rs_path.as_path(),
+            &create_theme_rs(tokens, &modules).to_string(),
+        )
+    }
+
+    /// ## create gen_responsive.rs, See [`create_responsive_rs`]
+    /// modules are the files which have `apply_responsive` in cache
+    fn create_responsive_rs(&self) -> Result<(), Error> {
+        if !self.is_responsive() {
+            return Ok(());
+        }
+        let modules = self.cache_modules(|entry| entry.responsive);
+        let responsive_rs_path = self.source.to_path().join("src").join("gen_responsive.rs");
+        fs::write(
+            responsive_rs_path.as_path(),
+            &create_responsive_rs(&modules).to_string(),
+        )
+    }
+
+    /// any file has responsive style rules or not, See [`crate::model::ResponsiveApply`]
+    fn is_responsive(&self) -> bool {
+        self.cache.files.values().any(|entry| entry.responsive)
+    }
+
+    /// modules (such as `views::home`) of the files in cache which match the filter, sorted
+    fn cache_modules<F>(&self, filter: F) -> Vec<String>
+    where
+        F: Fn(&CacheEntry) -> bool,
+    {
+        let mut modules = self
+            .cache
+            .files
+            .iter()
+            .filter(|(_, entry)| filter(entry))
+            .map(|(key, _)| key.trim_end_matches(".gen").replace('/', "::"))
+            .collect::<Vec<_>>();
+        modules.sort();
+        modules
+    }
+
     fn create_app_main(&mut self) -> Result<(), Error> {
         let source = AppMain::source_from_entry(self.conf.entry.as_ref(), &self.source);
         let mut app_main = AppMain::new(&mut self.context, source, &self.conf.root)?;
         app_main.registers.replace(self.tree.registers());
+        app_main.app_main.responsive = self.is_responsive();
         fs::write(app_main.source.to_path(), &app_main.content()?.to_string())
     }
 
+    /// key of the file in cache, path relative to source project
+    fn cache_key(&self, path: &Path) -> String {
+        path_to_str(path.strip_prefix(self.source.from_path()).unwrap_or(path))
+    }
+
+    /// plugins can change the generated code, so they are part of the cache hash
+    fn plugin_names(&self) -> String {
+        let mut names = self
+            .context
+            .plugins
+            .as_ref()
+            .map(|plugins| {
+                plugins
+                    .iter()
+                    .map(|plugin| plugin.plugin.name.to_string())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        names.sort();
+        names.join(",")
+    }
+
+    /// ## hash of the file in cache
+    /// besides the content, everything which can change the generated code is part of the hash:
+    /// root or not, config, router config, plugins, design tokens and contents of the stylesheets imported by `@import`
+    fn hash(&self, key: &str, content: &str, is_root: bool, styles: &[String]) -> String {
+        let from_path = self.source.from_path();
+        // router and nav pages are generated by the router config, See [`Context::load_router`]
+        let router = self
+            .conf
+            .router
+            .as_ref()
+            .map(|router| fs::read(from_path.join(router)).unwrap_or_default())
+            .unwrap_or_default();
+        let styles = styles
+            .iter()
+            .flat_map(|style| {
+                let content = fs::read(from_path.join(style)).unwrap_or_default();
+                [style.to_string(), content]
+            })
+            .collect::<Vec<_>>();
+        let plugin_names = self.plugin_names();
+        let theme = self
+            .context
+            .theme
+            .as_ref()
+            .map(|theme| theme.to_string())
+            .unwrap_or_default();
+        let mut parts = vec![
+            key,
+            content,
+            if is_root { "root" } else { "" },
+            if self.conf.warn_override {
+                "warn_override"
+            } else {
+                ""
+            },
+            plugin_names.as_str(),
+            theme.as_str(),
+            router.as_str(),
+        ];
+        parts.extend(styles.iter().map(|part| part.as_str()));
+        Cache::hash(&parts)
+    }
+
+    /// keys of entries: root `.gen` and router pages, See [`Reach`]
+    fn entries(&self) -> Vec<String> {
+        let from_path = self.source.from_path();
+        let mut entries = vec![self.cache_key(from_path.join(&self.conf.root.path).as_path())];
+        if let Some(router) = self.context.router.as_ref() {
+            let pages = router
+                .bar_pages
+                .iter()
+                .map(|(_, page)| page)
+                .chain(router.nav_pages.values());
+            for page in pages {
+                let (Page::Path(import) | Page::Component { path: import, .. }) = page;
+                entries.extend(self.import_keys(std::slice::from_ref(&import.0)));
+            }
+        }
+        entries
+    }
+
+    /// ## scan the files reachable from root and router pages
+    /// dead files and unused imports are collected into [`Compiler::warnings`]
+    fn scan_reach(&mut self) {
+        self.reach = Reach::scan(self.source.from_path(), self.entries());
+        self.warnings.extend(self.reach.warnings());
+    }
+
+    /// key in cache of `import!` entry, See [`DepGraph::resolve`]
+    fn import_keys(&self, imports: &[Vec<String>]) -> Vec<String> {
+        let from_path = self.source.from_path();
+        imports
+            .iter()
+            .filter_map(|import| DepGraph::resolve(import, |key| from_path.join(key).is_file()))
+            .collect()
+    }
+
+    /// ## get the compile task of the file
+    /// return None if the file does not need to be compiled:
+    /// - `main.rs`: copy to lib.rs
+    /// - the file hits the cache, See [`Cache::get`]
+    fn task(&mut self, path: &Path) -> Result<Option<CompileTask>, Error> {
+        if path.is_file() && path.file_name().unwrap() == "main.rs" {
+            // main.rs文件不需要编译直接复制到lib.rs中
+            let lib_content = fs::read(path)?;
+            if !lib_content.is_empty() {
+                self.context.lib_content = Some(lib_content);
+            }
+            return Ok(None);
+        }
+
+        // 编译gen文件，生成rs文件，生成rs文件后，将rs文件插入到tree中
+        // 需要注意的是只有一个root.gen(self.conf.root)的跟节点文件需要处理一些特殊逻辑, 需要将一些信息存储到compiler到上下文中
+        let compiled_path = path.to_compiled_from_source(&self.source)?;
+        let widget_source = path.widget_source(&self.source)?;
+        let is_root = self.conf.root.is_root(path);
+        let key = self.cache_key(path);
+        let compile = self.reach.is_reachable(&key);
+        // [增量编译] 文件内容及使用到的自定义组件都没有变化时, 直接使用缓存 ---------------------------------
+        let content = fs::read(path)?;
+        // 样式文件(@import)在上次编译时记录, 如果本文件的@import变化, 文件内容也会变化
+        let styles = self
+            .cache
+            .files
+            .get(&key)
+            .map(|entry| entry.styles.clone())
+            .unwrap_or_default();
+        let hash = self.hash(&key, &content, is_root, &styles);
+        if let Some((entry, output)) = self
+            .cache
+            .get(&key, &hash, &self.context.define_widget_poll)
+            .filter(|_| compile)
+        {
+            self.context
+                .define_widget_poll
+                .extend(entry.widgets.clone());
+            self.graph.insert(key, entry.into());
+            if is_root {
+                self.context.app_main.root_ref.source.replace(widget_source);
+            }
+            let _ = self.tree.insert(compiled_path.as_path());
+            if fs::read(compiled_path.as_path()).ok().as_ref() != Some(&output) {
+                fs::write(compiled_path, &output)?;
+            }
+            return Ok(None);
+        }
+
+        Ok(Some(CompileTask {
+            key,
+            hash,
+            compiled_path,
+            widget_source,
+            is_root,
+            compile,
+            warn_override: self.conf.warn_override,
+            styles,
+        }))
+    }
+
+    /// ## custom components defined and used by the unchanged file, See [`CompileTask::deps`]
+    /// return None if the file is not a `.gen` file, not reachable or changed (not hit in cache),
+    /// the cache is checked in [`Compiler::task`] after the components it uses are compiled
+    fn cached(&mut self, path: &Path) -> Result<Option<Deps>, Error> {
+        if !path.is_gen_file() {
+            return Ok(None);
+        }
+        let key = self.cache_key(path);
+        if !self.reach.is_reachable(&key) {
+            return Ok(None);
+        }
+        let Some(entry) = self.cache.files.get(&key) else {
+            return Ok(None);
+        };
+        let content = fs::read(path)?;
+        let hash = self.hash(&key, &content, self.conf.root.is_root(path), &entry.styles);
+        if entry.hash != hash {
+            return Ok(None);
+        }
+        let mut defines = entry.widgets.keys().cloned().collect::<Vec<_>>();
+        defines.sort();
+        let mut uses = entry.deps.keys().cloned().collect::<Vec<_>>();
+        uses.sort();
+        Ok(Some((defines, uses)))
+    }
+
+    /// ## convert the file with the context of compiler
+    /// - `model`: parsed model, the file is parsed if None, See [`CompileTask::convert`]
+    fn convert(&mut self, task: &CompileTask, model: Option<Model>) -> Result<Option<Compiled>, Error> {
+        // 取出define_widget_poll, 编译后context中的define_widget_poll即为当前文件添加的组件
+        let poll = std::mem::take(&mut self.context.define_widget_poll);
+        let compiled = match model {
+            Some(model) => task
+                .convert_model(model, &mut self.context, &poll)
+                .map(Some),
+            None => task.convert(&mut self.context, &poll),
+        };
+        self.context.define_widget_poll = poll;
+        compiled
+    }
+
+    /// ## merge the compiled result into context, cache and tree, then write the rust file
+    fn finish(&mut self, task: CompileTask, compiled: Option<Compiled>) -> Result<(), Error> {
+        let Some(Compiled {
+            output,
+            deps,
+            imports,
+            widgets,
+            root,
+            warnings,
+            styles,
+            themed,
+            responsive,
+        }) = compiled
+        else {
+            // 不可达的文件, 移除之前生成的代码
+            if !task.compile {
+                self.cache.remove(&task.key);
+                for (_, node) in self.graph.remove(&task.key) {
+                    for define in node.defines {
+                        self.context.define_widget_poll.remove(&define);
+                    }
+                }
+                if task.compiled_path.is_file() {
+                    self.tree.remove(task.compiled_path.as_path());
+                    fs::delete(task.compiled_path.as_path())?;
+                }
+            }
+            return Ok(());
+        };
+
+        self.warnings.extend(warnings);
+        // [依赖图] 组件循环引用时不生成代码 -------------------------------------------------------
+        let node = DepNode::new(self.import_keys(&imports), styles, deps, &widgets);
+        let reachable = self
+            .reach
+            .insert(task.key.to_string(), node.clone(), imports);
+        self.graph.insert(task.key.to_string(), node.clone());
+        if let Some(cycle) = self.graph.cycle_of(&task.key) {
+            return Err(CompilerError::runtime(
+                "Makepad Compiler - DepGraph",
+                &format!("component cycle is found: {}", cycle.join(" -> ")),
+            )
+            .into());
+        }
+
+        // 编译后@import可能变化, 使用新的样式文件重新计算hash, 否则下次编译无法命中缓存
+        let hash = if node.styles == task.styles {
+            task.hash
+        } else {
+            let content = fs::read(task.widget_source.from_path())?;
+            self.hash(&task.key, &content, task.is_root, &node.styles)
+        };
+        self.cache.insert(
+            task.key,
+            hash,
+            &node,
+            &self.context.define_widget_poll,
+            widgets.clone(),
+            &output,
+            themed,
+            responsive,
+        )?;
+        self.context.define_widget_poll.extend(widgets);
+        if let Some(root) = root {
+            self.context.app_main.root_ref.source.replace(root);
+        }
+        // 将widget插入到tree中
+        let _ = self.tree.insert(task.compiled_path.as_path());
+        fs::write(task.compiled_path, &output)?;
+        // 文件新引用了之前不可达的文件, 编译这些文件
+        let from_path = self.source.from_path();
+        for key in reachable {
+            self.compile(from_path.join(key))?;
+        }
+        Ok(())
+    }
+
     fn download_plugins(&self) -> Result<(), Error> {
         if let Some(plugins) = self.context.plugins.as_ref() {
             let compiled_src = self.source.to_path().join("src");
@@ -267,47 +646,165 @@ impl CompilerImpl for Compiler {
         let _ = self.create_app_main()?;
         // [download plugins] -----------------------------------------------------------------
         let _ = self.download_plugins()?;
+        // [reachability] ---------------------------------------------------------------------
+        self.scan_reach();
         Ok(())
     }
 
     fn after_compile(&mut self) -> Result<(), Error> {
-        println!("after compile");
+        for warning in self.warnings.drain(..) {
+            eprintln!("warning: {}", warning);
+        }
         Ok(())
     }
 
     fn compile(&mut self, path: PathBuf) -> Result<(), Error> {
-        if path.is_file() && path.file_name().unwrap() == "main.rs" {
-            // main.rs文件不需要编译直接复制到lib.rs中
-            let lib_content = fs::read(path.as_path())?;
-            if !lib_content.is_empty() {
-                self.context.lib_content = Some(lib_content);
-            }
+        let Some(task) = self.task(path.as_path())? else {
             return Ok(());
+        };
+        let compiled = self.convert(&task, None)?;
+        self.finish(task, compiled)
+    }
+
+    /// ## `.gen` files and main.rs of the project (sorted)
+    /// hidden dirs (`.gen_cache`, ...) and `target` are skipped
+    fn sources(&self) -> Vec<PathBuf> {
+        let from_path = self.source.from_path();
+        let mut sources = WalkDir::new(from_path.as_path())
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.depth() == 0 || {
+                    let name = entry.file_name().to_string_lossy();
+                    !name.starts_with('.') && name != "target"
+                }
+            })
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                entry.file_type().is_file()
+                    && (entry.path().extension().is_some_and(|ext| ext == "gen")
+                        || entry.file_name() == "main.rs")
+            })
+            .map(|entry| entry.into_path())
+            .collect::<Vec<_>>();
+        sources.sort();
+        sources
+    }
+
+    /// ## compile all files in parallel
+    /// 1. main.rs and changed files are handled in order, See [`Compiler::task`]
+    /// 2. changed files are parsed in parallel
+    /// 3. files are split into waves by dependency, See [`CompileTask::waves`], for each wave:
+    ///    - unchanged files check the cache after the components they use are compiled
+    ///    - files which need to be compiled are converted in parallel (each thread has a forked context)
+    ///    - results are merged into context in order
+    ///
+    /// so the output is the same as compiling one by one in dependency order.
+    /// plugins (dynamic lib) can not be shared between threads, so files are converted one by one if there are plugins
+    fn compile_all(&mut self, paths: Vec<PathBuf>) -> Result<(), Error> {
+        // [files] -------------------------------------------------------------------------------------
+        let mut files = vec![];
+        let mut tasks = vec![];
+        for path in paths {
+            if let Some(deps) = self.cached(path.as_path())? {
+                files.push((Pending::Cached(path), deps));
+            } else if let Some(task) = self.task(path.as_path())? {
+                tasks.push(task);
+            }
+        }
+        // [parse] -------------------------------------------------------------------------------------
+        let models = CompileTask::parse_all(&tasks);
+        for (task, model) in tasks.into_iter().zip(models) {
+            let model = model?;
+            let deps = model.as_ref().map(CompileTask::deps).unwrap_or_default();
+            files.push((Pending::Parsed(task, model.map(Box::new)), deps));
         }
+        // [waves] -------------------------------------------------------------------------------------
+        let waves = CompileTask::waves(
+            &files
+                .iter()
+                .map(|(_, deps)| deps.clone())
+                .collect::<Vec<_>>(),
+        );
+        let mut files = files
+            .into_iter()
+            .map(|(file, _)| Some(file))
+            .collect::<Vec<_>>();
+        for wave in waves {
+            let mut jobs = vec![];
+            for index in wave {
+                match files[index].take() {
+                    Some(Pending::Cached(path)) => {
+                        // 使用的组件已编译, 重新检查缓存
+                        if let Some(task) = self.task(path.as_path())? {
+                            jobs.push((task, None));
+                        }
+                    }
+                    Some(Pending::Parsed(task, Some(model))) => jobs.push((task, Some(*model))),
+                    // 文件在之前的合并中被引用, 变为可达
+                    Some(Pending::Parsed(task, None))
+                        if !task.compile && self.reach.is_reachable(&task.key) =>
+                    {
+                        let path = task.widget_source.from_path();
+                        if let Some(task) = self.task(path.as_path())? {
+                            jobs.push((task, None));
+                        }
+                    }
+                    Some(Pending::Parsed(task, None)) => self.finish(task, None)?,
+                    None => {}
+                }
+            }
 
-        // 编译gen文件，生成rs文件，生成rs文件后，将rs文件插入到tree中
-        // 需要注意的是只有一个root.gen(self.conf.root)的跟节点文件需要处理一些特殊逻辑, 需要将一些信息存储到compiler到上下文中
-        let compiled_path = path.as_path().to_compiled_from_source(&self.source)?;
-        let widget_source = path.as_path().widget_source(&self.source)?;
-        let model = Model::new(widget_source, self.conf.root.is_root(path.as_path()))?;
-
-        if !model.is_empty() {
-            // 编译widget
-            let widget = Widget::new(&mut self.context, model)?;
-            // 将widget插入到tree中
-            let _ = self.tree.insert(compiled_path.as_path());
-            fs::write(compiled_path, &ToRs::content(&widget)?.to_string())
-        } else {
-            Ok(())
+            if self.context.plugins.is_some() || jobs.len() < 2 {
+                for (task, model) in jobs {
+                    let compiled = self.convert(&task, model)?;
+                    self.finish(task, compiled)?;
+                }
+                continue;
+            }
+            let (tasks, models): (Vec<_>, Vec<_>) = jobs.into_iter().unzip();
+            let results = CompileTask::convert_all(
+                tasks.iter().zip(models).collect(),
+                self.context.router.as_ref(),
+                self.context.theme.as_ref(),
+                &self.context.define_widget_poll,
+            );
+            for (task, compiled) in tasks.into_iter().zip(results) {
+                self.finish(task, compiled?)?;
+            }
         }
+        Ok(())
     }
 
     fn update(&mut self) -> Result<(), Error> {
         self.create_lib_rs()?;
-        self.create_app_main()
+        self.create_app_main()?;
+        self.create_theme_rs()?;
+        self.create_responsive_rs()?;
+        self.cache.save()
+    }
+
+    /// ## the file (or files in the dir) and all files depend on it, See [`DepGraph::dependants`]
+    fn affected(&self, path: &Path) -> Vec<PathBuf> {
+        let key = self.cache_key(path);
+        let from_path = self.source.from_path();
+        let mut keys = self.graph.keys_of(&key);
+        if keys.is_empty() {
+            keys.push(key.to_string());
+        }
+        keys.extend(self.graph.dependants(&key));
+        keys.into_iter().map(|key| from_path.join(key)).collect()
     }
 
     fn remove(&mut self, path: PathBuf) -> Result<Option<Vec<PathBuf>>, Error> {
+        let key = self.cache_key(path.as_path());
+        self.cache.remove(&key);
+        self.reach.remove(&key);
+        // 移除文件定义的组件, 依赖它的文件重新编译时会被检查
+        for (_, node) in self.graph.remove(&key) {
+            for define in node.defines {
+                self.context.define_widget_poll.remove(&define);
+            }
+        }
         let compiled_path = path.as_path().to_compiled_from_delete(&self.source)?;
         if compiled_path.is_file() {
             self.tree.remove(compiled_path.as_path());
@@ -338,6 +835,181 @@ impl CompilerImpl for Compiler {
 
 #[cfg(test)]
 mod tes {
+    use std::path::{Path, PathBuf};
+
+    use gen_utils::{
+        common::Source,
+        compiler::{CompilerImpl, UnderlayerConfImpl},
+    };
+
+    use super::{Compiler, Config};
+
+    const CARD: &str = r#"
+<template>
+    <component name="MyCard">
+        <slot name="header" :title="title"></slot>
+        <slot></slot>
+    </component>
+</template>
+
+<script>
+#[component]
+pub struct MyCard {
+    title: String,
+}
+</script>
+"#;
+
+    const HOME: &str = r#"
+<template>
+    <component name="Home">
+        <my_card id="card">
+            <label id="lb" text="'body'"></label>
+        </my_card>
+    </component>
+</template>
+"#;
+
+    const ABOUT: &str = r#"
+<template>
+    <component name="About">
+        <label id="title" text="'about'"></label>
+    </component>
+</template>
+"#;
+
+    /// project with `components/card.gen`, `views/home.gen` (uses `<my_card>`) and `views/about.gen`,
+    /// all widgets have ids, otherwise the generated ids are different in each compile
+    fn project(root: &Path) -> Compiler {
+        let _ = std::fs::remove_dir_all(root);
+        for (file, content) in [
+            (
+                "from/Cargo.toml",
+                "[package]\nname = \"from\"\n\n[dependencies]\n",
+            ),
+            ("from/components/card.gen", CARD),
+            ("from/views/home.gen", HOME),
+            ("from/views/about.gen", ABOUT),
+        ] {
+            let path = root.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+        std::fs::create_dir_all(root.join("to/src")).unwrap();
+        let conf: Box<dyn UnderlayerConfImpl> = Box::new(Config::new(PathBuf::from("root.gen")));
+        Compiler::new(Source::new(root, Path::new("from"), Path::new("to")), &conf).unwrap()
+    }
+
+    fn outputs(root: &Path) -> Vec<String> {
+        ["components/card.rs", "views/home.rs", "views/about.rs"]
+            .iter()
+            .map(|file| std::fs::read_to_string(root.join("to/src").join(file)).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn compile_all() {
+        let root = std::env::temp_dir().join("gen_makepad_compile_all");
+        let from = root.join("from");
+        // [serial] callee first -------------------------------------------------------------------
+        let mut compiler = project(&root);
+        for file in ["components/card.gen", "views/about.gen", "views/home.gen"] {
+            compiler.compile(from.join(file)).unwrap();
+        }
+        let serial = outputs(&root);
+        // [parallel] callee last, the files are sorted by dependency ------------------------------
+        let mut compiler = project(&root);
+        compiler
+            .compile_all(
+                ["views/home.gen", "views/about.gen", "components/card.gen"]
+                    .iter()
+                    .map(|file| from.join(file))
+                    .collect(),
+            )
+            .unwrap();
+        let parallel = outputs(&root);
+        // [execute] -----------------------------------------------------------------------------
+        let mut compiler = project(&root);
+        assert_eq!(
+            compiler.sources(),
+            ["components/card.gen", "views/about.gen", "views/home.gen"]
+                .iter()
+                .map(|file| from.join(file))
+                .collect::<Vec<_>>()
+        );
+        compiler.compile_all(compiler.sources()).unwrap();
+        let execute = outputs(&root);
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert_eq!(serial, parallel);
+        assert_eq!(serial, execute);
+        // `MyCard` is compiled before `Home`, so the children are put into the default slot
+        assert!(serial[1]
+            .replace(' ', "")
+            .contains("card=<MyCard>{default={lb=<GLabel>{"));
+    }
+
+    #[test]
+    fn hash_router() {
+        let root = std::env::temp_dir().join("gen_makepad_hash_router");
+        let mut compiler = project(&root);
+        let hash = |compiler: &Compiler| compiler.hash("views/home.gen", HOME, false, &[]);
+        let without_router = hash(&compiler);
+        compiler.conf.router = Some(PathBuf::from("router.toml"));
+        std::fs::write(root.join("from/router.toml"), "name = \"a\"").unwrap();
+        let router_a = hash(&compiler);
+        std::fs::write(root.join("from/router.toml"), "name = \"b\"").unwrap();
+        let router_b = hash(&compiler);
+        let _ = std::fs::remove_dir_all(&root);
+
+        // editing the router config invalidates the cache
+        assert_ne!(without_router, router_a);
+        assert_ne!(router_a, router_b);
+    }
+
+    #[test]
+    fn reach_warnings() {
+        let root = std::env::temp_dir().join("gen_makepad_reach_warnings");
+        let mut compiler = project(&root);
+        std::fs::write(
+            root.join("from/root.gen"),
+            "<template>\n    <root id=\"ui\">\n        <home id=\"home\"></home>\n    </root>\n</template>\n\n<script>\nimport! {\n    crate::views::home::*;\n    crate::views::about::*;\n}\n</script>\n",
+        )
+        .unwrap();
+        compiler.scan_reach();
+        let warnings = compiler.warnings.clone();
+        compiler.after_compile().unwrap();
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("unused import `crate::views::about::*` in `root.gen`"));
+        // reported once in after_compile
+        assert!(compiler.warnings.is_empty());
+    }
+
+    #[test]
+    fn override_warnings() {
+        let root = std::env::temp_dir().join("gen_makepad_override_warnings");
+        let mut compiler = project(&root);
+        compiler.conf.warn_override = true;
+        let about = root.join("from/views/about.gen");
+        std::fs::write(
+            about.as_path(),
+            ABOUT.replace(
+                "</template>",
+                "</template>\n\n<style>\n#title {\n    font_size: 12;\n}\n.title {\n    font_size: 10;\n}\n</style>",
+            )
+            .replace("id=\"title\"", "id=\"title\" class=\"title\""),
+        )
+        .unwrap();
+        compiler.compile(about).unwrap();
+        let warnings = compiler.warnings.clone();
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("`font_size` in `.title` is overridden by `#title`"));
+    }
+
     #[test]
     fn test_pathbuf_eq() {
         let a =
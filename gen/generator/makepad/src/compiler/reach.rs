@@ -0,0 +1,389 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+};
+
+use gen_analyzer::{Model, Template};
+use gen_utils::common::{camel_to_snake, fs};
+use rssyin::analyzer::ScriptAnalyzer;
+use walkdir::WalkDir;
+
+use super::{Cache, DepGraph, DepNode};
+
+/// # Reachability
+/// `.gen` files which are reachable from the entries (root `.gen` and router pages) through `import!`, `@import` and tags,
+/// See [`DepGraph::dependencies`]
+/// - reachable files are compiled (`Model.compile` is true)
+/// - dead files are never referenced, they are not generated and reported as warnings
+/// - `import!` entries which are not used as tags in template are reported as warnings
+///
+/// if the project is not analyzed (such as the entries are unknown), all files are reachable
+#[derive(Debug, Clone, Default)]
+pub struct Reach {
+    /// source project path
+    pub path: PathBuf,
+    /// keys of entries
+    pub entries: Vec<String>,
+    /// all analyzed `.gen` files in the source project
+    pub graph: DepGraph,
+    /// `import!` entries of each file (path segments)
+    pub imports: HashMap<String, Vec<Vec<String>>>,
+    /// files can not be analyzed, they are always compiled so that the errors are reported
+    pub unknown: HashSet<String>,
+    reachable: Option<HashSet<String>>,
+}
+
+impl Reach {
+    /// ## analyze all `.gen` files in the source project
+    /// - `path`: source project path
+    /// - `entries`: keys of entries
+    pub fn scan<P>(path: P, entries: Vec<String>) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref().to_path_buf();
+        let mut reach = Self {
+            path: path.to_path_buf(),
+            entries,
+            ..Default::default()
+        };
+        let files = WalkDir::new(path.as_path())
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                entry.file_type().is_file()
+                    && entry.path().extension().is_some_and(|ext| ext == "gen")
+            })
+            .filter_map(|entry| {
+                entry
+                    .path()
+                    .strip_prefix(path.as_path())
+                    .ok()
+                    .map(fs::path_to_str)
+            })
+            .collect::<Vec<_>>();
+
+        for key in files {
+            reach.analyze(key);
+        }
+        reach.update();
+        reach
+    }
+    /// ## analyze the file and update reachability
+    /// return files which become reachable (sorted), they should be compiled
+    pub fn insert(&mut self, key: String, node: DepNode, imports: Vec<Vec<String>>) -> Vec<String> {
+        if self.reachable.is_none() {
+            return vec![];
+        }
+        self.unknown.remove(&key);
+        self.graph.insert(key.to_string(), node);
+        self.imports.insert(key, imports);
+        let before = self.reachable.take().unwrap_or_default();
+        self.update();
+        let mut reachable = self
+            .reachable
+            .iter()
+            .flatten()
+            .filter(|key| !before.contains(*key))
+            .cloned()
+            .collect::<Vec<_>>();
+        reachable.sort();
+        reachable
+    }
+    /// ## remove the file or all files in the dir
+    pub fn remove(&mut self, key: &str) {
+        for (key, _) in self.graph.remove(key) {
+            self.imports.remove(&key);
+        }
+        let dir = format!("{}/", key.trim_end_matches('/'));
+        self.unknown
+            .retain(|file| file != key && !file.starts_with(&dir));
+        if self.reachable.is_some() {
+            self.update();
+        }
+    }
+    /// ## the file needs to be compiled or not
+    /// new files (not analyzed yet) are analyzed first
+    pub fn is_reachable(&mut self, key: &str) -> bool {
+        if self.reachable.is_none() {
+            return true;
+        }
+        if !self.graph.nodes.contains_key(key) && !self.unknown.contains(key) {
+            self.analyze(key.to_string());
+            self.update();
+        }
+        self.unknown.contains(key) || self.reachable.as_ref().is_some_and(|r| r.contains(key))
+    }
+    /// ## files which are never referenced (sorted)
+    pub fn dead(&self) -> Vec<String> {
+        let Some(reachable) = self.reachable.as_ref() else {
+            return vec![];
+        };
+        let mut dead = self
+            .graph
+            .nodes
+            .keys()
+            .filter(|key| !reachable.contains(*key))
+            .cloned()
+            .collect::<Vec<_>>();
+        dead.sort();
+        dead
+    }
+    /// ## `import!` entries which are not used in reachable files (sorted)
+    /// return (key of file, import), an import is used if:
+    /// - `crate::a::b::*`: any component defined in `a/b.gen` is used as tag
+    /// - `crate::a::b::Hello`: `<Hello>` is used as tag
+    ///
+    /// imports which are not from `.gen` files (such as rust modules) are ignored
+    pub fn unused_imports(&self) -> Vec<(String, String)> {
+        let Some(reachable) = self.reachable.as_ref() else {
+            return vec![];
+        };
+        let mut keys = reachable.iter().collect::<Vec<_>>();
+        keys.sort();
+        let mut unused = vec![];
+        for key in keys {
+            let (Some(node), Some(imports)) = (self.graph.nodes.get(key), self.imports.get(key))
+            else {
+                continue;
+            };
+            for import in imports {
+                let Some(file) =
+                    DepGraph::resolve(import, |file| self.graph.nodes.contains_key(file))
+                else {
+                    continue;
+                };
+                let used = match import.last().map(|item| item.as_str()) {
+                    Some("*") => self.graph.nodes[&file]
+                        .defines
+                        .iter()
+                        .any(|define| node.uses.contains(define)),
+                    Some(item) => node.uses.contains(&camel_to_snake(item)),
+                    None => true,
+                };
+                if !used {
+                    unused.push((key.to_string(), import.join("::")));
+                }
+            }
+        }
+        unused
+    }
+    /// ## warnings of dead files and unused imports
+    pub fn warnings(&self) -> Vec<String> {
+        self.dead()
+            .into_iter()
+            .map(|key| {
+                format!(
+                    "`{}` is never used (not reachable from root or router pages), it is not generated",
+                    key
+                )
+            })
+            .chain(
+                self.unused_imports()
+                    .into_iter()
+                    .map(|(key, import)| format!("unused import `{}` in `{}`", import, key)),
+            )
+            .collect()
+    }
+    /// ## dependency node of the model
+    /// - `exists`: check the key of `import!` is a `.gen` file or not, See [`DepGraph::resolve`]
+    pub fn node<F>(model: &Model, exists: F) -> (DepNode, Vec<Vec<String>>)
+    where
+        F: Fn(&str) -> bool,
+    {
+        let imports: Vec<Vec<String>> = model
+            .script
+            .as_ref()
+            .and_then(|sc| ScriptAnalyzer::analyze(sc).ok())
+            .and_then(|bridger| bridger.imports)
+            .map(|imports| imports.0.into_iter().map(|import| import.0).collect())
+            .unwrap_or_default();
+        let node = DepNode {
+            imports: imports
+                .iter()
+                .filter_map(|import| DepGraph::resolve(import, &exists))
+                .collect(),
+            styles: model
+                .style_imports
+                .iter()
+                .filter(|import| exists(import))
+                .cloned()
+                .collect(),
+            uses: Cache::deps(model.template.as_ref()),
+            defines: model
+                .template
+                .as_ref()
+                .and_then(component_name)
+                .map(|name| vec![camel_to_snake(&name)])
+                .unwrap_or_default(),
+        };
+        (node, imports)
+    }
+    fn analyze(&mut self, key: String) {
+        let model = fs::read(self.path.join(&key))
+            .ok()
+            .and_then(|content| content.parse::<Model>().ok());
+        match model {
+            Some(model) => {
+                let (node, imports) = Self::node(&model, |file| self.path.join(file).is_file());
+                self.graph.insert(key.to_string(), node);
+                self.imports.insert(key, imports);
+            }
+            None => {
+                self.unknown.insert(key);
+            }
+        }
+    }
+    /// walk from entries through dependencies
+    fn update(&mut self) {
+        let mut reachable = HashSet::new();
+        let mut stack = self.entries.clone();
+        while let Some(key) = stack.pop() {
+            if reachable.insert(key.to_string()) {
+                stack.extend(self.graph.dependencies(&key));
+            }
+        }
+        self.reachable.replace(reachable);
+    }
+}
+
+/// name of the component, `<component name="Hello">` => `Hello`
+fn component_name(template: &Template) -> Option<String> {
+    if !template.is_component() {
+        return None;
+    }
+    template
+        .props
+        .as_ref()?
+        .iter()
+        .find(|(key, _)| key.name == "name" && key.is_normal())
+        .map(|(_, value)| value.to_string())
+}
+
+#[cfg(test)]
+mod test_reach {
+    use super::{DepNode, Reach};
+
+    fn node(imports: &[&str], uses: &[&str], defines: &[&str]) -> DepNode {
+        let strings = |items: &[&str]| items.iter().map(|s| s.to_string()).collect();
+        DepNode {
+            imports: strings(imports),
+            uses: strings(uses),
+            defines: strings(defines),
+            ..Default::default()
+        }
+    }
+
+    fn import(s: &str) -> Vec<String> {
+        s.split("::").map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn dead_and_unused() {
+        let mut reach = Reach {
+            entries: vec!["root.gen".to_string()],
+            ..Default::default()
+        };
+        reach.update();
+        reach.insert(
+            "root.gen".to_string(),
+            node(&["views/home.gen"], &["home"], &[]),
+            vec![import("crate::views::home::*")],
+        );
+        reach.insert(
+            "views/home.gen".to_string(),
+            node(
+                &["components/hello.gen", "components/unused.gen"],
+                &["hello"],
+                &["home"],
+            ),
+            vec![
+                import("crate::components::hello::Hello"),
+                import("crate::components::unused::*"),
+                import("crate::utils::*"),
+            ],
+        );
+        reach.insert(
+            "components/hello.gen".to_string(),
+            node(&[], &[], &["hello"]),
+            vec![],
+        );
+        reach.insert(
+            "components/unused.gen".to_string(),
+            node(&[], &[], &["unused"]),
+            vec![],
+        );
+        let newly = reach.insert(
+            "components/dead.gen".to_string(),
+            node(&[], &[], &["dead"]),
+            vec![],
+        );
+        assert!(newly.is_empty());
+
+        assert_eq!(reach.dead(), vec!["components/dead.gen"]);
+        assert!(!reach.is_reachable("components/dead.gen"));
+        assert!(reach.is_reachable("components/unused.gen"));
+        assert_eq!(
+            reach.unused_imports(),
+            vec![(
+                "views/home.gen".to_string(),
+                "crate::components::unused::*".to_string()
+            )]
+        );
+
+        // use the dead component in home
+        let newly = reach.insert(
+            "views/home.gen".to_string(),
+            node(&["components/dead.gen"], &["dead"], &["home"]),
+            vec![import("crate::components::dead::*")],
+        );
+        assert_eq!(newly, vec!["components/dead.gen"]);
+        assert_eq!(
+            reach.dead(),
+            vec!["components/hello.gen", "components/unused.gen"]
+        );
+    }
+
+    #[test]
+    fn node_of_model() {
+        let input = r#"
+<template>
+    <component name="HomePage">
+        <view>
+            <Hello></Hello>
+            <view></view>
+        </view>
+    </component>
+</template>
+
+<script>
+import! {
+    crate::components::hello::*;
+}
+
+#[component]
+pub struct HomePage {}
+</script>
+"#;
+        let model = input.parse::<gen_analyzer::Model>().unwrap();
+        let (node, imports) = Reach::node(&model, |key| key == "components/hello.gen");
+        assert_eq!(imports, vec![import("crate::components::hello::*")]);
+        assert_eq!(
+            node,
+            super::DepNode {
+                imports: vec!["components/hello.gen".to_string()],
+                uses: vec!["hello".to_string()],
+                defines: vec!["home_page".to_string()],
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn not_analyzed() {
+        let mut reach = Reach::default();
+        assert!(reach.is_reachable("any.gen"));
+        assert!(reach.dead().is_empty());
+        assert!(reach.warnings().is_empty());
+    }
+}
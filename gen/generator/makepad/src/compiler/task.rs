@@ -0,0 +1,274 @@
+use std::path::PathBuf;
+
+use gen_analyzer::Model;
+use gen_utils::{
+    common::{camel_to_snake, Source},
+    compiler::ToRs,
+    error::Error,
+};
+use quote::ToTokens;
+
+use crate::{
+    checker::Checker,
+    model::{ResponsiveApply, ThemeApply, Widget},
+};
+
+use super::{Cache, Context, DesignTokens, RouterBuilder, WidgetPoll};
+
+/// # Compile Task
+/// a `.gen` file which needs to be compiled (not hit in cache)
+#[derive(Debug, Clone)]
+pub struct CompileTask {
+    /// key in cache, See [`Cache`]
+    pub key: String,
+    pub hash: String,
+    pub compiled_path: PathBuf,
+    pub widget_source: Source,
+    pub is_root: bool,
+    /// the file is reachable from entries or not, See [`super::Reach`]
+    pub compile: bool,
+    /// report overridden style props or not, See [`super::Config::warn_override`]
+    pub warn_override: bool,
+    /// stylesheets imported by `@import` when the hash is calculated, See [`super::CacheEntry::styles`]
+    pub styles: Vec<String>,
+}
+
+/// custom components (snake name) defined and used by a file: (defines, uses), See [`CompileTask::deps`]
+pub type Deps = (Vec<String>, Vec<String>);
+
+/// # Compiled Result
+#[derive(Debug, Clone)]
+pub struct Compiled {
+    /// generated rust code
+    pub output: String,
+    /// custom components used by the file, See [`Cache::deps`]
+    pub deps: Vec<String>,
+    /// entries of `import!` in script, See [`Checker::imports`]
+    pub imports: Vec<Vec<String>>,
+    /// define widgets pushed into `define_widget_poll` by the file
+    pub widgets: WidgetPoll,
+    /// source of the root widget (only when the file is root)
+    pub root: Option<Source>,
+    /// warnings of the file, See [`Checker::overrides`]
+    pub warnings: Vec<String>,
+    /// stylesheets imported by `@import` (key in cache), See [`gen_analyzer::Model::style_imports`]
+    pub styles: Vec<String>,
+    /// the file has `apply_theme` or not, See [`ThemeApply`]
+    pub themed: bool,
+    /// the file has `apply_responsive` or not, See [`ResponsiveApply`]
+    pub responsive: bool,
+}
+
+impl CompileTask {
+    /// ## parse the `.gen` file
+    /// return None if the model is empty or does not need to be compiled
+    pub fn parse(&self) -> Result<Option<Model>, Error> {
+        let mut model = Model::new(self.widget_source.clone(), self.is_root)?;
+        // 未被引用的文件不需要生成代码
+        model.compile = self.compile;
+        if model.is_empty() || !model.compile {
+            return Ok(None);
+        }
+        Ok(Some(model))
+    }
+    /// ## parse and convert the `.gen` file to rust code
+    /// - `ctx`: `define_widget_poll` of ctx should be empty, so that the pushed widgets can be taken after convert
+    /// - `poll`: compiled define widgets, used to check the model and put children into slots, See [`Checker`] and [`Widget::resolve_slots`]
+    ///
+    /// convert never reads `define_widget_poll`, the result only depends on the model and `poll`,
+    /// return None if the model is empty or does not need to be compiled
+    pub fn convert(&self, ctx: &mut Context, poll: &WidgetPoll) -> Result<Option<Compiled>, Error> {
+        match self.parse()? {
+            Some(model) => self.convert_model(model, ctx, poll).map(Some),
+            None => Ok(None),
+        }
+    }
+    /// ## convert the parsed model to rust code, See [`CompileTask::convert`]
+    pub fn convert_model(
+        &self,
+        mut model: Model,
+        ctx: &mut Context,
+        poll: &WidgetPoll,
+    ) -> Result<Compiled, Error> {
+        // 替换设计令牌(token), 在检查前进行, 检查的是替换后的值
+        let theme = ThemeApply::new(ctx.theme.as_ref(), &mut model)?;
+        // 检查绑定等是否正确, 在生成代码前报告错误位置
+        let checker = Checker::new(&model, poll);
+        let imports = checker.imports();
+        let warnings = if self.warn_override {
+            checker.overrides()
+        } else {
+            vec![]
+        };
+        checker.check()?;
+        let deps = Cache::deps(model.template.as_ref());
+        let styles = model.style_imports.clone();
+        let responsive = ResponsiveApply::new(&model)?;
+        // 编译widget
+        let mut widget = Widget::new(ctx, model)?;
+        widget.resolve_slots(poll);
+        let mut output = ToRs::content(&widget)?;
+        if let Some(theme) = theme.as_ref() {
+            output.extend(theme.to_token_stream());
+        }
+        if let Some(responsive) = responsive.as_ref() {
+            output.extend(responsive.to_token_stream());
+        }
+        let output = output.to_string();
+
+        Ok(Compiled {
+            output,
+            deps,
+            imports,
+            widgets: std::mem::take(&mut ctx.define_widget_poll),
+            root: self
+                .is_root
+                .then(|| ctx.app_main.root_ref.source.clone())
+                .flatten(),
+            warnings,
+            styles,
+            themed: theme.is_some(),
+            responsive: responsive.is_some(),
+        })
+    }
+    /// ## parse tasks in parallel
+    /// results are returned in the order of tasks, See [`CompileTask::parse`]
+    pub fn parse_all(tasks: &[CompileTask]) -> Vec<Result<Option<Model>, Error>> {
+        parallel(tasks.iter().collect(), |task| task.parse())
+    }
+    /// ## convert tasks in parallel
+    /// each task is converted with a forked context (router and theme), the task is parsed if the model is None,
+    /// results are returned in the order of tasks
+    pub fn convert_all(
+        tasks: Vec<(&CompileTask, Option<Model>)>,
+        router: Option<&RouterBuilder>,
+        theme: Option<&DesignTokens>,
+        poll: &WidgetPoll,
+    ) -> Vec<Result<Option<Compiled>, Error>> {
+        parallel(tasks, |(task, model)| {
+            let mut ctx = Context {
+                router: router.cloned(),
+                theme: theme.cloned(),
+                ..Default::default()
+            };
+            match model {
+                Some(model) => task.convert_model(model, &mut ctx, poll).map(Some),
+                None => task.convert(&mut ctx, poll),
+            }
+        })
+    }
+    /// ## custom components defined and used by the model (snake name)
+    /// `<component name="Hello">` defines `hello`, See [`Cache::deps`] for the used components
+    pub fn deps(model: &Model) -> Deps {
+        let defines = model
+            .template
+            .as_ref()
+            .filter(|template| template.root && template.is_component())
+            .and_then(|template| template.props.as_ref())
+            .and_then(|props| props.iter().find(|(key, _)| key.name == "name"))
+            .map(|(_, name)| vec![camel_to_snake(&name.to_string())])
+            .unwrap_or_default();
+        (defines, Cache::deps(model.template.as_ref()))
+    }
+    /// ## split files into waves by the custom components they define and use
+    /// - `files`: (defines, uses) of each file, See [`CompileTask::deps`]
+    ///
+    /// a file is in a wave after all files which define the components it uses,
+    /// so that each file is checked and converted with the define widgets of its dependencies,
+    /// the same as compiling one by one in dependency order. files in a component cycle are put into the last wave.
+    ///
+    /// return indexes of files, each wave keeps the order of files
+    pub fn waves(files: &[Deps]) -> Vec<Vec<usize>> {
+        let mut pending = (0..files.len()).collect::<Vec<_>>();
+        let mut waves = vec![];
+        while !pending.is_empty() {
+            let (ready, rest): (Vec<usize>, Vec<usize>) = pending.iter().partition(|&&index| {
+                !pending.iter().any(|&other| {
+                    other != index
+                        && files[other]
+                            .0
+                            .iter()
+                            .any(|define| files[index].1.contains(define))
+                })
+            });
+            if ready.is_empty() {
+                waves.push(rest);
+                break;
+            }
+            waves.push(ready);
+            pending = rest;
+        }
+        waves
+    }
+}
+
+/// ## map items in parallel
+/// items are split into chunks by the available parallelism, results are returned in the order of items
+fn parallel<T, R, F>(items: Vec<T>, f: F) -> Vec<Result<R, Error>>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> Result<R, Error> + Sync,
+{
+    let threads = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    let chunk = items.len().div_ceil(threads).max(1);
+    let mut items = items.into_iter().peekable();
+    let mut chunks = vec![];
+    while items.peek().is_some() {
+        chunks.push(items.by_ref().take(chunk).collect::<Vec<_>>());
+    }
+
+    std::thread::scope(|scope| {
+        let f = &f;
+        let handles = chunks
+            .into_iter()
+            .map(|items| {
+                let len = items.len();
+                let handle = scope.spawn(move || items.into_iter().map(f).collect::<Vec<_>>());
+                (len, handle)
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .flat_map(|(len, handle)| {
+                handle.join().unwrap_or_else(|_| {
+                    (0..len)
+                        .map(|_| Err(Error::from("Makepad Compiler - compile thread panicked")))
+                        .collect()
+                })
+            })
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod test_task {
+    use super::{CompileTask, Deps};
+
+    fn file(defines: &[&str], uses: &[&str]) -> Deps {
+        (
+            defines.iter().map(|s| s.to_string()).collect(),
+            uses.iter().map(|s| s.to_string()).collect(),
+        )
+    }
+
+    #[test]
+    fn waves() {
+        let files = vec![
+            file(&["home"], &["card", "list"]),
+            file(&["card"], &["button"]),
+            file(&["about"], &[]),
+            file(&["list"], &["card"]),
+            // cycle
+            file(&["a"], &["b"]),
+            file(&["b"], &["a"]),
+        ];
+        assert_eq!(
+            CompileTask::waves(&files),
+            vec![vec![1, 2], vec![3], vec![0], vec![4, 5]]
+        );
+    }
+}
@@ -7,7 +7,7 @@ use std::str::FromStr;
 
 use super::MakepadColor;
 use crate::{builtin::prop::LiveDependency, str_to_tk, traits::ToTokensExt};
-use gen_analyzer::value::{BuiltinColor, Enum, Function, Struct, Value};
+use gen_analyzer::value::{BuiltinColor, Enum, Function, Length, Struct, Value};
 use gen_utils::{common::format_float, err_from_to};
 use proc_macro2::TokenStream;
 use quote::quote;
@@ -143,6 +143,18 @@ impl ToTokensExt for Struct {
     }
 }
 
+/// props of custom widgets only accept `Fill`, `Fit` and fixed lengths (px, em), See [`Length::fixed`]
+/// percentages are `Fill`, See [`crate::model::RelativeSize`]
+impl ToTokensExt for Length {
+    fn to_token_stream(&self) -> Result<proc_macro2::TokenStream, gen_utils::error::Error> {
+        match self.fixed() {
+            Some(fixed) => ToTokensExt::to_token_stream(&fixed),
+            None if matches!(self, Length::Fit) => str_to_tk!("Fit"),
+            None => str_to_tk!("Fill"),
+        }
+    }
+}
+
 impl ToTokensExt for Value {
     fn to_token_stream(&self) -> Result<proc_macro2::TokenStream, gen_utils::error::Error> {
         match self {
@@ -157,8 +169,29 @@ impl ToTokensExt for Value {
             Value::Function(function) => ToTokensExt::to_token_stream(function),
             Value::Struct(s) => ToTokensExt::to_token_stream(s),
             Value::Enum(e) => ToTokensExt::to_token_stream(e),
-            Value::Animation(_hash_map) => unimplemented!("wait till GenUI > v0.1.2"),
+            // `transition` and `animation` are converted to the animator of the widget, See [`crate::builtin::Animator`]
+            Value::Animation(_) => {
+                Err(err_from_to!(self.to_string() => "TokenStream, animation has no static value"))
+            }
             Value::UnKnown(s) => parse_str::<TokenStream>(s).map_err(|e| e.to_string().into()),
+            Value::Length(length) => ToTokensExt::to_token_stream(length),
+            // interpolation is rebuilt in the setter, it has no static value
+            Value::Interpolation(interp) => Err(err_from_to!(interp.to_string() => "TokenStream")),
         }
     }
 }
+
+#[cfg(test)]
+mod test_value {
+    use std::collections::HashMap;
+
+    use gen_analyzer::value::Value;
+
+    use crate::traits::ToTokensExt;
+
+    #[test]
+    fn animation() {
+        let value = Value::Animation(HashMap::new());
+        assert!(ToTokensExt::to_token_stream(&value).is_err());
+    }
+}
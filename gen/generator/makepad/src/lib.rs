@@ -2,6 +2,8 @@
 pub mod compiler;
 /// makepad builtin widgets and structs
 pub mod builtin;
+/// static check of `.gen` model before generating makepad code
+pub mod checker;
 /// makepad model, include AppMain, Widget and Virtual
 pub mod model;
 /// needed traits for makepad
@@ -39,6 +41,7 @@ mod test_widget {
             dyn_processor: None,
             lib_content: None,
             router: Some(RouterBuilder::new("/Users/shengyifei/projects/gen_ui/made_with_GenUI/tests/router/router.toml", "").unwrap()),
+            theme: None,
         }
     }
 
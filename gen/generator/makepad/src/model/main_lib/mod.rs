@@ -1,5 +1,9 @@
 mod main_rs;
 mod lib_rs;
+mod responsive_rs;
+mod theme_rs;
 
 pub use main_rs::*;
-pub use lib_rs::*;
\ No newline at end of file
+pub use lib_rs::*;
+pub use responsive_rs::*;
+pub use theme_rs::*;
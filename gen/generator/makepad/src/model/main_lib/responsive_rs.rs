@@ -0,0 +1,23 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse_str;
+
+/// # 生成gen_responsive.rs
+/// 响应式样式的入口, 在启动和窗口尺寸变化时由AppMain调用, See [`crate::model::ResponsiveApply`]
+/// - `modules`: 含有`apply_responsive`的模块, 例如: `views::home`
+pub fn create_responsive_rs(modules: &[String]) -> TokenStream {
+    let modules = modules.iter().map(|module| {
+        let module = parse_str::<TokenStream>(module).unwrap();
+        quote! { crate::#module::apply_responsive(cx, root, size); }
+    });
+
+    quote! {
+        use makepad_widgets::*;
+
+        /// apply the responsive style rules to the widgets under `root`,
+        /// `size` is the inner size of the window, None at startup (only `@platform` rules are applied)
+        pub fn apply(cx: &mut Cx, root: &WidgetRef, size: Option<DVec2>) {
+            #(#modules)*
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse_str;
+
+use crate::compiler::DesignTokens;
+
+/// # 生成gen_theme.rs
+/// 主题注册表, 用于在运行时切换主题, See [`crate::model::ThemeApply`]
+/// - `tokens`: 设计令牌
+/// - `modules`: 含有`apply_theme`的模块, 例如: `views::home`
+pub fn create_theme_rs(tokens: &DesignTokens, modules: &[String]) -> TokenStream {
+    let default = tokens.default.as_str();
+    let names = tokens.themes.iter().map(|theme| theme.name.as_str());
+    let themes = tokens.themes.iter().map(|theme| {
+        let name = theme.name.as_str();
+        let tokens = theme
+            .tokens
+            .iter()
+            .map(|(token, value)| quote! { (#token, #value) });
+        quote! { (#name, &[#(#tokens),*]) }
+    });
+    let modules = modules.iter().map(|module| {
+        let module = parse_str::<TokenStream>(module).unwrap();
+        quote! { crate::#module::apply_theme(cx, root, theme); }
+    });
+
+    quote! {
+        use makepad_widgets::*;
+        use std::sync::RwLock;
+
+        pub const THEMES: &[&str] = &[#(#names),*];
+        pub const DEFAULT_THEME: &str = #default;
+        /// (theme, [(token, value)])
+        pub const TOKENS: &[(&str, &[(&str, &str)])] = &[#(#themes),*];
+
+        static ACTIVE: RwLock<&'static str> = RwLock::new(DEFAULT_THEME);
+
+        pub fn active_theme() -> &'static str {
+            *ACTIVE.read().unwrap()
+        }
+
+        /// value of the token in the active theme, fall back to the default theme
+        pub fn token(name: &str) -> Option<&'static str> {
+            let find = |theme: &str| {
+                TOKENS
+                    .iter()
+                    .find(|(t, _)| *t == theme)
+                    .and_then(|(_, tokens)| tokens.iter().find(|(token, _)| *token == name))
+                    .map(|(_, value)| *value)
+            };
+            find(active_theme()).or_else(|| find(DEFAULT_THEME))
+        }
+
+        /// switch the active theme and apply it to the widgets under `root`,
+        /// return false if the theme is not defined
+        pub fn set_theme(cx: &mut Cx, root: &WidgetRef, theme: &str) -> bool {
+            let Some(theme) = THEMES.iter().copied().find(|t| *t == theme) else {
+                return false;
+            };
+            *ACTIVE.write().unwrap() = theme;
+            #(#modules)*
+            root.redraw(cx);
+            true
+        }
+    }
+}
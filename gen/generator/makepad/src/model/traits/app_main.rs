@@ -7,12 +7,15 @@ pub struct AppMainTrait {
     /// handle_event需要接受一个root节点的名字来生成具体的代码，需要使用者在一开始编译器设置时就提供，默认获取`root`
     /// 例如：`self.root.handle_event(cx, event, &mut Scope::empty());`
     pub handle_event: String,
+    /// 是否有响应式样式, 有则在启动和窗口尺寸变化时调用`crate::gen_responsive::apply`
+    pub responsive: bool,
 }
 
 impl Default for AppMainTrait {
     fn default() -> Self {
         Self {
             handle_event: "root".to_string(),
+            responsive: false,
         }
     }
 }
@@ -29,12 +32,26 @@ impl ToTokens for AppMainTrait {
             self.handle_event
         ))
         .unwrap();
+        // [响应式样式] 启动时应用`@platform`, 窗口尺寸变化时应用`@media` ----------------------------
+        let responsive = self.responsive.then(|| {
+            let root = parse_str::<TokenStream>(&format!("&self.{}", self.handle_event)).unwrap();
+            quote! {
+                match event {
+                    Event::Startup => crate::gen_responsive::apply(cx, #root, None),
+                    Event::WindowGeomChange(e) => {
+                        crate::gen_responsive::apply(cx, #root, Some(e.new_geom.inner_size))
+                    }
+                    _ => {}
+                }
+            }
+        });
         tokens.extend(quote! {
             impl AppMain for App {
                 #[allow(unused_variables)]
                 fn handle_event(&mut self, cx: &mut Cx, event: &Event){
                     self.match_event(cx, event);
                     #root
+                    #responsive
                 }
             }
         });
@@ -51,6 +68,15 @@ mod app_main_trait_tests {
         dbg!(&app_main.to_token_stream().to_string());
     }
 
+    #[test]
+    fn test_app_main_trait_responsive() {
+        let mut app_main = AppMainTrait::default();
+        app_main.responsive = true;
+        let tk = app_main.to_token_stream().to_string();
+        assert!(tk.contains("crate :: gen_responsive :: apply (cx , & self . root , None)"));
+        assert!(tk.contains("Event :: WindowGeomChange"));
+    }
+
     #[test]
     fn test_app_main_trait_none() {
         let mut app_main = AppMainTrait::default();
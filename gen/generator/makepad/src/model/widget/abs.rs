@@ -21,6 +21,8 @@ pub enum AbsWidget {
         name: String,
         props: Option<HashMap<String, String>>,
         events: Option<HashMap<String, String>>,
+        /// slots declared by `<slot>`, key: name of the slot, value: props of the scoped slot, See [`super::Slot`]
+        slots: Option<HashMap<String, Vec<String>>>,
     },
 }
 
@@ -29,16 +31,8 @@ impl PartialEq for AbsWidget {
         match (self, other) {
             (Self::Builtin(l0), Self::Builtin(r0)) => l0 == r0,
             (
-                Self::Define {
-                    name: l_name,
-                    props: _l_props,
-                    events: _l_events,
-                },
-                Self::Define {
-                    name: r_name,
-                    props: _r_props,
-                    events: _r_events,
-                },
+                Self::Define { name: l_name, .. },
+                Self::Define { name: r_name, .. },
             ) => l_name == r_name,
             _ => false,
         }
@@ -64,6 +58,7 @@ impl AbsWidget {
                 name: name.to_string(),
                 props: to_prop_map(props),
                 events: None,
+                slots: None,
             }
         }
     }
@@ -0,0 +1,70 @@
+use gen_analyzer::{
+    value::{Bind, Expr, Value},
+    PropKV,
+};
+
+use super::class::bind_value;
+
+/// bind expression in the setter of `field`, See [`bind_value`]
+///
+/// setter of `count`: `count > 0 && !loading` => `value > 0 && !self.loading`
+pub fn expr_setter(expr: &Expr, field: &str) -> String {
+    expr.fmt_with(&mut |idents| bind_value(idents, field).to_string())
+}
+
+/// visible of `:else` in the setter of `field` when any condition before is a bind expression,
+/// `:else` is visible only when all the conditions are false
+///
+/// `:if="count > 0"` + `:else_if="loading"` => `!(value > 0) && !self.loading`
+pub fn else_setter(conditions: &[PropKV], field: &str) -> Option<String> {
+    if !conditions
+        .iter()
+        .any(|kv| matches!(kv.value, Value::Bind(Bind::Expr(_))))
+    {
+        return None;
+    }
+
+    conditions
+        .iter()
+        .map(|kv| match &kv.value {
+            Value::Bind(Bind::Expr(expr)) => Some(format!("!({})", expr_setter(expr, field))),
+            Value::Bind(Bind::Normal(idents)) => Some(format!("!{}", bind_value(idents, field))),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()
+        .map(|conditions| conditions.join(" && "))
+}
+
+#[cfg(test)]
+mod test_bind_expr {
+    use gen_analyzer::{
+        value::{Bind, Expr, Ident, Value},
+        PropKV,
+    };
+
+    use super::{else_setter, expr_setter};
+
+    #[test]
+    fn setter() {
+        let expr = Expr::parse("count > 0 && !user.loading").unwrap();
+        assert_eq!(
+            expr_setter(&expr, "count").replace(' ', ""),
+            "value>0&&!self.user.loading"
+        );
+        assert!(syn::parse_str::<syn::Expr>(&expr_setter(&expr, "user")).is_ok());
+
+        let conditions = vec![
+            PropKV::new(":else".to_string(), Value::Bind(Bind::Expr(expr))),
+            PropKV::new(
+                ":else".to_string(),
+                Value::Bind(Bind::Normal(vec![Ident::new("loading")])),
+            ),
+        ];
+        let setter = else_setter(&conditions, "loading").unwrap();
+        assert_eq!(
+            setter.replace(' ', ""),
+            "!(self.count>0&&!self.user.loading)&&!value"
+        );
+        assert!(else_setter(&conditions[1..], "loading").is_none());
+    }
+}
@@ -0,0 +1,338 @@
+use std::collections::HashSet;
+
+use gen_analyzer::{
+    value::{Bind, Ident, IdentSplit, Value},
+    IdClass, Props, Style, StyleVisitor,
+};
+use gen_utils::{common::ident, err_from_to, error::Error};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse_str;
+
+use crate::token::ToLiveDesign;
+
+use super::WidgetType;
+
+/// key of `:class` bind
+pub const CLASS: &str = "class";
+
+/// # Class Apply
+/// props of the classes which can be set by `:class` bind, they are precomputed from the style,
+/// the setter of the bind fields reapplies them when the value changes, See [`crate::two_way_binding::GetSet`]
+/// - `:class="{ active: is_active }"`: class map, the class is on when the bind is true
+/// - `:class="class_name"`: field of class names (`String`, split by space)
+///
+/// ```rust,ignore
+/// fn set_is_active(&mut self, cx: &mut Cx, value: bool) -> Result<(), Box<dyn std::error::Error>> {
+///     let widget = self.widget(id!(btn));
+///     widget.apply_over(cx, live! { draw_bg: { color: #FFF } });
+///     if value {
+///         widget.apply_over(cx, live! { draw_bg: { color: #F00 } });
+///     }
+///     widget.redraw(cx);
+///     // ...
+/// }
+/// ```
+/// each class is applied on its own in order (compound selectors such as `.btn.active` are matched with the static classes),
+/// props in template (inline) always win and are never changed
+#[derive(Debug, Clone)]
+pub struct ClassApply {
+    pub id: String,
+    pub bind: Bind,
+    /// live props out of the classes, used to reset the widget before the classes are applied
+    pub base: Option<TokenStream>,
+    /// (class, live props), classes which do not change any prop are skipped
+    pub classes: Vec<(String, TokenStream)>,
+}
+
+impl ClassApply {
+    /// ## precompute the classes of `:class` bind
+    /// - `name`, `id`, `class`, `props`: the widget and its static classes and inline props
+    /// - `chain`: parents of the widget, See [`StyleVisitor::visit`]
+    pub fn new(
+        name: &str,
+        id: &str,
+        class: Option<&Value>,
+        props: Option<&Props>,
+        bind: &Value,
+        styles: &Style,
+        chain: &[IdClass],
+    ) -> Result<Self, Error> {
+        let bind = bind.as_bind()?;
+        let candidates = match &bind {
+            Bind::Class(items) => items.iter().fold(vec![], |mut classes, item| {
+                if !classes.contains(&item.class) {
+                    classes.push(item.class.to_string());
+                }
+                classes
+            }),
+            Bind::Normal(_) => StyleVisitor::classes(styles),
+            _ => {
+                return Err(err_from_to!(
+                    bind.to_string() => "Makepad Class, `:class` should bind a field or a class map"
+                ))
+            }
+        };
+
+        let id = id.to_string();
+        let inline: HashSet<String> = props
+            .map(|props| props.keys().map(|k| k.name.to_string()).collect())
+            .unwrap_or_default();
+        let statics = class
+            .map(gen_analyzer::class_value_to_string)
+            .transpose()?
+            .unwrap_or_default();
+        let base = StyleVisitor::visit(styles, Some(&id), class, chain)?.props;
+        let live_props = |props: Props| -> Result<Option<TokenStream>, Error> {
+            if props.is_empty() {
+                return Ok(None);
+            }
+            Ok(WidgetType::try_from((name.to_string(), Some(props), false))?.props())
+        };
+
+        let mut changed = HashSet::new();
+        let mut classes = vec![];
+        for candidate in candidates {
+            let names = statics
+                .iter()
+                .map(|class| class.trim_start_matches('.'))
+                .chain(std::iter::once(candidate.as_str()))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let class = Value::parse_template(&format!("[{}]", names))?;
+            let props = StyleVisitor::visit(styles, Some(&id), Some(&class), chain)?
+                .props
+                .into_iter()
+                .filter(|(k, v)| !inline.contains(&k.name) && base.get(k) != Some(v))
+                .collect::<Props>();
+            changed.extend(props.keys().map(|k| k.name.to_string()));
+            if let Some(props) = live_props(props)? {
+                classes.push((candidate, props));
+            }
+        }
+
+        let base = base
+            .into_iter()
+            .filter(|(k, _)| changed.contains(&k.name))
+            .collect::<Props>();
+
+        Ok(Self {
+            id,
+            bind,
+            base: live_props(base)?,
+            classes,
+        })
+    }
+
+    /// ## reapply the classes in the setter of `field`
+    /// `value` is the new value of the field, other fields are read from `self`
+    pub fn to_setter(&self, field: &str) -> TokenStream {
+        let id = ident(&self.id);
+        let base = self.base.as_ref().map(|base| {
+            quote! {
+                widget.apply_over(cx, live!{ #base });
+            }
+        });
+        let apply = match &self.bind {
+            Bind::Class(items) => {
+                let rules = items.iter().filter_map(|item| {
+                    let (_, props) = self
+                        .classes
+                        .iter()
+                        .find(|(class, _)| class == &item.class)?;
+                    let value = bind_value(&item.ident, field);
+                    let not = item.not.then(|| quote! {!});
+                    Some(quote! {
+                        if #not #value {
+                            widget.apply_over(cx, live!{ #props });
+                        }
+                    })
+                });
+                quote! { #(#rules)* }
+            }
+            Bind::Normal(idents) => {
+                let value = bind_value(idents, field);
+                let arms = self.classes.iter().map(|(class, props)| {
+                    quote! {
+                        #class => widget.apply_over(cx, live!{ #props }),
+                    }
+                });
+                quote! {
+                    for class in #value.split_whitespace() {
+                        match class {
+                            #(#arms)*
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            _ => TokenStream::new(),
+        };
+
+        quote! {
+            let widget = self.widget(id!(#id));
+            #base
+            #apply
+            widget.redraw(cx);
+        }
+    }
+}
+
+/// `a.b` => `value.b` if `a` is the field which is being set, else `self.a.b`
+pub(super) fn bind_value(idents: &[Ident], field: &str) -> TokenStream {
+    let path = idents
+        .iter()
+        .map(|ident| ident.to_string())
+        .collect::<String>();
+    let path = match idents.first() {
+        Some(first) if first.name == field && first.split == IdentSplit::None => {
+            format!("value{}", &path[first.name.len()..])
+        }
+        _ => format!("self.{}", path),
+    };
+    parse_str(&path).unwrap()
+}
+
+#[cfg(test)]
+mod test_class {
+    use gen_analyzer::{value::Value, Model};
+    use gen_utils::compiler::ToRs;
+
+    use crate::{compiler::Context, model::Widget};
+
+    use super::ClassApply;
+
+    #[test]
+    fn apply() {
+        let input = r#"
+<template>
+    <component name="Home">
+        <button id="btn" class="btn" :class="{ active: is_active, off: !enabled }" text="'Hi'"></button>
+    </component>
+</template>
+
+<style>
+.btn {
+    height: 30;
+    &.active {
+        height: 40;
+        width: 100;
+    }
+}
+.off {
+    height: 10;
+}
+.none {
+    text: 'no';
+}
+</style>
+"#;
+        let model = input.parse::<Model>().unwrap();
+        let template = model.template.as_ref().unwrap();
+        let btn = &template.children.as_ref().unwrap()[0];
+        let bind = btn.binds.as_ref().unwrap().values().next().unwrap();
+        let apply = ClassApply::new(
+            &btn.name,
+            btn.id.as_ref().unwrap(),
+            btn.class.as_ref(),
+            btn.props.as_ref(),
+            bind,
+            model.style.as_ref().unwrap(),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            apply
+                .classes
+                .iter()
+                .map(|(c, _)| c.as_str())
+                .collect::<Vec<_>>(),
+            vec!["active", "off"]
+        );
+        assert!(apply.base.is_some());
+
+        let setter = apply.to_setter("is_active").to_string();
+        assert!(setter.contains("if value"));
+        assert!(setter.contains("if ! self . enabled"));
+
+        // field bind: all classes in style, inline `text` is never changed
+        let apply = ClassApply::new(
+            &btn.name,
+            "btn",
+            None,
+            btn.props.as_ref(),
+            &Value::Bind("cls".parse().unwrap()),
+            model.style.as_ref().unwrap(),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            apply
+                .classes
+                .iter()
+                .map(|(c, _)| c.as_str())
+                .collect::<Vec<_>>(),
+            vec!["btn", "off"]
+        );
+        assert!(apply
+            .to_setter("cls")
+            .to_string()
+            .contains("value . split_whitespace ()"));
+    }
+
+    #[test]
+    fn nested_field() {
+        let input = r#"
+<template>
+    <component name="Home">
+        <button id="btn" :class="{ hidden: !state.visible }"></button>
+    </component>
+</template>
+
+<script>
+#[prop]
+#[derive(Debug, Clone)]
+pub struct State {
+    visible: bool,
+}
+
+#[component]
+pub struct Home {
+    state: State,
+}
+</script>
+
+<style>
+.hidden {
+    height: 0;
+}
+</style>
+"#;
+        let model = input.parse::<Model>().unwrap();
+        let template = model.template.as_ref().unwrap();
+        let btn = &template.children.as_ref().unwrap()[0];
+        let bind = btn.binds.as_ref().unwrap().values().next().unwrap();
+        let apply = ClassApply::new(
+            &btn.name,
+            btn.id.as_ref().unwrap(),
+            btn.class.as_ref(),
+            btn.props.as_ref(),
+            bind,
+            model.style.as_ref().unwrap(),
+            &[],
+        )
+        .unwrap();
+        assert!(apply
+            .to_setter("state")
+            .to_string()
+            .contains("if ! value . visible"));
+
+        // the setter of the root field re-applies the class
+        let mut context = Context::default();
+        let widget = Widget::new(&mut context, model).unwrap();
+        let code = widget.content().unwrap().to_string().replace(' ', "");
+        let setter = &code[code.find("fnset_state").unwrap()..];
+        let setter = &setter[..setter.find("fnget_state").unwrap_or(setter.len())];
+        assert!(setter.contains("if!value.visible{widget.apply_over"));
+    }
+}
@@ -1,13 +1,16 @@
 use crate::{
+    builtin::Animator,
     compiler::{Context, WidgetPoll},
     model::{
-        role::ForParent, widget::role::Role, AbsWidget, PropWidget, Widget, WidgetTemplate,
-        WidgetType,
+        check_style_bind, interpolation_format, role::ForParent, widget::role::Role, AbsWidget,
+        ClassApply, PropWidget, RelativeSize, Slot, Widget, WidgetTemplate, WidgetType, CLASS,
+        STYLE,
     },
 };
 
 use gen_analyzer::{
-    value::Bind, IdClass, Polls, Script, Style, StyleVisitor, SugarProps, Template,
+    value::{Bind, Ident, Value},
+    IdClass, Polls, Script, Style, StyleVisitor, SugarProps, Template,
 };
 use gen_utils::{common::Source, err_from_to, error::Error};
 use std::{
@@ -98,12 +101,15 @@ fn handle_template(
     index: usize,
     father_role: Role,
 ) -> Result<TemplateResult, Error> {
+    // [处理<slot>] ----------------------------------------------------------------------------------------
+    let (template, slot_scope) = Slot::placeholder(template)?;
     let is_static = template.is_static();
     let is_define = template.is_component();
     let Template {
         id,
         class,
         as_prop,
+        slot,
         name,
         mut props,
         callbacks,
@@ -113,20 +119,51 @@ fn handle_template(
         sugar_props,
         parent,
         binds,
+        key,
+        virtual_for,
         ..
     } = template;
+    let is_father_virtual = father_role.is_virtual();
     // [绑定变量处理] ----------------------------------------------------------------------------------------
     let mut bind_props = HashMap::new();
     if let Some(binds) = binds.as_ref() {
-        for (k, v) in binds {
-            bind_props.insert(v.as_bind()?.ident(), k.name.to_string());
+        // `:class` and `:style` are applied by the setter of the fields, See [`ClassApply`] and [`StyleBind`]
+        for (k, v) in binds
+            .iter()
+            .filter(|(k, _)| k.name != CLASS && k.name != STYLE)
+        {
+            // interpolation is rebuilt by `format!`, See [`interpolation_format`]
+            let value = match v {
+                Value::Interpolation(interp) => interpolation_format(interp, Ident::fmt_idents),
+                _ => v.as_bind()?.ident(),
+            };
+            bind_props.insert(value, k.name.to_string());
         }
     }
     // [处理语法糖] -----------------------------------------------------------------------------------------
     // [for] ------------------------------------------------------------------------------------------
     let mut role = if let SugarProps::For(for_sign) = sugar_props {
         if let Ok(Bind::For(bind)) = for_sign.as_bind() {
+            let key = key
+                .map(|key| key.as_bind().map(|key| key.to_string()))
+                .transpose()?;
+            // keyed and virtual children are reused, the nested lists of them can not be updated by the old index
+            if father_role.is_for()
+                && (key.is_some()
+                    || virtual_for.is_some()
+                    || father_role.is_keyed_for()
+                    || father_role.is_virtual_list())
+            {
+                return Err(err_from_to!(
+                    "GenUI Component" => "Makepad Widget, `:key` and `virtual` are not supported in nested `:for` widget!"
+                ));
+            }
             let mut parent: ForParent = parent.as_ref().unwrap().into();
+            if virtual_for.is_some() && parent.is_root {
+                return Err(err_from_to!(
+                    "GenUI Component" => "Makepad Widget, `virtual` list should be in a scrollable view, not the root!"
+                ));
+            }
             parent.set_credential(father_role);
             if let Some(id) = id.as_ref() {
                 Ok(Role::For {
@@ -134,6 +171,8 @@ fn handle_template(
                     creditial: bind,
                     origin_pos: index,
                     props: bind_props.clone(),
+                    key,
+                    virtual_for,
                     children: vec![],
                     id: id.to_string(),
                     name: name.to_string(),
@@ -177,21 +216,47 @@ fn handle_template(
             ));
         }
     }
-    // [处理节点, 属性, 子组件] ------------------------------------------------------------------------------
-    if let Some(styles) = styles.as_ref() {
-        let other_props = StyleVisitor::visit(styles, id.as_ref(), class.as_ref(), chain)?;
-        // 合并props
-        if !other_props.is_empty() {
-            if props.is_none() {
-                props = Some(HashMap::new());
-            }
-            if let Some(props) = props.as_mut() {
-                for p in other_props {
-                    props.extend(p);
-                }
-            }
+    // [处理:style] ----------------------------------------------------------------------------------------
+    check_style_bind(
+        binds.as_ref(),
+        as_prop.is_some() || is_role_virtual || is_father_virtual,
+    )?;
+    // [处理:class] ----------------------------------------------------------------------------------------
+    // 需要在合并样式之前计算, 模版中的属性不会被class修改
+    let class_bind = binds
+        .as_ref()
+        .and_then(|binds| binds.iter().find(|(k, _)| k.name == CLASS));
+    let class_apply = match (class_bind, styles, id.as_ref()) {
+        (Some(_), _, _) if as_prop.is_some() || is_role_virtual || is_father_virtual => {
+            return Err(err_from_to!(
+                "GenUI Component" => "Makepad Widget, `:class` bind is not supported in as_prop, `:for` or `:if` widget!"
+            ));
         }
-    }
+        (Some((_, bind)), Some(styles), Some(id)) => Some(ClassApply::new(
+            &name,
+            id,
+            class.as_ref(),
+            props.as_ref(),
+            bind,
+            styles,
+            chain,
+        )?),
+        _ => None,
+    };
+    // [处理节点, 属性, 子组件] ------------------------------------------------------------------------------
+    // 合并props, 模版中的属性优先于样式, 伪类状态和动画转为组件的animator
+    let animator = if let Some(styles) = styles.as_ref() {
+        let cascade = StyleVisitor::visit(styles, id.as_ref(), class.as_ref(), chain)?;
+        Animator::merge(&name, styles, cascade, &mut props)?
+    } else {
+        None
+    };
+    // [处理百分比尺寸] 绘制后根据父组件的尺寸计算, See [`RelativeSize`] ------------------------------------------
+    let relative = RelativeSize::new(
+        props.as_ref(),
+        root,
+        as_prop.is_some() || is_role_virtual || is_father_virtual,
+    )?;
 
     let ty = if !is_define {
         WidgetType::try_from((name, props, root))?
@@ -224,6 +289,8 @@ fn handle_template(
                 }
             }
         }
+        // 兄弟节点不是父级
+        chain.pop();
         if w_children.is_empty() {
             None
         } else {
@@ -233,6 +300,15 @@ fn handle_template(
         None
     };
 
+    Slot::check(
+        slot.as_ref(),
+        slot_scope.as_ref(),
+        as_prop.is_some() || is_role_virtual || is_father_virtual,
+        &ty,
+        root,
+        children.as_ref(),
+    )?;
+
     let binds = if bind_props.is_empty() {
         None
     } else {
@@ -243,11 +319,16 @@ fn handle_template(
         id,
         is_root: root,
         as_prop,
+        slot,
+        slot_scope,
         is_static,
         ty,
         children,
         role,
         binds,
+        animator,
+        class_apply,
+        relative,
     };
     if is_role_virtual {
         let role = widget.role.clone();
@@ -12,11 +12,16 @@ pub fn single_style(
         id: None,
         is_root: true,
         as_prop: None,
+        slot: None,
+        slot_scope: None,
         is_static: true,
         ty: WidgetType::Global(style),
         children: None,
         role: Role::default(),
         binds: None,
+        animator: None,
+        class_apply: None,
+        relative: None,
     };
 
     Ok(Widget {
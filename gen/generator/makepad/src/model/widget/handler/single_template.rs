@@ -1,7 +1,7 @@
 use gen_analyzer::Template;
 use gen_utils::{common::Source, err_from_to, error::Error};
 
-use crate::model::{widget::role::Role, Widget, WidgetTemplate, WidgetType};
+use crate::model::{widget::role::Role, Slot, Widget, WidgetTemplate, WidgetType};
 
 /// 处理单个模板<template>节点
 pub fn single_template(
@@ -33,11 +33,14 @@ fn handle(template: Template) -> Result<WidgetTemplate, Error> {
     // [检查并解析template] ---------------------------------------------------------------------------------
     // - 对于只有<template>节点的.gen文件, 不能带有动态脚本, 不能带有callbacks, 只能是静态组件
     // - 不能含有inherit属性首个标签不能是<component>
+    let (template, slot_scope) = Slot::placeholder(template)?;
+    Slot::check_static(slot_scope.as_ref())?;
     let is_static = template.is_static();
     let is_define = template.is_component();
     let Template {
         id,
         as_prop,
+        slot,
         name,
         props,
         callbacks,
@@ -75,15 +78,28 @@ fn handle(template: Template) -> Result<WidgetTemplate, Error> {
     } else {
         None
     };
+    Slot::check(
+        slot.as_ref(),
+        slot_scope.as_ref(),
+        as_prop.is_some(),
+        &ty,
+        root,
+        children.as_ref(),
+    )?;
 
     Ok(WidgetTemplate {
         id,
         is_root: root,
         as_prop,
+        slot,
+        slot_scope,
         is_static,
         ty,
         children,
         role: Role::default(),
         binds: None,
+        animator: None,
+        class_apply: None,
+        relative: None,
     })
 }
@@ -3,12 +3,18 @@ use std::{
     sync::{Arc, RwLock},
 };
 
-use gen_analyzer::{value::Bind, Polls, Script, SugarProps, Template};
+use gen_analyzer::{
+    value::{Bind, Ident, Value},
+    Polls, Script, SugarProps, Template,
+};
 use gen_utils::{common::Source, err_from_to, error::Error};
 
 use crate::{
     compiler::{Context, WidgetPoll},
-    model::{role::ForParent, widget::role::Role, AbsWidget, Widget, WidgetTemplate, WidgetType},
+    model::{
+        check_style_bind, interpolation_format, role::ForParent, widget::role::Role, AbsWidget,
+        Slot, Widget, WidgetTemplate, WidgetType, CLASS, STYLE,
+    },
 };
 
 use super::{TemplatePtrs, TemplateResult};
@@ -86,11 +92,14 @@ fn handle(
     index: usize,
     father_role: Role,
 ) -> Result<TemplateResult, Error> {
+    // [处理<slot>] ----------------------------------------------------------------------------------------
+    let (template, slot_scope) = Slot::placeholder(template)?;
     let is_static = template.is_static();
     let is_define = template.is_component();
     let Template {
         id,
         as_prop,
+        slot,
         name,
         props,
         callbacks,
@@ -100,20 +109,51 @@ fn handle(
         sugar_props,
         parent,
         binds,
+        key,
+        virtual_for,
         ..
     } = template;
+    let is_father_virtual = father_role.is_virtual();
     // [绑定变量处理] ----------------------------------------------------------------------------------------
     let mut bind_props = HashMap::new();
     if let Some(binds) = binds.as_ref() {
-        for (k, v) in binds {
-            bind_props.insert(v.as_bind()?.ident(), k.name.to_string());
+        // `:class` and `:style` are applied by the setter of the fields, See [`ClassApply`] and [`StyleBind`]
+        for (k, v) in binds
+            .iter()
+            .filter(|(k, _)| k.name != CLASS && k.name != STYLE)
+        {
+            // interpolation is rebuilt by `format!`, See [`interpolation_format`]
+            let value = match v {
+                Value::Interpolation(interp) => interpolation_format(interp, Ident::fmt_idents),
+                _ => v.as_bind()?.ident(),
+            };
+            bind_props.insert(value, k.name.to_string());
         }
     }
     // [处理语法糖] -----------------------------------------------------------------------------------------
     // - [for] --------------------------------------------------------------------------------------------
     let mut role = if let SugarProps::For(for_sign) = sugar_props {
         if let Ok(Bind::For(bind)) = for_sign.as_bind() {
+            let key = key
+                .map(|key| key.as_bind().map(|key| key.to_string()))
+                .transpose()?;
+            // keyed and virtual children are reused, the nested lists of them can not be updated by the old index
+            if father_role.is_for()
+                && (key.is_some()
+                    || virtual_for.is_some()
+                    || father_role.is_keyed_for()
+                    || father_role.is_virtual_list())
+            {
+                return Err(err_from_to!(
+                    "GenUI Component" => "Makepad Widget, `:key` and `virtual` are not supported in nested `:for` widget!"
+                ));
+            }
             let mut parent: ForParent = parent.as_ref().unwrap().into();
+            if virtual_for.is_some() && parent.is_root {
+                return Err(err_from_to!(
+                    "GenUI Component" => "Makepad Widget, `virtual` list should be in a scrollable view, not the root!"
+                ));
+            }
             parent.set_credential(father_role);
             if let Some(id) = id.as_ref() {
                 Ok(Role::For {
@@ -121,6 +161,8 @@ fn handle(
                     creditial: bind,
                     origin_pos: index,
                     props: bind_props.clone(),
+                    key,
+                    virtual_for,
                     children: vec![],
                     id: id.to_string(),
                     name: name.to_string(),
@@ -163,6 +205,11 @@ fn handle(
             ));
         }
     }
+    // [处理:style] ----------------------------------------------------------------------------------------
+    check_style_bind(
+        binds.as_ref(),
+        as_prop.is_some() || is_role_virtual || is_father_virtual,
+    )?;
     // [处理节点, 属性, 子组件] ------------------------------------------------------------------------------
     let ty = if !is_define {
         WidgetType::try_from((name, props, root))?
@@ -192,6 +239,15 @@ fn handle(
         None
     };
 
+    Slot::check(
+        slot.as_ref(),
+        slot_scope.as_ref(),
+        as_prop.is_some() || is_role_virtual || is_father_virtual,
+        &ty,
+        root,
+        children.as_ref(),
+    )?;
+
     let binds = if bind_props.is_empty() {
         None
     } else {
@@ -202,11 +258,16 @@ fn handle(
         id,
         is_root: root,
         as_prop,
+        slot,
+        slot_scope,
         is_static,
         ty,
         children,
         role,
         binds,
+        animator: None,
+        class_apply: None,
+        relative: None,
     };
     if is_role_virtual {
         let role = widget.role.clone();
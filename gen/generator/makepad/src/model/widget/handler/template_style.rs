@@ -1,8 +1,10 @@
-use crate::model::{widget::role::Role, Widget, WidgetTemplate, WidgetType};
+use crate::{
+    builtin::Animator,
+    model::{widget::role::Role, RelativeSize, Slot, Widget, WidgetTemplate, WidgetType},
+};
 
 use gen_analyzer::{IdClass, Style, StyleVisitor, Template};
 use gen_utils::{common::Source, err_from_to, error::Error};
-use std::collections::HashMap;
 
 /// 处理template + style的情况
 pub fn template_style(
@@ -39,12 +41,16 @@ fn handle(
     styles: Option<&Style>,
     chain: &mut Vec<IdClass>,
 ) -> Result<WidgetTemplate, Error> {
+    // [处理<slot>] ----------------------------------------------------------------------------------------
+    let (template, slot_scope) = Slot::placeholder(template)?;
+    Slot::check_static(slot_scope.as_ref())?;
     let is_static = template.is_static();
     let is_define = template.is_component();
     let Template {
         id,
         class,
         as_prop,
+        slot,
         name,
         mut props,
         callbacks,
@@ -66,20 +72,15 @@ fn handle(
         ));
     }
     // [处理节点, 属性, 子组件] ------------------------------------------------------------------------------
-    if let Some(styles) = styles.as_ref() {
-        let other_props = StyleVisitor::visit(styles, id.as_ref(), class.as_ref(), chain)?;
-        // 合并props
-        if !other_props.is_empty() {
-            if props.is_none() {
-                props = Some(HashMap::new());
-            }
-            if let Some(props) = props.as_mut() {
-                for p in other_props {
-                    props.extend(p);
-                }
-            }
-        }
-    }
+    // 合并props, 模版中的属性优先于样式, 伪类状态和动画转为组件的animator
+    let animator = if let Some(styles) = styles.as_ref() {
+        let cascade = StyleVisitor::visit(styles, id.as_ref(), class.as_ref(), chain)?;
+        Animator::merge(&name, styles, cascade, &mut props)?
+    } else {
+        None
+    };
+    // [处理百分比尺寸] 绘制后根据父组件的尺寸计算, See [`RelativeSize`] ------------------------------------------
+    let relative = RelativeSize::new(props.as_ref(), root, as_prop.is_some())?;
 
     let ty = if !is_define {
         WidgetType::try_from((name, props, root))?
@@ -97,18 +98,33 @@ fn handle(
             let w = handle(child, styles, chain)?;
             w_children.push(w);
         }
+        // 兄弟节点不是父级
+        chain.pop();
         Some(w_children)
     } else {
         None
     };
+    Slot::check(
+        slot.as_ref(),
+        slot_scope.as_ref(),
+        as_prop.is_some(),
+        &ty,
+        root,
+        children.as_ref(),
+    )?;
     Ok(WidgetTemplate {
         id,
         is_root: root,
         as_prop,
+        slot,
+        slot_scope,
         is_static,
         ty,
         children,
         role: Role::default(),
         binds: None,
+        animator,
+        class_apply: None,
+        relative,
     })
 }
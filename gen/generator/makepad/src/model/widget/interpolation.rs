@@ -0,0 +1,57 @@
+use gen_analyzer::value::{Ident, Interpolation, Segment};
+
+use super::class::bind_value;
+
+/// # Interpolation Format
+/// build the `format!` expression of the interpolation, `{` and `}` in the literal text are escaped
+/// - `bind`: convert the bound idents to the expression, such as `item.name` in `:for` children
+///
+/// `Hello {{ user.name }}, {{ count }}` => `format!("Hello {}, {}", user.name, count)`
+pub fn interpolation_format<F>(interp: &Interpolation, mut bind: F) -> String
+where
+    F: FnMut(&Vec<Ident>) -> String,
+{
+    let (literal, args) = interp.0.iter().fold(
+        (String::new(), vec![]),
+        |(mut literal, mut args), segment| {
+            match segment {
+                Segment::Text(text) => {
+                    literal.push_str(&text.replace('{', "{{").replace('}', "}}"));
+                }
+                Segment::Bind(idents) => {
+                    literal.push_str("{}");
+                    args.push(bind(idents));
+                }
+            }
+            (literal, args)
+        },
+    );
+
+    format!("format!({:?}, {})", literal, args.join(", "))
+}
+
+/// format expression in the setter of `field`, See [`bind_value`]
+///
+/// setter of `user`: `Hello {{ user.name }}, {{ count }}` => `format!("Hello {}, {}", value.name, self.count)`
+pub fn interpolation_setter(interp: &Interpolation, field: &str) -> String {
+    interpolation_format(interp, |idents| bind_value(idents, field).to_string())
+}
+
+#[cfg(test)]
+mod test_interpolation {
+    use gen_analyzer::value::{Ident, Interpolation};
+
+    use super::{interpolation_format, interpolation_setter};
+
+    #[test]
+    fn format() {
+        let interp = Interpolation::parse("{ \"Hi\" } {{ user.name }}, {{ count }}").unwrap();
+        assert_eq!(
+            interpolation_format(&interp, Ident::fmt_idents),
+            r#"format!("{{ \"Hi\" }} {}, {}", user.name, count)"#
+        );
+        let setter = interpolation_setter(&interp, "user").replace(' ', "");
+        assert!(setter.ends_with(r#"value.name,self.count)"#));
+        assert!(syn::parse_str::<syn::Expr>(&interpolation_setter(&interp, "count")).is_ok());
+    }
+}
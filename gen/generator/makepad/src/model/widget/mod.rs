@@ -1,19 +1,36 @@
 mod abs;
+mod bind_expr;
+mod class;
 mod handler;
+mod interpolation;
+mod relative;
+mod responsive;
 pub mod role;
+mod slot;
+mod style_bind;
 mod template;
+mod theme;
 mod traits;
 
 use std::sync::{Arc, RwLock};
 
 pub use abs::*;
+pub use bind_expr::*;
+pub use class::*;
 pub use handler::*;
+pub use interpolation::*;
+pub use relative::*;
+pub use responsive::*;
+pub use slot::*;
+pub use style_bind::*;
 pub use template::*;
+pub use theme::*;
 pub use traits::*;
 
 use crate::{
-    compiler::Context,
+    compiler::{Context, WidgetPoll},
     token::{import_default_all, import_draw_shader, use_crate_all, use_default_all},
+    visitor::SlotLzVisitor,
 };
 use gen_analyzer::{Model, Polls, Script, Style, Template};
 use gen_utils::{common::Source, compiler::ToRs, error::Error};
@@ -43,6 +60,17 @@ impl Widget {
         let widget = Widget::try_from((context, model))?;
         Ok(widget)
     }
+    /// ## put the children of custom components into slots, See [`Slot::resolve`]
+    /// - `poll`: compiled define widgets, the slots of custom components are in it
+    pub fn resolve_slots(&mut self, poll: &WidgetPoll) {
+        for template in self
+            .template
+            .iter_mut()
+            .chain(self.template_ptrs.iter_mut().flatten())
+        {
+            Slot::resolve(template, poll);
+        }
+    }
     pub fn imports(&self) -> Option<proc_macro2::TokenStream> {
         self.script.as_ref().and_then(|sc| sc.uses())
     }
@@ -53,7 +81,7 @@ impl Widget {
             if let Some(patch_sc) = self.script.as_ref() {
                 if let crate::script::Script::Rust(patch_sc) = patch_sc {
                     if patch_sc.live_component.is_some() {
-                        return Ok(());
+                        return self.visit_relative();
                     }
                     // 说明没有进行具体的定义，但有一些其他的代码，需要patch
                     self.script = template
@@ -71,6 +99,24 @@ impl Widget {
                     })
                     .transpose()?;
             }
+            // [scoped slot] 没有`#[component]`时在默认script中注册调用方使用的作用域插槽
+            if let Some(crate::script::Script::Rust(sc)) = self.script.as_mut() {
+                if let Some(impls) = sc.impls.as_mut() {
+                    SlotLzVisitor::visit_default(template, impls)?;
+                }
+            }
+        }
+
+        self.visit_relative()
+    }
+    /// ## resize the widgets with percentage size after drawing, See [`RelativeSize`]
+    fn visit_relative(&mut self) -> Result<(), Error> {
+        if let Some(template) = self.template.as_ref() {
+            let impls = match self.script.as_mut() {
+                Some(crate::script::Script::Rust(sc)) => sc.impls.as_mut(),
+                _ => None,
+            };
+            RelativeSize::visit(template, impls)?;
         }
 
         Ok(())
@@ -119,22 +165,26 @@ impl TryFrom<(&mut Context, Model)> for Widget {
 
         // [handle commons] ----------------------------------------------------------------------------------
 
-        let widget = match strategy {
+        let widget: Widget = match strategy {
             gen_analyzer::Strategy::SingleStyle => (special, style, is_entry).try_into(),
             gen_analyzer::Strategy::SingleTemplate => (special, template, is_entry).try_into(),
-            gen_analyzer::Strategy::SingleScript => (context, special, script, is_entry).try_into(),
+            gen_analyzer::Strategy::SingleScript => (&mut *context, special, script, is_entry).try_into(),
             gen_analyzer::Strategy::TemplateScript => {
-                (context, special, template, script, is_entry, polls).try_into()
+                (&mut *context, special, template, script, is_entry, polls).try_into()
             }
             gen_analyzer::Strategy::TemplateStyle => {
                 (special, template, style, is_entry).try_into()
             }
             gen_analyzer::Strategy::All => {
-                (context, special, template, script, style, is_entry, polls).try_into()
+                (&mut *context, special, template, script, style, is_entry, polls).try_into()
             }
             gen_analyzer::Strategy::None => (special, is_entry).try_into(), // means no strategy, just a empty file
             _ => panic!("can not reach here"),
         }?;
+        // [slots] 自定义组件声明的插槽, 调用方根据插槽放置子组件 ----------------------------------------------------
+        if let Some(template) = widget.template.as_ref() {
+            Slot::register(context, template);
+        }
 
         Ok(widget)
     }
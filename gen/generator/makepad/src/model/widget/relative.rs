@@ -0,0 +1,270 @@
+use gen_analyzer::{value::Value, Props};
+use gen_utils::{
+    common::{format_float, ident},
+    err_from_to,
+    error::Error,
+};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse_str;
+
+use crate::{builtin::prop::resolve_em, script::Impls};
+
+use super::WidgetTemplate;
+
+/// # Relative Size
+/// `width` and `height` which are relative to the parent: `50%`, `calc(100% - 20px)`, See [`gen_analyzer::value::Length::relative`]
+///
+/// Makepad Size has no relative value, the widget is `Fill` in live design,
+/// after the component is drawn the size is computed by the rect of the parent and the widget is redrawn if the size changes
+/// ```rust,ignore
+/// fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+///     let step = { self.deref_widget.draw_walk(cx, scope, walk) };
+///     {
+///         let size = self.widget(id!(body)).area().rect(cx).size;
+///         let widget = self.widget(id!(btn));
+///         let current = widget.area().rect(cx).size;
+///         if size.x > 0.0 {
+///             let value = (size.x * 1.0 - 20.0).max(0.0);
+///             if (current.x - value).abs() > 0.5 {
+///                 widget.apply_over(cx, live!{ width: (value) });
+///                 widget.redraw(cx);
+///             }
+///         }
+///     }
+///     step
+/// }
+/// ```
+/// the parent of the children of the root is `self.deref_widget`, others are found by id (ids are filled when parsing)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RelativeSize {
+    /// (ratio, px) of width
+    pub width: Option<(f64, f64)>,
+    /// (ratio, px) of height
+    pub height: Option<(f64, f64)>,
+}
+
+impl RelativeSize {
+    /// ## relative size from the merged props of the widget
+    /// `em` is resolved by the `font_size` of the widget, return None if neither width nor height is relative
+    /// - `is_dynamic`: as_prop, `:for` or `:if` widget, they are not in the template tree of the component
+    pub fn new(
+        props: Option<&Props>,
+        is_root: bool,
+        is_dynamic: bool,
+    ) -> Result<Option<Self>, Error> {
+        let Some(props) = props else {
+            return Ok(None);
+        };
+        let mut props = props
+            .iter()
+            .filter(|(k, _)| {
+                k.is_normal() && ["width", "height", "font_size"].contains(&k.name.as_str())
+            })
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect::<Props>();
+        resolve_em(&mut props);
+        let relative = |name: &str| {
+            props.iter().find_map(|(k, v)| match v {
+                Value::Length(length) if k.name == name => length.relative(),
+                _ => None,
+            })
+        };
+        let size = Self {
+            width: relative("width"),
+            height: relative("height"),
+        };
+        if size == Self::default() {
+            return Ok(None);
+        }
+
+        if is_root {
+            return Err(err_from_to!(
+                "GenUI Component" => "Makepad Widget, root widget can not use percentage size, it has no parent in the component!"
+            ));
+        }
+        if is_dynamic {
+            return Err(err_from_to!(
+                "GenUI Component" => "Makepad Widget, percentage size is not supported in as_prop, `:for` or `:if` widget!"
+            ));
+        }
+        Ok(Some(size))
+    }
+
+    /// ## resize the widgets with relative size after the component is drawn
+    /// return an error if the template has relative sizes but the component can not be drawn by itself (no impls)
+    pub fn visit(template: &WidgetTemplate, impls: Option<&mut Impls>) -> Result<(), Error> {
+        let mut resizes = vec![];
+        if let Some(children) = template.children.as_ref() {
+            for child in children {
+                collect_resizes(child, &quote! { self.deref_widget }, &mut resizes);
+            }
+        }
+        if resizes.is_empty() {
+            return Ok(());
+        }
+        let Some(impls) = impls else {
+            return Err(err_from_to!(
+                "GenUI Component" => "Makepad Widget, percentage size is only supported in custom component!"
+            ));
+        };
+        let draw_walk = impls.traits().widget.draw_walk.clone();
+        impls.traits().widget.draw_walk = quote! {
+            let step = { #draw_walk };
+            #(#resizes)*
+            step
+        };
+        Ok(())
+    }
+
+    fn resize(&self, id: &str, father: &TokenStream) -> TokenStream {
+        let id = ident(id);
+        let axes = [("x", "width", self.width), ("y", "height", self.height)]
+            .into_iter()
+            .filter_map(|(axis, prop, relative)| {
+                let (ratio, px) = relative?;
+                let axis = ident(axis);
+                let prop = ident(prop);
+                let ratio = parse_str::<TokenStream>(&format_float(ratio)).unwrap();
+                let px = (px != 0.0).then(|| {
+                    let sign = if px < 0.0 {
+                        quote! {-}
+                    } else {
+                        quote! {+}
+                    };
+                    let px = parse_str::<TokenStream>(&format_float(px.abs())).unwrap();
+                    quote! { #sign #px }
+                });
+                Some(quote! {
+                    if size.#axis > 0.0 {
+                        let value = (size.#axis * #ratio #px).max(0.0);
+                        if (current.#axis - value).abs() > 0.5 {
+                            widget.apply_over(cx, live!{ #prop: (value) });
+                            widget.redraw(cx);
+                        }
+                    }
+                })
+            });
+
+        quote! {
+            {
+                let size = #father.area().rect(cx).size;
+                let widget = self.widget(id!(#id));
+                let current = widget.area().rect(cx).size;
+                #(#axes)*
+            }
+        }
+    }
+}
+
+/// `father`: the parent widget in the component, its size is used by the relative size of the children
+fn collect_resizes(
+    template: &WidgetTemplate,
+    father: &TokenStream,
+    resizes: &mut Vec<TokenStream>,
+) {
+    let Some(id) = template.id.as_ref() else {
+        return;
+    };
+    if let Some(relative) = template.relative.as_ref() {
+        resizes.push(relative.resize(id, father));
+    }
+    let id = ident(id);
+    let father = quote! { self.widget(id!(#id)) };
+    for child in template.children.iter().flatten() {
+        collect_resizes(child, &father, resizes);
+    }
+}
+
+#[cfg(test)]
+mod test_relative {
+    use gen_analyzer::Model;
+    use gen_utils::compiler::ToRs;
+
+    use crate::{compiler::Context, model::Widget};
+
+    fn widget(input: &str) -> Result<String, gen_utils::error::Error> {
+        let model = input.parse::<Model>()?;
+        let mut ctx = Context::default();
+        let widget = Widget::new(&mut ctx, model)?;
+        Ok(widget.content()?.to_string().replace(' ', ""))
+    }
+
+    #[test]
+    fn resize() {
+        let input = r#"
+        <template>
+            <component name="Card">
+                <view id="body" height="100">
+                    <view id="btn"></view>
+                </view>
+                <label id="tip" text="tip"></label>
+            </component>
+        </template>
+
+        <style>
+        #btn {
+            width: calc(100% - 20px);
+            height: 30px;
+        }
+        #tip {
+            width: 50%;
+        }
+        </style>
+        "#;
+        let content = widget(input).unwrap();
+        // `Fill` in live design
+        assert!(content.contains("btn=<GView>{"));
+        assert!(content.contains("width:Fill"));
+        // resized by the parent after drawing
+        assert!(content.contains("letstep={self.deref_widget.draw_walk(cx,scope,walk)};"));
+        assert!(content.contains(
+            "letsize=self.widget(id!(body)).area().rect(cx).size;letwidget=self.widget(id!(btn));"
+        ));
+        assert!(content.contains("letvalue=(size.x*1.0-20.0).max(0.0);"));
+        assert!(content.contains(
+            "letsize=self.deref_widget.area().rect(cx).size;letwidget=self.widget(id!(tip));"
+        ));
+        assert!(content.contains("letvalue=(size.x*0.5).max(0.0);"));
+        assert!(content.contains("widget.apply_over(cx,live!{width:(value)});"));
+        assert!(!content.contains("live!{height:(value)}"));
+    }
+
+    #[test]
+    fn unsupported() {
+        let err = |input: &str| widget(input).unwrap_err().to_string();
+        let root = err(r#"
+        <template>
+            <component name="Card" id="card"></component>
+        </template>
+
+        <style>
+        #card {
+            width: 50%;
+        }
+        </style>
+        "#);
+        assert!(root.contains("root widget can not use percentage size"));
+        let for_widget = err(r#"
+        <template>
+            <component name="Card">
+                <label id="item" :for="item in list" :text="item"></label>
+            </component>
+        </template>
+
+        <script>
+        #[component]
+        pub struct Card {
+            list: Vec<String>,
+        }
+        </script>
+
+        <style>
+        #item {
+            height: calc(50% + 4px);
+        }
+        </style>
+        "#);
+        assert!(for_widget.contains("percentage size is not supported"));
+    }
+}
@@ -0,0 +1,249 @@
+use std::collections::HashSet;
+
+use gen_analyzer::{
+    style::{MediaKind, Platform, StyleCondition},
+    IdClass, Model, Props, Style, StyleVisitor, Template,
+};
+use gen_utils::{
+    common::{ident, Os},
+    error::Error,
+};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::parse_str;
+
+use crate::token::ToLiveDesign;
+
+use super::WidgetType;
+
+/// # Responsive Apply
+/// widgets which have responsive style rules (`@media`, `@platform`), See [`StyleCondition`]
+///
+/// `apply_responsive` is generated for the file, it is called at startup (size is None)
+/// and when the window resizes, See [`crate::model::create_responsive_rs`]:
+/// ```rust,ignore
+/// pub fn apply_responsive(cx: &mut Cx, root: &WidgetRef, size: Option<DVec2>) {
+///     {
+///         let widget = root.widget(id!(title));
+///         widget.apply_over(cx, live! { font_size: 16.0 });
+///         if cfg!(any(target_os = "android")) && size.is_some_and(|size| size.x <= 600f64) {
+///             widget.apply_over(cx, live! { font_size: 12.0 });
+///         }
+///     }
+///     root.redraw(cx);
+/// }
+/// ```
+/// the props changed by the rules are reset to the values out of the rules first (if they have), then the matched
+/// rules are applied from low to high specificity, props in template (inline) always win and are never changed
+#[derive(Debug, Clone, Default)]
+pub struct ResponsiveApply {
+    pub widgets: Vec<ResponsiveWidget>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ResponsiveWidget {
+    pub id: String,
+    /// live props out of the rules, used to reset the widget when the rules do not match
+    pub base: Option<TokenStream>,
+    /// (conditions, live props)
+    pub rules: Vec<(Vec<StyleCondition>, TokenStream)>,
+}
+
+impl ResponsiveApply {
+    /// return None if no widget has responsive style rules
+    pub fn new(model: &Model) -> Result<Option<Self>, Error> {
+        let mut widgets = vec![];
+        if let (Some(template), Some(style)) = (model.template.as_ref(), model.style.as_ref()) {
+            responsive_widgets(template, style, &mut vec![], &mut widgets)?;
+        }
+        Ok((!widgets.is_empty()).then_some(Self { widgets }))
+    }
+}
+
+impl ToTokens for ResponsiveApply {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let widgets = self.widgets.iter().map(|widget| {
+            let id = ident(&widget.id);
+            let base = widget.base.as_ref().map(|base| {
+                quote! {
+                    widget.apply_over(cx, live!{ #base });
+                }
+            });
+            let rules = widget.rules.iter().map(|(conditions, props)| {
+                let conditions = conditions.iter().map(condition);
+                quote! {
+                    if #(#conditions)&&* {
+                        widget.apply_over(cx, live!{ #props });
+                    }
+                }
+            });
+            quote! {
+                {
+                    let widget = root.widget(id!(#id));
+                    #base
+                    #(#rules)*
+                }
+            }
+        });
+
+        tokens.extend(quote! {
+            #[allow(unused_variables)]
+            pub fn apply_responsive(cx: &mut Cx, root: &WidgetRef, size: Option<DVec2>) {
+                #(#widgets)*
+                root.redraw(cx);
+            }
+        });
+    }
+}
+
+/// ## runtime check of the condition
+/// - `@media(max_width:600.0)` => `size.is_some_and(|size| size.x <= 600f64)`
+/// - `@platform(android|desktop)` => `cfg!(any(target_os = "android", target_os = "windows", ...))`
+fn condition(condition: &StyleCondition) -> TokenStream {
+    match condition {
+        StyleCondition::Media(features) => {
+            let features = features.iter().map(|feature| {
+                let value = feature.value;
+                match feature.kind {
+                    MediaKind::MinWidth => quote! { size.x >= #value },
+                    MediaKind::MaxWidth => quote! { size.x <= #value },
+                    MediaKind::MinHeight => quote! { size.y >= #value },
+                    MediaKind::MaxHeight => quote! { size.y <= #value },
+                }
+            });
+            quote! { size.is_some_and(|size| #(#features)&&*) }
+        }
+        StyleCondition::Platform(platforms) => {
+            let cfgs = platforms
+                .iter()
+                .flat_map(|platform| match platform {
+                    Platform::Os(os) => vec![*os],
+                    Platform::Desktop => Os::DESKTOP.to_vec(),
+                })
+                .filter_map(|os| os.cfg())
+                .map(|cfg| parse_str::<TokenStream>(&cfg).unwrap());
+            quote! { cfg!(any(#(#cfgs),*)) }
+        }
+    }
+}
+
+/// widgets which have responsive style rules, the root component and `as_prop` widgets are skipped,
+/// they can not be found by id, See [`super::ThemeApply`]
+fn responsive_widgets(
+    template: &Template,
+    style: &Style,
+    chain: &mut Vec<IdClass>,
+    widgets: &mut Vec<ResponsiveWidget>,
+) -> Result<(), Error> {
+    if let (Some(id), false, None) = (
+        template.id.as_ref(),
+        template.is_component(),
+        template.as_prop.as_ref(),
+    ) {
+        let cascade = StyleVisitor::visit(style, Some(id), template.class.as_ref(), chain)?;
+        let inline: HashSet<String> = template
+            .props
+            .as_ref()
+            .map(|props| props.keys().map(|k| k.name.to_string()).collect())
+            .unwrap_or_default();
+        let live_props = |props: Props| -> Result<Option<TokenStream>, Error> {
+            if props.is_empty() {
+                return Ok(None);
+            }
+            Ok(WidgetType::try_from((template.name.to_string(), Some(props), false))?.props())
+        };
+
+        let mut changed = HashSet::new();
+        let mut rules = vec![];
+        for (conditions, props) in cascade.conditions {
+            let props = props
+                .into_iter()
+                .filter(|(k, _)| !inline.contains(&k.name))
+                .collect::<Props>();
+            changed.extend(props.keys().map(|k| k.name.to_string()));
+            if let Some(props) = live_props(props)? {
+                rules.push((conditions, props));
+            }
+        }
+        if !rules.is_empty() {
+            let base = cascade
+                .props
+                .into_iter()
+                .filter(|(k, _)| changed.contains(&k.name))
+                .collect::<Props>();
+            widgets.push(ResponsiveWidget {
+                id: id.to_string(),
+                base: live_props(base)?,
+                rules,
+            });
+        }
+    }
+
+    if let Some(children) = template.children.as_ref() {
+        chain.push(IdClass {
+            id: template.id.clone(),
+            class: template.class.clone(),
+        });
+        for child in children {
+            responsive_widgets(child, style, chain, widgets)?;
+        }
+        chain.pop();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test_responsive {
+    use gen_analyzer::Model;
+    use quote::ToTokens;
+
+    use super::ResponsiveApply;
+
+    #[test]
+    fn apply() {
+        let input = r#"
+<template>
+    <component name="Home">
+        <label id="title" text="'Hello'"></label>
+        <label id="sub" font_size="20" text="'World'"></label>
+        <label id="plain" text="'!'"></label>
+    </component>
+</template>
+
+<style>
+#title {
+    font_size: 16;
+    @platform(android|ios) {
+        font_size: 12;
+    }
+}
+#sub {
+    @media (max_width: 600) {
+        font_size: 10;
+        color: #FF0000;
+    }
+}
+</style>
+"#;
+        let model = input.parse::<Model>().unwrap();
+        let apply = ResponsiveApply::new(&model).unwrap().unwrap();
+        assert_eq!(apply.widgets.len(), 2);
+        let title = &apply.widgets[0];
+        assert_eq!(title.id, "title");
+        assert!(title.base.is_some());
+        // inline `font_size` wins, only `color` is changed and it has no base value
+        let sub = &apply.widgets[1];
+        assert!(sub.base.is_none());
+        assert!(!sub.rules[0].1.to_string().contains("font_size"));
+
+        let tokens = apply.to_token_stream().to_string();
+        assert!(tokens.contains("apply_responsive"));
+        assert!(tokens.contains("target_os = \"android\""));
+        assert!(tokens.contains("size . x <= 600f64"));
+
+        let model = "<template><component name=\"Home\"><label id=\"a\"></label></component></template>\n<style>\n#a { font_size: 1; }\n</style>"
+            .parse::<Model>()
+            .unwrap();
+        assert!(ResponsiveApply::new(&model).unwrap().is_none());
+    }
+}
@@ -1,6 +1,6 @@
 // use gen_converter::Parent;
 use gen_analyzer::{
-    value::{For, Value}, Parent, PropKey
+    value::{For, Value}, Parent, PropKey, VirtualFor
 };
 use gen_utils::common::{IFSignal, Ulid};
 use std::collections::HashMap;
@@ -19,6 +19,10 @@ pub enum Role {
         origin_pos: usize,
         /// 涉及到的变量
         props: HashMap<String, String>,
+        /// `:key`, 子组件按key复用, 例如: `item.id`
+        key: Option<String>,
+        /// `virtual`, 只创建视口中可见的子组件
+        virtual_for: Option<VirtualFor>,
         id: String,
         name: String,
         children: Vec<Role>,
@@ -49,6 +53,25 @@ impl Role {
     pub fn is_for(&self) -> bool {
         matches!(self, Role::For { .. })
     }
+    /// is for with `:key`
+    pub fn is_keyed_for(&self) -> bool {
+        matches!(self, Role::For { key: Some(_), .. })
+    }
+    /// is for with `virtual`
+    pub fn is_virtual_list(&self) -> bool {
+        matches!(self, Role::For { virtual_for: Some(_), .. })
+    }
+    /// id of the `virtual` list, the ids of the spacers and items are derived from it
+    pub fn virtual_list_id(&self) -> Option<&str> {
+        match self {
+            Role::For {
+                virtual_for: Some(_),
+                id,
+                ..
+            } => Some(id),
+            _ => None,
+        }
+    }
     pub fn push_child(&mut self, role: Role) -> () {
         match self {
             Role::For { children, .. } => {
@@ -0,0 +1,587 @@
+use std::collections::HashMap;
+
+use gen_analyzer::{
+    value::{Bind, Value},
+    Binds, PropKey, SugarProps, Template,
+};
+use gen_utils::{err_from_to, error::Error};
+use proc_macro2::{TokenStream, TokenTree};
+use quote::quote;
+use syn::parse_str;
+
+use crate::compiler::{Context, WidgetPoll};
+
+use super::{AbsWidget, WidgetTemplate, WidgetType};
+
+/// name of the default slot, children of custom component without `slot` are put into it
+pub const DEFAULT_SLOT: &str = "default";
+
+/// # Slot Scope
+/// data of scoped slot, the custom component sets the data and the caller uses it in the binds of the children
+#[derive(Debug, Clone, PartialEq)]
+pub enum SlotScope {
+    /// placeholder in custom component, (prop of the scope, field of the component) sorted by prop,
+    /// `<slot name="header" :title="title" />` => `[("title", "title")]`, empty if the slot is not scoped
+    Declare(Vec<(String, String)>),
+    /// name of the scope in caller: `<label slot="header" slot_scope="scope" :text="scope.title" />` => `scope`
+    Use(String),
+}
+
+/// # Slot
+/// named and default slots of custom component
+/// - in custom component: `<slot name="header" height="Fit" />` is a placeholder, it is converted to a `<view>` whose id is
+///   the name of the slot, `<slot />` is the default slot, See [`Slot::placeholder`]
+/// - in caller: `<my_card><label slot="header" /><label /></my_card>`, the children patch the placeholders
+///   if the custom component declares slots, See [`Slot::resolve`] and [`Slot::live_children`]
+///
+/// ```rust,ignore
+/// <MyCard>{
+///     header = { <GLabel>{} }
+///     default = { <GLabel>{} }
+/// }
+/// ```
+/// ## Scoped Slot
+/// bind props of `<slot>` are the data of the slot: `<slot name="header" :title="title" />`,
+/// the caller names the data by `slot_scope` and uses it in binds: `<label slot="header" slot_scope="scope" :text="scope.title" />`
+/// - custom component: `MyCardHeaderScope` struct, `on_slot_header` to register the callback,
+///   the callback is called when the fields of the scope change, See [`crate::visitor::SlotLzVisitor`]
+/// - caller: registers the callback in `after_apply_from_doc`, the callback sets the props of the children in the slot
+///
+/// slots are static in live design, so fallback content is not supported
+pub struct Slot;
+
+impl Slot {
+    /// ## convert `<slot>` to the placeholder view
+    /// other templates are returned without change, the scope is [`SlotScope::Use`] if `slot_scope` is set
+    pub fn placeholder(mut template: Template) -> Result<(Template, Option<SlotScope>), Error> {
+        if !template.is_slot() {
+            if let Some(scope) = template.slot_scope.as_ref() {
+                if !is_live_id(scope) {
+                    return Err(err_from_to!(
+                        format!("slot_scope=\"{}\"", scope) => "Makepad Widget, `slot_scope` should be a snake case ident, such as `scope`"
+                    ));
+                }
+            }
+            let scope = template.slot_scope.clone().map(SlotScope::Use);
+            return Ok((template, scope));
+        }
+        let err = |msg: &str| -> Error {
+            err_from_to!("GenUI Component" => format!("Makepad Widget, <slot> {}", msg))
+        };
+        if template.root {
+            return Err(err("can not be the root of template"));
+        }
+        if template.children.is_some() {
+            return Err(err(
+                "can not have children, fallback content is not supported",
+            ));
+        }
+        // id is generated by special if it is not set
+        let has_id = template
+            .id
+            .as_ref()
+            .is_some_and(|id| *id != template.special.to_snake());
+        if has_id
+            || template.as_prop.is_some()
+            || template.slot.is_some()
+            || template.slot_scope.is_some()
+            || template.callbacks.is_some()
+            || template.inherits.is_some()
+            || !matches!(template.sugar_props, SugarProps::None)
+        {
+            return Err(err(
+                "only accepts `name`, the props of view and the binds of scope, the name is the id of the placeholder",
+            ));
+        }
+
+        let name = template
+            .props
+            .as_mut()
+            .and_then(|props| props.remove(&PropKey::new_tag_normal("name")))
+            .map_or_else(|| DEFAULT_SLOT.to_string(), |name| name.to_string());
+        if !is_live_id(&name) {
+            return Err(err(&format!(
+                "name `{}` should be a snake case ident, such as `header`",
+                name
+            )));
+        }
+        // [scope] 绑定的字段是插槽的数据, 不是view的属性 ------------------------------------------------------
+        let mut scope = vec![];
+        for (key, value) in template.binds.take().unwrap_or_default() {
+            match value {
+                Value::Bind(Bind::Normal(idents)) if idents.len() == 1 => {
+                    scope.push((key.name, idents[0].name.to_string()));
+                }
+                _ => {
+                    return Err(err(&format!(
+                        "`:{}` of scoped slot should bind a field, such as `:{}=\"{}\"`",
+                        key.name, key.name, key.name
+                    )))
+                }
+            }
+        }
+        scope.sort();
+        template.id.replace(name);
+        template.name = "view".to_string();
+        Ok((template, Some(SlotScope::Declare(scope))))
+    }
+
+    /// ## check `slot` and `slot_scope` of the widget
+    /// - `slot` and `slot_scope` can not be used with `as_prop`, `:for` and `:if`
+    /// - children with `slot` or `slot_scope` should be in a custom component (not the root)
+    pub fn check(
+        slot: Option<&String>,
+        slot_scope: Option<&SlotScope>,
+        unsupported: bool,
+        ty: &WidgetType,
+        is_root: bool,
+        children: Option<&Vec<WidgetTemplate>>,
+    ) -> Result<(), Error> {
+        if unsupported {
+            let attr = match (slot, slot_scope) {
+                (Some(slot), _) => Some(format!("slot=\"{}\"", slot)),
+                (None, Some(SlotScope::Use(scope))) => Some(format!("slot_scope=\"{}\"", scope)),
+                _ => None,
+            };
+            if let Some(attr) = attr {
+                return Err(err_from_to!(
+                    attr => "Makepad Widget, `slot` and `slot_scope` are not supported in as_prop, `:for` or `:if` widget!"
+                ));
+            }
+        }
+        let is_caller = ty.is_define() && !is_root;
+        if let (false, Some(child)) = (
+            is_caller,
+            children.and_then(|children| children.iter().find(|child| child.is_slotted())),
+        ) {
+            let attr = match (child.slot.as_ref(), child.slot_scope.as_ref()) {
+                (Some(slot), _) => format!("slot=\"{}\"", slot),
+                (_, Some(SlotScope::Use(scope))) => format!("slot_scope=\"{}\"", scope),
+                _ => unreachable!("slotted child has slot or slot_scope"),
+            };
+            return Err(err_from_to!(
+                attr => "Makepad Widget, `slot` and `slot_scope` can only be used in the children of custom component"
+            ));
+        }
+        Ok(())
+    }
+
+    /// ## check scoped slot in static widget (no `<script>`)
+    /// the data of scoped slot is set by `#[component]` struct and used by binds, static widget has neither of them
+    pub fn check_static(slot_scope: Option<&SlotScope>) -> Result<(), Error> {
+        let attr = match slot_scope {
+            Some(SlotScope::Declare(scope)) if !scope.is_empty() => scope
+                .iter()
+                .map(|(prop, field)| format!(":{}=\"{}\"", prop, field))
+                .collect::<Vec<_>>()
+                .join(" "),
+            Some(SlotScope::Use(scope)) => format!("slot_scope=\"{}\"", scope),
+            _ => return Ok(()),
+        };
+        Err(err_from_to!(
+            attr => "Makepad Widget, Static Widget has no scoped slot, add `<script>` with `#[component]` struct"
+        ))
+    }
+
+    /// ## children of custom component grouped by slot
+    /// the order of slots follows the first child in the slot
+    pub fn live_children(children: &[WidgetTemplate]) -> TokenStream {
+        let mut slots: Vec<(&str, TokenStream)> = vec![];
+        for child in children {
+            let slot = child.slot.as_deref().unwrap_or(DEFAULT_SLOT);
+            let index = match slots.iter().position(|(name, _)| *name == slot) {
+                Some(index) => index,
+                None => {
+                    slots.push((slot, TokenStream::new()));
+                    slots.len() - 1
+                }
+            };
+            slots[index].1.extend(child.to_token_stream(None));
+        }
+
+        slots
+            .into_iter()
+            .fold(TokenStream::new(), |mut tk, (slot, children)| {
+                let slot = parse_str::<TokenStream>(slot).unwrap();
+                tk.extend(quote! {
+                    #slot = { #children }
+                });
+                tk
+            })
+    }
+
+    /// ## put the children of custom components into slots or not
+    /// - the custom component is compiled (in `poll`): only when it declares slots, See [`Slot::register`]
+    /// - otherwise: only when a child has `slot` or `slot_scope`
+    ///
+    /// children of a custom component without slots are added to the component directly
+    pub fn resolve(template: &mut WidgetTemplate, poll: &WidgetPoll) {
+        if let (WidgetType::Define(define_widget), false) = (&mut template.ty, template.is_root) {
+            define_widget.slots = match poll.get(&define_widget.snake_name()) {
+                Some(AbsWidget::Define { slots, .. }) => {
+                    slots.as_ref().is_some_and(|slots| !slots.is_empty())
+                }
+                _ => template
+                    .children
+                    .as_ref()
+                    .is_some_and(|children| children.iter().any(|child| child.is_slotted())),
+            };
+        }
+        if let Some(children) = template.children.as_mut() {
+            for child in children {
+                Self::resolve(child, poll);
+            }
+        }
+    }
+
+    /// ## slots declared in the template of custom component
+    /// (name of the slot, scope of the slot), sorted by name, See [`SlotScope::Declare`]
+    pub fn declared(template: &WidgetTemplate) -> Vec<(String, Vec<(String, String)>)> {
+        fn collect(template: &WidgetTemplate, slots: &mut Vec<(String, Vec<(String, String)>)>) {
+            if let (Some(SlotScope::Declare(scope)), Some(id)) =
+                (template.slot_scope.as_ref(), template.id.as_ref())
+            {
+                slots.push((id.to_string(), scope.clone()));
+            }
+            for child in template.children.iter().flatten() {
+                collect(child, slots);
+            }
+        }
+
+        let mut slots = vec![];
+        collect(template, &mut slots);
+        slots.sort();
+        slots
+    }
+
+    /// ## push the slots of custom component into `define_widget_poll`
+    /// callers use them to put children into slots and check `slot`, See [`Slot::resolve`]
+    pub fn register(context: &mut Context, template: &WidgetTemplate) {
+        let declared = Self::declared(template)
+            .into_iter()
+            .map(|(slot, scope)| (slot, scope.into_iter().map(|(prop, _)| prop).collect()))
+            .collect::<HashMap<_, _>>();
+        if declared.is_empty() {
+            return;
+        }
+        let Some((snake_name, name)) = template
+            .is_define_root_and(|define_widget| (define_widget.snake_name(), define_widget.name.to_string()))
+        else {
+            return;
+        };
+        match context.define_widget_poll.get_mut(&snake_name) {
+            Some(AbsWidget::Define { slots, .. }) => {
+                slots.replace(declared);
+            }
+            _ => context.push_widget(
+                snake_name,
+                AbsWidget::Define {
+                    name,
+                    props: None,
+                    events: None,
+                    slots: Some(declared),
+                },
+            ),
+        }
+    }
+
+    /// ## binds without the binds of `<slot>`
+    /// binds of `<slot>` are the data of scoped slot instead of props of widget, See [`SlotScope::Declare`]
+    pub fn widget_binds(binds: &Binds) -> Binds {
+        binds
+            .iter()
+            .map(|(field, widgets)| {
+                let widgets = widgets
+                    .iter()
+                    .filter(|widget| widget.name != "slot")
+                    .cloned()
+                    .collect::<Vec<_>>();
+                (field.to_string(), widgets)
+            })
+            .filter(|(_, widgets)| !widgets.is_empty())
+            .collect()
+    }
+
+    /// ## scoped slots used by the caller
+    /// children with `slot_scope` of custom components in the template, See [`SlotUse`]
+    pub fn uses(template: &WidgetTemplate) -> Vec<SlotUse> {
+        fn collect(template: &WidgetTemplate, uses: &mut Vec<SlotUse>) {
+            if let (WidgetType::Define(define_widget), false, Some(id)) =
+                (&template.ty, template.is_root, template.id.as_ref())
+            {
+                for child in template.children.iter().flatten() {
+                    if let Some(SlotScope::Use(scope)) = child.slot_scope.as_ref() {
+                        let mut binds = vec![];
+                        scope_binds(child, scope, &mut binds);
+                        uses.push(SlotUse {
+                            id: id.to_string(),
+                            widget: define_widget.snake_name(),
+                            slot: child.slot.as_deref().unwrap_or(DEFAULT_SLOT).to_string(),
+                            scope: scope.to_string(),
+                            binds,
+                        });
+                    }
+                }
+            }
+            for child in template.children.iter().flatten() {
+                collect(child, uses);
+            }
+        }
+
+        let mut uses = vec![];
+        collect(template, &mut uses);
+        uses
+    }
+}
+
+/// # Scoped Slot in Caller
+/// `<my_card id="card"><label id="lb" slot="header" slot_scope="scope" :text="scope.title" /></my_card>`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotUse {
+    /// id of the custom component: `card`
+    pub id: String,
+    /// snake name of the custom component: `my_card`
+    pub widget: String,
+    /// name of the slot: `header`
+    pub slot: String,
+    /// name of the scope: `scope`
+    pub scope: String,
+    /// binds which use the scope, (id, snake name of widget, prop, value): `("lb", "glabel", "text", "scope.title")`
+    pub binds: Vec<(String, String, String, String)>,
+}
+
+impl WidgetTemplate {
+    /// the widget has `slot` or `slot_scope`, it should be in a custom component
+    pub fn is_slotted(&self) -> bool {
+        self.slot.is_some() || matches!(self.slot_scope, Some(SlotScope::Use(_)))
+    }
+}
+
+/// binds of the widget and its children which use the scope
+fn scope_binds(
+    template: &WidgetTemplate,
+    scope: &str,
+    binds: &mut Vec<(String, String, String, String)>,
+) {
+    if let (Some(widget_binds), Some(id)) = (template.binds.as_ref(), template.id.as_ref()) {
+        let mut widget_binds = widget_binds
+            .iter()
+            .filter(|(value, _)| uses_scope(value, scope))
+            .collect::<Vec<_>>();
+        widget_binds.sort_by_key(|(_, prop)| *prop);
+        for (value, prop) in widget_binds {
+            binds.push((
+                id.to_string(),
+                template.ty.snake_name(),
+                prop.to_string(),
+                value.to_string(),
+            ));
+        }
+    }
+    for child in template.children.iter().flatten() {
+        scope_binds(child, scope, binds);
+    }
+}
+
+/// the value (ident, expression or `format!`) uses the scope or not, `scope.title` => true, `a.scope` => false
+fn uses_scope(value: &str, scope: &str) -> bool {
+    fn walk(tokens: TokenStream, scope: &str) -> bool {
+        let mut after_dot = false;
+        for token in tokens {
+            let used = match &token {
+                TokenTree::Ident(ident) => !after_dot && ident == scope,
+                TokenTree::Group(group) => walk(group.stream(), scope),
+                _ => false,
+            };
+            if used {
+                return true;
+            }
+            after_dot = matches!(&token, TokenTree::Punct(punct) if punct.as_char() == '.');
+        }
+        false
+    }
+
+    parse_str::<TokenStream>(value).is_ok_and(|tokens| walk(tokens, scope))
+}
+
+/// id in live design: `[a-zA-Z_][a-zA-Z0-9_]*`
+fn is_live_id(name: &str) -> bool {
+    name.chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod test_slot {
+    use gen_analyzer::Model;
+    use gen_utils::compiler::ToRs;
+
+    use crate::{
+        compiler::Context,
+        model::{AbsWidget, Widget},
+    };
+
+    use super::{uses_scope, Slot, SlotScope, DEFAULT_SLOT};
+
+    /// generated rust code of the component without whitespace, children are put into slots by the poll of `context`
+    fn compile(context: &mut Context, input: &str) -> String {
+        let model = input.parse::<Model>().unwrap();
+        let mut widget = Widget::new(context, model).unwrap();
+        widget.resolve_slots(&context.define_widget_poll);
+        widget.content().unwrap().to_string().replace(' ', "")
+    }
+
+    #[test]
+    fn placeholder() {
+        let input = r#"
+<template>
+    <component name="MyCard">
+        <slot name="header" height="Fit"></slot>
+        <slot></slot>
+        <label id="lb" text="'Hi'"></label>
+        <slot name="footer" :title="title" :count="num"></slot>
+    </component>
+</template>
+"#;
+        let model = input.parse::<Model>().unwrap();
+        let children = model.template.unwrap().children.unwrap();
+        let (header, scope) = Slot::placeholder(children[0].clone()).unwrap();
+        assert_eq!(header.name, "view");
+        assert_eq!(header.id.as_deref(), Some("header"));
+        assert_eq!(scope, Some(SlotScope::Declare(vec![])));
+        let props = header.props.unwrap();
+        assert_eq!(props.len(), 1);
+        assert!(props.keys().all(|k| k.name == "height"));
+        let (default, _) = Slot::placeholder(children[1].clone()).unwrap();
+        assert_eq!(default.id.as_deref(), Some(DEFAULT_SLOT));
+        // not a slot
+        let (label, scope) = Slot::placeholder(children[2].clone()).unwrap();
+        assert_eq!(label.name, "label");
+        assert!(scope.is_none());
+        // scoped slot
+        let (footer, scope) = Slot::placeholder(children[3].clone()).unwrap();
+        assert!(footer.binds.is_none());
+        assert_eq!(
+            scope,
+            Some(SlotScope::Declare(vec![
+                ("count".to_string(), "num".to_string()),
+                ("title".to_string(), "title".to_string()),
+            ]))
+        );
+
+        for slot in [
+            r#"<slot :title="get_title()"></slot>"#,
+            r#"<slot :title="a.b"></slot>"#,
+            r#"<slot name="9lives"></slot>"#,
+            r#"<slot><label></label></slot>"#,
+            r#"<slot id="a"></slot>"#,
+            r#"<slot slot_scope="a"></slot>"#,
+        ] {
+            let input = format!(
+                "<template><component name=\"MyCard\">{}</component></template>",
+                slot
+            );
+            let model = input.parse::<Model>().unwrap();
+            let slot = model.template.unwrap().children.unwrap().remove(0);
+            assert!(Slot::placeholder(slot).is_err());
+        }
+    }
+
+    #[test]
+    fn scope() {
+        assert!(uses_scope("scope.title", "scope"));
+        assert!(uses_scope("scope", "scope"));
+        assert!(uses_scope("format!(\"{}-{}\", name, scope.title)", "scope"));
+        assert!(uses_scope("! scope.open", "scope"));
+        assert!(!uses_scope("item.scope", "scope"));
+        assert!(!uses_scope("scopes.title", "scope"));
+    }
+
+    #[test]
+    fn scoped() {
+        let callee = r#"
+<template>
+    <component name="MyCard">
+        <slot name="header" :title="title"></slot>
+        <slot></slot>
+    </component>
+</template>
+
+<script>
+#[component]
+pub struct MyCard {
+    title: String,
+}
+</script>
+"#;
+        let mut context = Context::default();
+        let code = compile(&mut context, callee);
+        assert!(code.contains("pubMyCard={{MyCard}}{header=<GView>{}default=<GView>{}}"));
+        assert!(code.contains("pubstructMyCardHeaderScope{pubtitle:String,}"));
+        assert!(code.contains("#[rust]slot_header:Option<Box<dynFn(&mutCx,&WidgetRef,&MyCardHeaderScope)"));
+        // the slot is notified after the field is set
+        assert!(code.contains("self.title=value.clone();self.notify_slot_header(cx)?;Ok(())"));
+        assert!(code.contains("letscope=MyCardHeaderScope{title:self.title.clone(),};f(cx,&self.widget(id!(header)),&scope)?;"));
+        assert!(code.contains("pubfnon_slot_header<F>(&self,cx:&mutCx,f:F)"));
+        let Some(AbsWidget::Define { slots, .. }) = context.define_widget_poll.get("my_card") else {
+            panic!("MyCard should be pushed into the poll");
+        };
+        let slots = slots.as_ref().unwrap();
+        assert_eq!(slots.get("header"), Some(&vec!["title".to_string()]));
+        assert_eq!(slots.get(DEFAULT_SLOT), Some(&vec![]));
+
+        let caller = r#"
+<template>
+    <component name="Hello">
+        <my_card id="card">
+            <label id="lb" slot="header" slot_scope="scope" :text="scope.title"></label>
+            <label id="lb2" text="'body'"></label>
+        </my_card>
+        <my_list id="list">
+            <label id="lb3" text="'item'"></label>
+        </my_list>
+    </component>
+</template>
+
+<script>
+#[component]
+pub struct Hello {
+    name: String,
+}
+</script>
+"#;
+        context.define_widget_poll.insert(
+            "my_list".to_string(),
+            AbsWidget::Define {
+                name: "MyList".to_string(),
+                props: None,
+                events: None,
+                slots: None,
+            },
+        );
+        let code = compile(&mut context, caller);
+        // children of the component without slots are not grouped
+        assert!(code.contains(
+            "card=<MyCard>{header={lb=<GLabel>{}}default={lb2=<GLabel>{text:\"body\",}}}list=<MyList>{lb3=<GLabel>{text:\"item\",}}"
+        ));
+        assert!(code.contains(
+            "let_=self.my_card(id!(card)).on_slot_header(cx,|cx,slot,scope|{slot.glabel(id!(lb)).set_text(cx,(scope.title).clone())?;Ok(())});"
+        ));
+        // `scope.title` is not a field
+        assert!(!code.contains("set_scope"));
+
+        // static widget has no scoped slot
+        let input = r#"
+<template>
+    <component name="Hello">
+        <my_card id="card">
+            <label id="lb" slot="header" slot_scope="scope" :text="scope.title"></label>
+        </my_card>
+    </component>
+</template>
+"#;
+        let model = input.parse::<Model>().unwrap();
+        assert!(Widget::new(&mut context, model).is_err());
+    }
+}
@@ -0,0 +1,268 @@
+use std::{collections::HashMap, str::FromStr};
+
+use gen_analyzer::{
+    value::{Bind, Value},
+    Prop, PropComponent, PropKV, Props,
+};
+use gen_utils::{common::ident, err_from_to, error::Error};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Fields;
+
+use crate::{builtin::BuiltinWidgetType, traits::MakepadExtComponent};
+
+use super::class::bind_value;
+
+/// key of `:style` bind
+pub const STYLE: &str = "style";
+
+/// fields of the `#[prop]` structs in script, key: struct ident, value: field names
+pub type PropFields = HashMap<String, Vec<String>>;
+
+/// # Style Bind
+/// props of the widget which are set by `:style` bind, the setters of the props are batched in the setter of the
+/// bind fields and the widget is redrawn once, See [`crate::two_way_binding::GetSet`]
+/// - `:style="{ color: text_color }"`: style map, the prop is set by the field
+/// - `:style="label_style"`: field of a `#[prop]` struct, each field of the struct is a prop of the widget
+///
+/// ```rust,ignore
+/// fn set_text_color(&mut self, cx: &mut Cx, value: String) -> Result<(), Box<dyn std::error::Error>> {
+///     let widget = self.glabel(id!(lb));
+///     widget.set_color(cx, value.clone())?;
+///     widget.redraw(cx);
+///     // ...
+/// }
+/// ```
+/// only the names of the props are checked when compiling: a prop of a builtin widget must be in
+/// [`BuiltinWidgetType::prop_keys`], otherwise it is rejected. The values are fields and only known at runtime,
+/// so they are not converted by the prop converters of the widget but passed to the `set_xxx` functions directly,
+/// the types are checked by rustc (and by the bind checker, See [`crate::checker::Checker::check_binds`])
+#[derive(Debug, Clone)]
+pub struct StyleBind<'a> {
+    pub component: &'a PropComponent,
+    pub bind: &'a Bind,
+}
+
+impl<'a> TryFrom<&'a PropComponent> for StyleBind<'a> {
+    type Error = Error;
+
+    fn try_from(component: &'a PropComponent) -> Result<Self, Self::Error> {
+        match &component.prop {
+            Prop::Value(PropKV {
+                value: Value::Bind(bind @ (Bind::Style(_) | Bind::Normal(_))),
+                ..
+            }) => Ok(Self { component, bind }),
+            prop => Err(err_from_to!(
+                prop.to_string() => "Makepad Style, `:style` should bind a field or a style map"
+            )),
+        }
+    }
+}
+
+impl StyleBind<'_> {
+    /// ## batched setters of the props in the setter of `field`
+    /// - `ty`: type of the field, for field bind it should be a `#[prop]` struct in `prop_fields`
+    pub fn to_setter(
+        &self,
+        field: &str,
+        ty: &str,
+        prop_fields: &PropFields,
+    ) -> Result<TokenStream, Error> {
+        let props = match self.bind {
+            Bind::Style(items) => items
+                .iter()
+                .filter(|item| item.ident[0].name == field)
+                .map(|item| (item.prop.as_str(), bind_value(&item.ident, field)))
+                .collect::<Vec<_>>(),
+            Bind::Normal(idents) if idents.len() == 1 => {
+                let ty = ty.rsplit("::").next().unwrap_or(ty).trim();
+                let fields = prop_fields.get(ty).ok_or_else(|| {
+                    err_from_to!(
+                        format!("{}: {}", field, ty) => "Makepad Style, field of `:style` should be a `#[prop]` struct with named fields"
+                    )
+                })?;
+                fields
+                    .iter()
+                    .map(|f| (f.as_str(), syn::parse_str(&format!("value.{}", f)).unwrap()))
+                    .collect()
+            }
+            _ => {
+                return Err(err_from_to!(
+                    self.bind.to_string() => "Makepad Style, `:style` should bind a field directly, such as `:style=\"label_style\"`"
+                ))
+            }
+        };
+        self.check(props.iter().map(|(prop, _)| *prop))?;
+
+        if props.is_empty() {
+            return Ok(TokenStream::new());
+        }
+        let widget = ident(&self.component.name());
+        let id = ident(&self.component.id);
+        let setters = props.iter().map(|(prop, value)| {
+            let set_prop_fn = ident(&format!("set_{}", prop));
+            quote! {
+                widget.#set_prop_fn(cx, #value.clone())?;
+            }
+        });
+
+        Ok(quote! {
+            let widget = self.#widget(id!(#id));
+            #(#setters)*
+            widget.redraw(cx);
+        })
+    }
+
+    /// names of props must be known by the builtin widget, props of define widgets are not checked,
+    /// values are not checked here (See [`StyleBind`])
+    fn check<'p, P>(&self, props: P) -> Result<(), Error>
+    where
+        P: Iterator<Item = &'p str>,
+    {
+        let PropComponent { name, id, .. } = self.component;
+        let Ok(widget) = BuiltinWidgetType::from_str(name) else {
+            return Ok(());
+        };
+        let keys = widget.prop_keys();
+        for prop in props {
+            if !keys.contains(&prop) {
+                return Err(err_from_to!(
+                    format!("<{} id=\"{}\" :style>", name, id) => format!("Makepad Style, `{}` is not a prop of <{}>", prop, name)
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `:style` is set by the setter of the fields, so it is not supported in the widget which is not a real widget
+pub fn check_style_bind(binds: Option<&Props>, unsupported: bool) -> Result<(), Error> {
+    if unsupported && binds.is_some_and(|binds| binds.keys().any(|k| k.name == STYLE)) {
+        return Err(err_from_to!(
+            "GenUI Component" => "Makepad Widget, `:style` bind is not supported in as_prop, `:for` or `:if` widget!"
+        ));
+    }
+    Ok(())
+}
+
+/// collect the named fields of `#[prop]` structs, See [`PropFields`]
+pub fn prop_fields<'p, I>(items: I) -> PropFields
+where
+    I: Iterator<Item = &'p syn::ItemStruct>,
+{
+    items
+        .filter_map(|item| match &item.fields {
+            Fields::Named(fields) => Some((
+                item.ident.to_string(),
+                fields
+                    .named
+                    .iter()
+                    .filter_map(|f| f.ident.as_ref().map(|ident| ident.to_string()))
+                    .collect(),
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test_style_bind {
+    use gen_analyzer::{
+        value::{Bind, Value},
+        Model, Prop, PropComponent, PropKV,
+    };
+    use gen_utils::compiler::ToRs;
+    use syn::ItemStruct;
+
+    use crate::{compiler::Context, model::Widget};
+
+    use super::{prop_fields, PropFields, StyleBind, STYLE};
+
+    fn component(bind: &str) -> PropComponent {
+        let bind = bind.parse::<Bind>().unwrap().into_style().unwrap();
+        PropComponent {
+            id: "v".to_string(),
+            name: "view".to_string(),
+            prop: Prop::Value(PropKV::new(STYLE.to_string(), Value::Bind(bind))),
+            as_prop: None,
+            father_ref: None,
+        }
+    }
+
+    #[test]
+    fn setter() {
+        let component = component("{ background_color: bg, height: size.h, width: size.w }");
+        let setter = StyleBind::try_from(&component)
+            .unwrap()
+            .to_setter("size", "Size", &PropFields::new())
+            .unwrap()
+            .to_string();
+        // only the props of the field are set, then the widget is redrawn once
+        assert!(setter.contains("widget . set_height (cx , value . h . clone ()) ?"));
+        assert!(setter.contains("widget . set_width (cx , value . w . clone ()) ?"));
+        assert!(!setter.contains("set_background_color"));
+        assert_eq!(setter.matches("redraw").count(), 1);
+
+        // unknown prop of builtin widget
+        let component = self::component("{ colour: bg }");
+        let style_bind = StyleBind::try_from(&component).unwrap();
+        assert!(style_bind
+            .to_setter("bg", "String", &PropFields::new())
+            .is_err());
+    }
+
+    #[test]
+    fn field() {
+        let item: ItemStruct = syn::parse_quote! {
+            pub struct ViewStyle {
+                height: Size,
+                background_color: String,
+            }
+        };
+        let fields = prop_fields(std::iter::once(&item));
+        let component = component("view_style");
+        let style_bind = StyleBind::try_from(&component).unwrap();
+        let setter = style_bind
+            .to_setter("view_style", "ViewStyle", &fields)
+            .unwrap()
+            .to_string();
+        assert!(setter.contains("set_height (cx , value . height . clone ())"));
+        assert!(setter.contains("set_background_color (cx , value . background_color . clone ())"));
+        // field which is not a `#[prop]` struct
+        assert!(style_bind
+            .to_setter("view_style", "String", &fields)
+            .is_err());
+    }
+
+    #[test]
+    fn nested_field() {
+        let input = r#"
+<template>
+    <component name="Home">
+        <label id="lb" :style="{ font_size: size.font }"></label>
+    </component>
+</template>
+
+<script>
+#[prop]
+#[derive(Debug, Clone)]
+pub struct TextSize {
+    font: f64,
+}
+
+#[component]
+pub struct Home {
+    size: TextSize,
+}
+</script>
+"#;
+        let model = input.parse::<Model>().unwrap();
+        let mut context = Context::default();
+        let widget = Widget::new(&mut context, model).unwrap();
+        let code = widget.content().unwrap().to_string().replace(' ', "");
+        // the setter of the root field sets the style
+        let setter = &code[code.find("fnset_size").unwrap()..];
+        let setter = &setter[..setter.find("fnget_size").unwrap_or(setter.len())];
+        assert!(setter.contains("widget.set_font_size(cx,value.font.clone())?;"));
+    }
+}
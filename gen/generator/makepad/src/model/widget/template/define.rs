@@ -18,6 +18,8 @@ pub struct DefineWidget {
     pub prop: Option<HashMap<String, String>>,
     // pub traits: Option<WidgetTraits>,
     pub role: Role,
+    /// children of the custom component (not root) are put into slots or not, See [`crate::model::Slot::resolve`]
+    pub slots: bool,
 }
 
 impl DefineWidget {
@@ -124,6 +126,7 @@ impl TryFrom<(String, Option<Props>, bool)> for DefineWidget {
             prop,
             // traits: todo!(),
             role: Role::Normal,
+            slots: false,
         })
     }
 }
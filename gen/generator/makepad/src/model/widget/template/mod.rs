@@ -4,15 +4,15 @@ mod ty;
 use crate::{
     builtin::{
         widget::{Root, RootConf, Window},
-        BuiltinWidget,
+        Animator, BuiltinWidget,
     },
     traits::ToTokensExt,
-    visitor::ptr_ident,
+    visitor::{ptr_ident, spacer_ident},
 };
 pub use define::*;
 use std::collections::HashMap;
 
-use super::role::Role;
+use super::{role::Role, ClassApply, RelativeSize, Slot, SlotScope};
 use crate::token::ToLiveDesign;
 use gen_utils::common::{punct_alone, snake_to_camel};
 use gen_utils::error::Error;
@@ -29,11 +29,21 @@ pub struct WidgetTemplate {
     pub id: Option<String>,
     pub is_root: bool,
     pub as_prop: Option<String>,
+    /// slot of the custom component which the widget is put into, See [`Slot`]
+    pub slot: Option<String>,
+    /// scoped slot: data declared by `<slot>` or the name of the data in caller, See [`SlotScope`]
+    pub slot_scope: Option<SlotScope>,
     pub is_static: bool,
     pub ty: WidgetType,
     pub children: Option<Vec<WidgetTemplate>>,
     pub role: Role,
     pub binds: Option<HashMap<String, String>>,
+    /// `animator` from `transition` and `animation` in style, See [`Animator`]
+    pub animator: Option<Animator>,
+    /// classes of `:class` bind, See [`ClassApply`]
+    pub class_apply: Option<ClassApply>,
+    /// width and height relative to the parent, See [`RelativeSize`]
+    pub relative: Option<RelativeSize>,
 }
 
 impl WidgetTemplate {
@@ -43,6 +53,18 @@ impl WidgetTemplate {
     pub fn root_name(&self) -> TokenStream {
         self.ty.root_name()
     }
+    /// find the classes of `:class` bind by widget id in the template tree
+    pub fn class_apply(&self, id: &str) -> Option<&ClassApply> {
+        if self.id.as_deref() == Some(id) {
+            if let Some(class_apply) = self.class_apply.as_ref() {
+                return Some(class_apply);
+            }
+        }
+        self.children
+            .as_ref()?
+            .iter()
+            .find_map(|child| child.class_apply(id))
+    }
     pub fn is_define_root_and<F, R>(&self, f: F) -> Option<R>
     where
         F: FnOnce(&DefineWidget) -> R,
@@ -75,7 +97,18 @@ impl WidgetTemplate {
                     let ptr_ident = ptr_ident(index);
                     let widget_name = item.ty.name();
                     let widget_props = item.ty.props();
+                    let animator = item.animator.as_ref();
+                    // `virtual` list replaces the items out of the viewport by the spacers
+                    let spacer = item.role.virtual_list_id().map(|id| {
+                        let spacer_ident = spacer_ident(id);
+                        quote! {
+                            #spacer_ident: <View>{ width: Fill, height: 0 },
+                        }
+                    });
                     let children = item.children.as_ref().map(|children| {
+                        if item.ty.has_slots() {
+                            return Slot::live_children(children);
+                        }
                         children.iter().fold(TokenStream::new(), |mut tk, child| {
                             tk.extend(child.to_token_stream(None).unwrap());
                             tk
@@ -85,11 +118,13 @@ impl WidgetTemplate {
                     let item = quote! {
                         <#widget_name>{
                             #widget_props
+                            #animator
                             #children
                         },
                     };
                     tk.extend(quote! {
                        #ptr_ident: #item
+                       #spacer
                     });
 
                     tk
@@ -158,8 +193,13 @@ impl WidgetTemplate {
 
         // [widget props] -------------------------------------------------------------------------------------
         let widget_props = widget.props();
+        let animator = self.animator.as_ref();
         // [children] -----------------------------------------------------------------------------------------
+        // 自定义组件(非root)声明了插槽时, 子组件放入插槽中
         let children = children.map(|children| {
+            if widget.has_slots() && !is_root {
+                return Slot::live_children(children);
+            }
             children.iter().fold(TokenStream::new(), |mut tk, child| {
                 // here child widget must be static and use live_node_static!
                 // tk.extend(
@@ -177,6 +217,7 @@ impl WidgetTemplate {
             #id #sig #widget_name{
                 #widget_ptrs
                 #widget_props
+                #animator
                 #children
             }
         }
@@ -203,33 +244,49 @@ impl From<(String, &RootConf)> for WidgetTemplate {
             name: root_name,
             prop: None,
             role: Role::Normal,
+            slots: false,
         });
 
         Self {
             id: None,
             is_root: true,
             as_prop: None,
+            slot: None,
+            slot_scope: None,
             is_static: true,
             ty: root_ty,
             role: Role::default(),
             binds: None,
+            animator: None,
+            class_apply: None,
+            relative: None,
             children: Some(vec![WidgetTemplate {
                 id: Some("main_window".to_string()),
                 is_root: false,
                 as_prop: None,
+                slot: None,
+                slot_scope: None,
                 is_static: true,
                 ty: window_ty,
                 role: Role::default(),
                 binds: None,
+                animator: None,
+                class_apply: None,
+                relative: None,
                 children: Some(vec![WidgetTemplate {
                     id: Some("body".to_string()),
                     is_root: false,
                     as_prop: None,
+                    slot: None,
+                    slot_scope: None,
                     is_static: true,
                     ty: root,
                     children: None,
                     role: Role::default(),
                     binds: None,
+                    animator: None,
+                    class_apply: None,
+                    relative: None,
                 }]),
             }]),
         }
@@ -1,4 +1,7 @@
-use crate::{builtin::BuiltinWidget, token::ToLiveDesign};
+use crate::{
+    builtin::{prop::resolve_em, BuiltinWidget},
+    token::ToLiveDesign,
+};
 // use gen_converter::ConvertStyle;
 use gen_analyzer::{Props, Style};
 use gen_utils::error::Error;
@@ -41,6 +44,10 @@ impl WidgetType {
     pub fn is_define(&self) -> bool {
         matches!(self, WidgetType::Define(_))
     }
+    /// children of the custom component are put into slots, See [`crate::model::Slot::resolve`]
+    pub fn has_slots(&self) -> bool {
+        matches!(self, WidgetType::Define(define_widget) if define_widget.slots)
+    }
     pub fn root_name(&self) -> TokenStream {
         match self {
             WidgetType::Define(define_widget) => define_widget.root_name(),
@@ -63,7 +70,11 @@ impl WidgetType {
 impl TryFrom<(String, Option<Props>, bool)> for WidgetType {
     type Error = Error;
 
-    fn try_from(value: (String, Option<Props>, bool)) -> Result<Self, Self::Error> {
+    fn try_from(mut value: (String, Option<Props>, bool)) -> Result<Self, Self::Error> {
+        // `em` is relative to the font size of the widget
+        if let Some(props) = value.1.as_mut() {
+            resolve_em(props);
+        }
         match BuiltinWidget::try_from(value.clone()) {
             Ok(w) => {
                 return Ok(WidgetType::Builtin(w));
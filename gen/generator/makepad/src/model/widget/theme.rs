@@ -0,0 +1,274 @@
+use gen_analyzer::{
+    value::{Function, Value},
+    IdClass, Model, PropKey, Props, Style, StyleVisitor, Template,
+};
+use gen_utils::{
+    common::ident,
+    error::{CompilerError, Error},
+};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+use crate::{builtin::Animator, compiler::DesignTokens, token::ToLiveDesign};
+
+use super::WidgetType;
+
+/// function name of design token in `<style>`: `color: token(primary_500);`
+pub const TOKEN_FN: &str = "token";
+
+/// # Theme Apply
+/// widgets whose props use design tokens, See [`DesignTokens`]
+///
+/// tokens are replaced by the values of the default theme when compiling, and `apply_theme` is generated
+/// for the file to apply the values of other themes at runtime:
+/// ```rust,ignore
+/// pub fn apply_theme(cx: &mut Cx, root: &WidgetRef, theme: &str) {
+///     match theme {
+///         "dark" => {
+///             root.widget(id!(btn)).apply_over(cx, live! { background_color: #1D4ED8 });
+///         }
+///         _ => {}
+///     }
+/// }
+/// ```
+/// widgets are found by id at runtime (widget without id uses the special id, See [`Template::id`])
+#[derive(Debug, Clone, Default)]
+pub struct ThemeApply {
+    /// (theme name, (widget id, live props))
+    pub themes: Vec<(String, Vec<(String, TokenStream)>)>,
+}
+
+impl ThemeApply {
+    /// ## replace design tokens in the model
+    /// return None if no widget uses design tokens
+    pub fn new(tokens: Option<&DesignTokens>, model: &mut Model) -> Result<Option<Self>, Error> {
+        let mut used = vec![];
+        if let Some(template) = model.template.as_ref() {
+            themed_widgets(template, model.style.as_ref(), &mut vec![], &mut used)?;
+        }
+        // [替换为默认主题的值] ----------------------------------------------------------------------
+        if let Some(style) = model.style.as_mut() {
+            for props in style.values_mut() {
+                resolve_props(tokens, props)?;
+            }
+        }
+        if let Some(template) = model.template.as_mut() {
+            resolve_template(tokens, template)?;
+        }
+
+        let Some(tokens) = tokens.filter(|_| !used.is_empty()) else {
+            return Ok(None);
+        };
+        let mut themes = vec![];
+        for theme in &tokens.themes {
+            let mut widgets = vec![];
+            for (id, name, props) in &used {
+                let mut props = props.clone();
+                for (key, value) in props.iter_mut() {
+                    *value = token_value(Some(tokens), &theme.name, key, value)?;
+                }
+                let ty = WidgetType::try_from((name.to_string(), Some(props), false))?;
+                if let Some(props) = ty.props() {
+                    widgets.push((id.to_string(), props));
+                }
+            }
+            themes.push((theme.name.to_string(), widgets));
+        }
+        Ok(Some(Self { themes }))
+    }
+}
+
+impl ToTokens for ThemeApply {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let themes = self.themes.iter().map(|(theme, widgets)| {
+            let widgets = widgets.iter().map(|(id, props)| {
+                let id = ident(id);
+                quote! {
+                    root.widget(id!(#id)).apply_over(cx, live!{ #props });
+                }
+            });
+            quote! {
+                #theme => {
+                    #(#widgets)*
+                }
+            }
+        });
+
+        tokens.extend(quote! {
+            pub fn apply_theme(cx: &mut Cx, root: &WidgetRef, theme: &str) {
+                match theme {
+                    #(#themes)*
+                    _ => {}
+                }
+            }
+        });
+    }
+}
+
+/// name of the design token if the value is `token(name)`
+pub fn token_name(value: &Value) -> Option<String> {
+    match value {
+        Value::Function(Function { name, params, .. }) if name == TOKEN_FN => params
+            .as_ref()
+            .filter(|params| params.len() == 1)
+            .map(|params| params[0].to_string().trim().to_string()),
+        _ => None,
+    }
+}
+
+/// widgets and the props which use design tokens (after cascade, See [`StyleVisitor::visit`]),
+/// the root component and `as_prop` widgets are skipped, they can not be found by id
+fn themed_widgets(
+    template: &Template,
+    style: Option<&Style>,
+    chain: &mut Vec<IdClass>,
+    used: &mut Vec<(String, String, Props)>,
+) -> Result<(), Error> {
+    if let (Some(id), false, None) = (
+        template.id.as_ref(),
+        template.is_component(),
+        template.as_prop.as_ref(),
+    ) {
+        let mut props = template.props.clone();
+        if let Some(style) = style {
+            let cascade = StyleVisitor::visit(style, Some(id), template.class.as_ref(), chain)?;
+            Animator::merge(&template.name, style, cascade, &mut props)?;
+        }
+        let props = props
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(_, value)| token_name(value).is_some())
+            .collect::<Props>();
+        if !props.is_empty() {
+            used.push((id.to_string(), template.name.to_string(), props));
+        }
+    }
+
+    if let Some(children) = template.children.as_ref() {
+        chain.push(IdClass {
+            id: template.id.clone(),
+            class: template.class.clone(),
+        });
+        for child in children {
+            themed_widgets(child, style, chain, used)?;
+        }
+        chain.pop();
+    }
+    Ok(())
+}
+
+fn resolve_template(tokens: Option<&DesignTokens>, template: &mut Template) -> Result<(), Error> {
+    if let Some(props) = template.props.as_mut() {
+        resolve_props(tokens, props)?;
+    }
+    if let Some(children) = template.children.as_mut() {
+        for child in children {
+            resolve_template(tokens, child)?;
+        }
+    }
+    Ok(())
+}
+
+fn resolve_props(tokens: Option<&DesignTokens>, props: &mut Props) -> Result<(), Error> {
+    for (key, value) in props.iter_mut() {
+        if token_name(value).is_some() {
+            let theme = tokens.map_or("", |tokens| tokens.default.as_str());
+            *value = token_value(tokens, theme, key, value)?;
+        }
+    }
+    Ok(())
+}
+
+/// value of `token(name)` in the theme
+fn token_value(
+    tokens: Option<&DesignTokens>,
+    theme: &str,
+    key: &PropKey,
+    value: &Value,
+) -> Result<Value, Error> {
+    let Some(name) = token_name(value) else {
+        return Ok(value.clone());
+    };
+    let Some(tokens) = tokens else {
+        return Err(CompilerError::runtime(
+            "Makepad Compiler - Theme",
+            &format!(
+                "`{}({})` needs design tokens, See `[makepad.theme]`",
+                TOKEN_FN, name
+            ),
+        )
+        .into());
+    };
+    match tokens.token(theme, &name) {
+        // token of size, margin and padding is a length, See [`Value::parse_style_prop`]
+        Some(token) if key.is_style => Value::parse_style_prop(&key.name, token),
+        Some(token) => Value::parse_template(token),
+        None => Err(CompilerError::runtime(
+            "Makepad Compiler - Theme",
+            &format!("token `{}` is not defined in theme `{}`", name, theme),
+        )
+        .into()),
+    }
+}
+
+#[cfg(test)]
+mod test_theme {
+    use gen_analyzer::{value::Value, Model};
+    use quote::ToTokens;
+
+    use crate::compiler::{DesignTokens, Theme};
+
+    use super::ThemeApply;
+
+    fn tokens() -> DesignTokens {
+        let theme = |name: &str, color: &str| Theme {
+            name: name.to_string(),
+            tokens: vec![("primary".to_string(), color.to_string())]
+                .into_iter()
+                .collect(),
+        };
+        DesignTokens {
+            default: "light".to_string(),
+            themes: vec![theme("light", "#FF0000"), theme("dark", "#00FF00")],
+        }
+    }
+
+    #[test]
+    fn apply() {
+        let input = r#"
+<template>
+    <component name="Home">
+        <label id="title" text="'Hello'"></label>
+        <label class="sub" text="'World'"></label>
+    </component>
+</template>
+
+<style>
+#title {
+    color: token(primary);
+}
+.sub {
+    color: token(primary);
+}
+</style>
+"#;
+        let mut model = input.parse::<Model>().unwrap();
+        let apply = ThemeApply::new(Some(&tokens()), &mut model)
+            .unwrap()
+            .unwrap();
+        // tokens in style are replaced by the default theme
+        let style = model.style.as_ref().unwrap();
+        assert!(style.values().all(|props| props
+            .values()
+            .all(|value| !matches!(value, Value::Function(_)))));
+        assert_eq!(apply.themes.len(), 2);
+        assert_eq!(apply.themes[1].0, "dark");
+        // the widget without id uses the special id
+        assert_eq!(apply.themes[1].1.len(), 2);
+        assert_eq!(apply.themes[1].1[0].0, "title");
+        assert!(apply.to_token_stream().to_string().contains("apply_theme"));
+
+        let mut model = input.parse::<Model>().unwrap();
+        assert!(ThemeApply::new(None, &mut model).is_err());
+    }
+}
@@ -7,10 +7,10 @@ use rssyin::{analyzer::ScriptAnalyzer, bridger::ScriptBridger};
 use syn::{parse_quote, ItemEnum, Stmt};
 use crate::{
     compiler::{Context, WidgetPoll},
-    model::{TemplatePtrs, WidgetTemplate, WidgetType},
+    model::{Slot, TemplatePtrs, WidgetTemplate, WidgetType},
     token::use_default_all,
     two_way_binding::TWBPollBuilder,
-    visitor::{EventLzVisitor, FnLzVisitor, InstanceLzVisitor, PropLzVisitor},
+    visitor::{EventLzVisitor, FnLzVisitor, InstanceLzVisitor, PropLzVisitor, SlotLzVisitor},
 };
 use super::{Impls, LiveComponent};
 
@@ -124,13 +124,16 @@ impl RsScript {
         // [component, two-way-binding, live_component] -----------------------------------------------------------
         // let mut component = component.expect("component is required in component!");
         let polls = polls.read().unwrap();
+        // binds of `<slot>` are handled as scoped slot, See [`SlotLzVisitor`]
+        let binds = polls.binds.as_ref().map(Slot::widget_binds);
         let (twb, live_component) = if let Some(mut component) = component {
-            let (twb, live_component) = PropLzVisitor::visit(
+            let (twb, mut live_component) = PropLzVisitor::visit(
                 &mut component,
                 props.as_mut(),
                 template_ptrs,
+                Some(template),
                 &mut impls,
-                polls.binds.as_ref(),
+                binds.as_ref(),
                 &mut others,
             )?;
             // - [twb token stream for other_stmts] --------------------------------------------------------------
@@ -160,6 +163,14 @@ impl RsScript {
                     component.attrs.push(parse_quote!(#[derive(Default)]));
                 }
             }
+            // [scoped slot] ------------------------------------------------------------------------------------
+            SlotLzVisitor::visit(
+                template,
+                &component,
+                &mut live_component,
+                &mut impls,
+                &mut others,
+            )?;
             others.push(parse_quote!(#component));
             (twb, Some(live_component))
         } else {
@@ -178,6 +189,7 @@ impl RsScript {
                             name,
                             props: twb.as_ref().map(|build| build.0.clone()),
                             events,
+                            slots: None,
                         },
                     );
                 }
@@ -193,7 +205,7 @@ impl RsScript {
                 impl_component,
                 &mut impls,
                 twb.as_ref(),
-                polls.binds.as_ref(),
+                binds.as_ref(),
                 polls.events.as_ref(),
                 widget_poll,
                 &ctx,
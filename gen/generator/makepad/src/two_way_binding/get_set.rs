@@ -1,12 +1,22 @@
-use gen_analyzer::{value::For, Binds, Else, ElseIf, If};
+use gen_analyzer::{
+    value::{Bind, For, Value},
+    Binds, Else, ElseIf, If, Prop, PropKV,
+};
 use gen_utils::error::Error;
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{parse_quote, parse_str, ImplItem, Stmt};
 
 use crate::{
-    builtin::BuiltinWidget, model::TemplatePtrs, script::Impls, str_to_tk,
-    traits::MakepadExtComponent, visitor::sugar_for_fn_ident,
+    builtin::BuiltinWidget,
+    model::{
+        else_setter, expr_setter, interpolation_setter, PropFields, StyleBind, TemplatePtrs,
+        WidgetTemplate, CLASS, STYLE,
+    },
+    script::Impls,
+    str_to_tk,
+    traits::MakepadExtComponent,
+    visitor::{sugar_for_fn_ident, SlotLzVisitor},
 };
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -31,17 +41,36 @@ impl GetSet {
     /// 生成组件双向绑定需要的get和set方法
     /// 这个方法一般由PropLzVisitor调用(通过传入的ItemStruct生成所有的get和set方法)
     /// ⚠️ set方法需要注意：需要添加组件对数据的绑定和重绘
+    /// - `template`: 用于查找`:class`绑定预先计算的样式, See [`crate::model::ClassApply`]
+    /// - `prop_fields`: `#[prop]` struct的字段, 用于`:style`绑定字段, See [`crate::model::StyleBind`]
     pub fn create(
         field: &str,
         ty: &str,
         binds: &Binds,
         // is_for: bool,
         ptrs: &TemplatePtrs,
+        template: Option<&WidgetTemplate>,
+        prop_fields: &PropFields,
         impls: &mut Impls,
     ) -> Result<(), Error> {
         let mut bind_and_redraw = TokenStream::new();
         if let Some(binds) = binds.get(field) {
             for widget in binds {
+                // `:class`重新应用所有class的样式, 没有样式的class不需要处理
+                if widget.prop.as_str() == CLASS {
+                    if let Some(class_apply) =
+                        template.and_then(|template| template.class_apply(&widget.id))
+                    {
+                        bind_and_redraw.extend(class_apply.to_setter(field));
+                    }
+                    continue;
+                }
+                // `:style`批量设置组件的属性, 只重绘一次
+                if widget.prop.as_str() == STYLE {
+                    let style_bind = StyleBind::try_from(widget)?;
+                    bind_and_redraw.extend(style_bind.to_setter(field, ty, prop_fields)?);
+                    continue;
+                }
                 // 如果是sugar_sign则跳过
                 let (set_prop_fn, value_prefix) = match widget.prop.as_str() {
                     For::SUGAR_SIGN => continue,
@@ -53,7 +82,22 @@ impl GetSet {
 
                 let set_prop_fn =
                     parse_str::<TokenStream>(&format!("set_{}", set_prop_fn)).unwrap();
-                let value = quote! {#value_prefix value.clone()};
+                // 插值字符串和绑定表达式在任意一个绑定字段改变时整体重新计算
+                let value = match &widget.prop {
+                    Prop::Value(PropKV {
+                        value: Value::Interpolation(interp),
+                        ..
+                    }) => str_to_tk!(&interpolation_setter(interp, field))?,
+                    Prop::Value(PropKV {
+                        value: Value::Bind(Bind::Expr(expr)),
+                        ..
+                    }) => str_to_tk!(&expr_setter(expr, field))?,
+                    Prop::Else(conditions) => match else_setter(conditions, field) {
+                        Some(setter) => str_to_tk!(&setter)?,
+                        None => quote! {#value_prefix value.clone()},
+                    },
+                    _ => quote! {#value_prefix value.clone()},
+                };
 
                 let set_prop = if let Some(as_prop) = widget.as_prop.as_ref() {
                     let (widget_name, widget_id) = if let Some(father_ref) =
@@ -104,8 +148,13 @@ impl GetSet {
             });
         }
 
+        // 作用域插槽的数据在字段赋值后通知调用方
+        let notify = template
+            .map(|template| SlotLzVisitor::notify(template, field))
+            .unwrap_or_default();
+
         let (self_get, self_get_ref) = Self::create_get_fn(field, ty);
-        let (self_set, self_set_ref) = Self::create_set_fn(field, ty, bind_and_redraw);
+        let (self_set, self_set_ref) = Self::create_set_fn(field, ty, bind_and_redraw, notify);
 
         impls.self_impl.extend(vec![self_get, self_set]);
         impls.self_ref_impl.extend(vec![self_get_ref, self_set_ref]);
@@ -159,7 +208,13 @@ impl GetSet {
         )
     }
     /// 生成双向绑定的set方法
-    fn create_set_fn(field: &str, ty: &str, bind_and_redraw: TokenStream) -> (ImplItem, Stmt) {
+    /// - `notify`: 字段赋值后执行, See [`SlotLzVisitor::notify`]
+    fn create_set_fn(
+        field: &str,
+        ty: &str,
+        bind_and_redraw: TokenStream,
+        notify: TokenStream,
+    ) -> (ImplItem, Stmt) {
         let fn_set = parse_str::<TokenStream>(&format!("set_{}", field)).unwrap();
         let field = parse_str::<TokenStream>(field).unwrap();
         let ty = parse_str::<TokenStream>(ty).unwrap();
@@ -168,6 +223,7 @@ impl GetSet {
                 fn #fn_set(&mut self, cx: &mut Cx, value: #ty) -> Result<(), Box<dyn std::error::Error>> {
                     #bind_and_redraw
                     self.#field = value.clone();
+                    #notify
                     Ok(())
                 }
             },
@@ -8,6 +8,7 @@ mod r#fn;
 mod instance;
 mod lifecycle;
 mod prop;
+mod slot;
 mod sugar;
 
 pub use event::*;
@@ -15,4 +16,5 @@ pub use instance::InstanceLzVisitor;
 pub use lifecycle::*;
 pub use prop::PropLzVisitor;
 pub use r#fn::FnLzVisitor;
+pub use slot::SlotLzVisitor;
 pub use sugar::*;
@@ -1,7 +1,10 @@
 mod fields;
 
 pub use fields::*;
-use gen_analyzer::{Binds, PropComponent};
+use gen_analyzer::{
+    value::{Bind, Value},
+    Binds, Prop, PropComponent, PropKV,
+};
 use proc_macro2::TokenStream;
 use rssyin::bridger::PropItem;
 use std::collections::{HashMap, HashSet};
@@ -9,8 +12,9 @@ use std::collections::{HashMap, HashSet};
 use crate::{
     builtin::BuiltinWidget,
     model::{
+        prop_fields,
         traits::{CRef, CallbackStmt, HandleEvent, ImplLiveHook, LiveHookType},
-        TemplatePtrs,
+        PropFields, TemplatePtrs, WidgetTemplate,
     },
     script::{Impls, LiveComponent},
     str_to_tk,
@@ -145,6 +149,8 @@ impl PropLzVisitor {
         deref_prop: &ItemStruct,
         binds: &Binds,
         template_ptrs: &TemplatePtrs,
+        template: Option<&WidgetTemplate>,
+        prop_fields: &PropFields,
         impls: &mut Impls,
     ) -> Result<Option<TWBPollBuilder>, Error> {
         // [生成get和set方法] -----------------------------------------------------------------------------------
@@ -154,7 +160,15 @@ impl PropLzVisitor {
             // - [根据binds生成相关双向绑定的getter setter] -------------------------------------------------------
             let field_ident = field.ident.as_ref().unwrap().to_string();
             let field_ty = field.ty.to_token_stream().to_string();
-            let _ = GetSet::create(&field_ident, &field_ty, &binds, template_ptrs, impls)?;
+            let _ = GetSet::create(
+                &field_ident,
+                &field_ty,
+                &binds,
+                template_ptrs,
+                template,
+                prop_fields,
+                impls,
+            )?;
 
             Self::handle_two_way_binding(
                 &mut twb_poll,
@@ -201,19 +215,27 @@ impl PropLzVisitor {
     /// - props: 使用#[prop(bool)]修饰的struct或enum
     /// - binds: 组件和变量之间的绑定关系
     /// - template_ptrs: 组件指针
+    /// - template: 组件模版
     /// - impls: 组件的impl
     pub fn visit(
         component: &mut ItemStruct,
         props: Option<&mut Vec<PropItem>>,
         template_ptrs: &TemplatePtrs,
+        template: Option<&WidgetTemplate>,
         impls: &mut Impls,
         binds: Option<&Binds>,
         others: &mut Vec<Stmt>,
     ) -> Result<(Option<TWBPollBuilder>, LiveComponent), Error> {
         // [处理props] ------------------------------------------------------------------------------------------
-        if let Some(props) = props {
+        let prop_fields = if let Some(props) = props {
             Self::props(props, others)?;
-        }
+            prop_fields(props.iter().filter_map(|item| match item {
+                PropItem::Struct(prop) => Some(prop),
+                PropItem::Enum(_) => None,
+            }))
+        } else {
+            PropFields::new()
+        };
         // [组件实例初始化] -------------------------------------------------------------------------------------
         let mut live_component = Self::instance(component, impls, binds)?;
         // [生成get和set方法] -----------------------------------------------------------------------------------
@@ -225,6 +247,8 @@ impl PropLzVisitor {
                 component,
                 binds,
                 template_ptrs,
+                template,
+                &prop_fields,
                 impls,
             )?
         } else {
@@ -358,7 +382,17 @@ impl PropLzVisitor {
                     .c_refs
                     .insert(CRef::new(id.to_string(), name.to_string()));
 
-                if let Some(event) = BuiltinWidget::twb_event(name, prop.as_str()) {
+                // 插值字符串和绑定表达式是单向绑定, 组件事件不能写回字段
+                let is_one_way = matches!(
+                    prop,
+                    Prop::Value(PropKV {
+                        value: Value::Interpolation(_) | Value::Bind(Bind::Expr(_)),
+                        ..
+                    })
+                );
+                if let Some(event) =
+                    BuiltinWidget::twb_event(name, prop.as_str()).filter(|_| !is_one_way)
+                {
                     handle_event.callbacks.insert(CallbackStmt::new(
                         id.to_string(),
                         field.to_string(),
@@ -0,0 +1,209 @@
+use gen_utils::{
+    common::snake_to_camel,
+    error::{CompilerError, Error},
+};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{parse_quote, Field, ItemStruct, Stmt};
+
+use crate::{
+    model::{
+        traits::{ImplLiveHook, LiveHookType},
+        Slot, WidgetTemplate,
+    },
+    script::{Impls, LiveComponent},
+    str_to_tk,
+};
+
+/// # Visitor for scoped slot
+/// ## custom component
+/// `<slot name="header" :title="title" />` in `MyCard`:
+/// ```rust,ignore
+/// pub struct MyCardHeaderScope { pub title: String }
+///
+/// pub struct MyCard {
+///     #[rust]
+///     slot_header: Option<Box<dyn Fn(&mut Cx, &WidgetRef, &MyCardHeaderScope) -> Result<(), Box<dyn std::error::Error>>>>,
+/// }
+///
+/// impl MyCard {
+///     // called in `set_title` after the field is set
+///     fn notify_slot_header(&mut self, cx: &mut Cx) -> Result<(), Box<dyn std::error::Error>> { ... }
+/// }
+///
+/// impl MyCardRef {
+///     pub fn on_slot_header<F>(&self, cx: &mut Cx, f: F) -> Result<(), Box<dyn std::error::Error>> { ... }
+/// }
+/// ```
+/// ## caller
+/// `<my_card id="card"><label id="lb" slot="header" slot_scope="scope" :text="scope.title" /></my_card>`:
+/// ```rust,ignore
+/// fn after_apply_from_doc(&mut self, cx: &mut Cx) {
+///     let _ = self.my_card(id!(card)).on_slot_header(cx, |cx, slot, scope| {
+///         slot.glabel(id!(lb)).set_text(cx, (scope.title).clone())?;
+///         Ok(())
+///     });
+/// }
+/// ```
+/// See [`Slot`]
+pub struct SlotLzVisitor;
+
+impl SlotLzVisitor {
+    /// ## generate the code of scoped slots
+    /// - `component`: `#[component]` struct, types of the fields are used as the types of the scope
+    pub fn visit(
+        template: &WidgetTemplate,
+        component: &ItemStruct,
+        live_component: &mut LiveComponent,
+        impls: &mut Impls,
+        others: &mut Vec<Stmt>,
+    ) -> Result<(), Error> {
+        Self::declare(template, component, live_component, impls, others)?;
+        Self::uses(template, impls)
+    }
+
+    /// ## generate the code of scoped slots for the default script (no `#[component]` struct)
+    /// scoped slots can be used but can not be declared, See [`crate::model::Widget::patch_or_default_script`]
+    pub fn visit_default(template: &WidgetTemplate, impls: &mut Impls) -> Result<(), Error> {
+        if let Some((slot, _)) = Slot::declared(template)
+            .into_iter()
+            .find(|(_, scope)| !scope.is_empty())
+        {
+            return Err(CompilerError::runtime(
+                "Makepad Compiler - Slot",
+                &format!(
+                    "scoped slot `{}` needs `#[component]` struct to set the data of the slot",
+                    slot
+                ),
+            )
+            .into());
+        }
+        Self::uses(template, impls)
+    }
+
+    /// scope struct, callback field and functions of scoped slots declared by the custom component
+    fn declare(
+        template: &WidgetTemplate,
+        component: &ItemStruct,
+        live_component: &mut LiveComponent,
+        impls: &mut Impls,
+        others: &mut Vec<Stmt>,
+    ) -> Result<(), Error> {
+        let ident = live_component.ident();
+        for (slot, scope) in Slot::declared(template) {
+            if scope.is_empty() {
+                continue;
+            }
+            let scope_ident = str_to_tk!(&format!("{}{}Scope", ident, snake_to_camel(&slot)))?;
+            let [callback, notify, on_slot] = slot_idents(&slot);
+            let slot_id = str_to_tk!(&slot)?;
+            let mut fields = vec![];
+            let mut values = vec![];
+            for (prop, field) in scope.iter() {
+                let ty = component
+                    .fields
+                    .iter()
+                    .find(|f| f.ident.as_ref().is_some_and(|ident| ident == field))
+                    .map(|f| f.ty.to_token_stream())
+                    .ok_or_else(|| {
+                        CompilerError::runtime(
+                            "Makepad Compiler - Slot",
+                            &format!(
+                                "`:{}=\"{}\"` of <slot name=\"{}\">, `{}` is not a field of `#[component]` struct",
+                                prop, field, slot, field
+                            ),
+                        )
+                    })?;
+                let prop = str_to_tk!(prop)?;
+                let field = str_to_tk!(field)?;
+                fields.push(quote! { pub #prop: #ty });
+                values.push(quote! { #prop: self.#field.clone() });
+            }
+
+            others.push(parse_quote! {
+                pub struct #scope_ident {
+                    #(#fields,)*
+                }
+            });
+            let field: Field = parse_quote! {
+                #[rust]
+                #callback: Option<Box<dyn Fn(&mut Cx, &WidgetRef, &#scope_ident) -> Result<(), Box<dyn std::error::Error>>>>
+            };
+            live_component.push_field(field)?;
+            impls.self_impl.push(parse_quote! {
+                fn #notify(&mut self, cx: &mut Cx) -> Result<(), Box<dyn std::error::Error>> {
+                    if let Some(f) = self.#callback.as_ref() {
+                        let scope = #scope_ident { #(#values,)* };
+                        f(cx, &self.widget(id!(#slot_id)), &scope)?;
+                    }
+                    Ok(())
+                }
+            });
+            impls.self_ref_impl.extend(vec![parse_quote! {
+                pub fn #on_slot<F>(&self, cx: &mut Cx, f: F) -> Result<(), Box<dyn std::error::Error>>
+                where
+                    F: Fn(&mut Cx, &WidgetRef, &#scope_ident) -> Result<(), Box<dyn std::error::Error>> + 'static,
+                {
+                    self.setter(cx, |c_ref, cx| {
+                        c_ref.#callback.replace(Box::new(f));
+                        c_ref.#notify(cx)
+                    })
+                }
+            }]);
+        }
+        Ok(())
+    }
+
+    /// callbacks of scoped slots used by the caller, they are registered after apply
+    fn uses(template: &WidgetTemplate, impls: &mut Impls) -> Result<(), Error> {
+        let mut tk = TokenStream::new();
+        for slot_use in Slot::uses(template) {
+            let widget = str_to_tk!(&slot_use.widget)?;
+            let id = str_to_tk!(&slot_use.id)?;
+            let [_, _, on_slot] = slot_idents(&slot_use.slot);
+            let scope = str_to_tk!(&slot_use.scope)?;
+            let mut sets = TokenStream::new();
+            for (id, name, prop, value) in slot_use.binds.iter() {
+                let (id, name, value) = (str_to_tk!(id)?, str_to_tk!(name)?, str_to_tk!(value)?);
+                let set_fn = str_to_tk!(&format!("set_{}", prop))?;
+                sets.extend(quote! {
+                    slot.#name(id!(#id)).#set_fn(cx, (#value).clone())?;
+                });
+            }
+            tk.extend(quote! {
+                let _ = self.#widget(id!(#id)).#on_slot(cx, |cx, slot, #scope| {
+                    #sets
+                    Ok(())
+                });
+            });
+        }
+        if !tk.is_empty() {
+            impls
+                .traits()
+                .live_hook
+                .push(tk, LiveHookType::AfterApplyFromDoc);
+        }
+        Ok(())
+    }
+
+    /// ## notify the scoped slots which use the field
+    /// called in the setter of the field after the field is set, See [`crate::two_way_binding::GetSet::create`]
+    pub fn notify(template: &WidgetTemplate, field: &str) -> TokenStream {
+        Slot::declared(template)
+            .into_iter()
+            .filter(|(_, scope)| scope.iter().any(|(_, f)| f == field))
+            .fold(TokenStream::new(), |mut tk, (slot, _)| {
+                let [_, notify, _] = slot_idents(&slot);
+                tk.extend(quote! {
+                    self.#notify(cx)?;
+                });
+                tk
+            })
+    }
+}
+
+/// idents of scoped slot: [callback field, notify fn, register fn]
+fn slot_idents(slot: &str) -> [TokenStream; 3] {
+    ["slot_", "notify_slot_", "on_slot_"]
+        .map(|prefix| str_to_tk!(&format!("{}{}", prefix, slot)).unwrap())
+}
@@ -10,13 +10,21 @@ use crate::{
     str_to_tk,
     traits::ToTokensExt,
 };
-use gen_analyzer::value::{For, IdentSplit};
+use gen_analyzer::{
+    value::{For, IdentSplit},
+    VirtualFor,
+};
 use gen_utils::error::{CompilerError, Error};
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 use syn::{parse_quote, Field, Fields, ImplItem, ItemStruct};
 
+/// height of the items which are not measured in `virtual` list before any item is measured
+const ESTIMATED_HEIGHT: f64 = 32.0;
+
 /// for 语法糖处理器
+/// 使用`:key`时子组件按key复用、移动、插入和删除, 而不是全部重新创建 (保留滚动位置和输入状态)
+/// 使用`virtual`时只创建视口中可见的子组件, See [`SugarScript::virtual_script`]
 pub struct SugarScript;
 
 impl SugarScript {
@@ -38,13 +46,25 @@ impl SugarScript {
             })
             .collect();
 
-        for index in 0..ptrs.len() {
+        for (index, ptr) in ptrs.iter().enumerate() {
             // [生成一个指针] ------------------------------------------------------------------------------------
             let ptr_ident = ptr_ident(index);
             for_ptrs.push(ptr_ident_field(&ptr_ident));
+            // [virtual list的占位组件指针和状态] -------------------------------------------------------------------
+            if let Some(id) = ptr.role.virtual_list_id() {
+                for_ptrs.extend(virtual_fields(id));
+            }
         }
         // [生成初始化代码] ----------------------------------------------------------------------------------
-        let for_sc = Self::for_script(ptrs, fields);
+        let (for_sc, virtual_calls) = Self::for_script(ptrs, fields)?;
+        // [virtual list在绘制前根据视口更新子组件] ---------------------------------------------------------------
+        if !virtual_calls.is_empty() {
+            let draw_walk = impls.traits().widget.draw_walk.clone();
+            impls.traits().widget.draw_walk = quote! {
+                #(self.#virtual_calls(cx);)*
+                #draw_walk
+            };
+        }
         // [添加指针到prop中] ------------------------------------------------------------------------------------
         match &mut prop.fields {
             Fields::Named(fields) => {
@@ -64,12 +84,20 @@ impl SugarScript {
         Ok(())
     }
 
+    /// return the sugar fns and the fns of virtual lists which should be called before drawing
     fn for_script(
         widgets: &Vec<WidgetTemplate>,
         fields: HashMap<String, TokenStream>,
-    ) -> Vec<ImplItem> {
+    ) -> Result<(Vec<ImplItem>, Vec<TokenStream>), Error> {
         let mut res = vec![];
+        let mut virtual_calls = vec![];
         for widget in widgets {
+            if widget.role.is_virtual_list() {
+                let (items, virtual_call) = Self::virtual_script(widget, widgets, &fields)?;
+                res.extend(items);
+                virtual_calls.push(virtual_call);
+                continue;
+            }
             // 首先确定这个ptr是否是嵌套的for, 如果不是直接生成, 如果father是for, 返回None, 等待father生成
             match (widget.role.is_single_for(), widget.role.is_nested_for()) {
                 (true, false) | (false, false) => {
@@ -95,7 +123,201 @@ impl SugarScript {
                 (false, true) | (true, true) => {}
             };
         }
-        res
+        Ok((res, virtual_calls))
+    }
+
+    /// ## virtual list of `:for`
+    /// the sugar fn only redraws the father, the items are updated before the father is drawn:
+    /// 1. measure the height of the drawn items (if `virtual` has no fixed height)
+    /// 2. find the items in the viewport by the scroll position of the father, add `overscan` items on each side
+    /// 3. reuse the drawn items (by `:key` or index), take the others from the recycled pool or create them from the ptr
+    /// 4. recycle the items out of the range and set the height of the spacers on the top and bottom
+    ///
+    /// the father should be a scrollable view and the items before the list in the father are not counted
+    fn virtual_script(
+        widget: &WidgetTemplate,
+        ptrs: &[WidgetTemplate],
+        fields: &HashMap<String, TokenStream>,
+    ) -> Result<(Vec<ImplItem>, TokenStream), Error> {
+        let Role::For {
+            parent,
+            creditial,
+            origin_pos,
+            props,
+            key,
+            virtual_for: Some(VirtualFor { height, overscan }),
+            id,
+            name,
+            ..
+        } = &widget.role
+        else {
+            return Err(CompilerError::runtime(
+                "Makepad Compiler - Script",
+                "current virtual script is only for `virtual` list",
+            )
+            .into());
+        };
+        let index = ptrs
+            .iter()
+            .position(|p| p.id.as_ref() == Some(id))
+            .ok_or_else(|| {
+                CompilerError::runtime("Makepad Compiler - Script", "can not find the ptr in ptrs")
+            })?;
+        let ptr_ident = ptr_ident(index);
+        let [spacer_ident, heights_ident, range_ident, pool_ident] = virtual_idents(id);
+        let top_id = str_to_tk!(&format!("{}_top", id))?;
+        let bottom_id = str_to_tk!(&format!("{}_bottom", id))?;
+
+        let as_widget = str_to_tk!(&format!(
+            "as_{}",
+            BuiltinWidget::builtin_name_or_snake(name)
+        ))?;
+        let father = {
+            let widget = str_to_tk!(&BuiltinWidget::builtin_name_or_snake(&parent.name))?;
+            let id = str_to_tk!(&parent.id)?;
+            quote! {self.#widget(id!(#id))}
+        };
+        let loop_ident = creditial.fmt_iter_ident();
+        let value_ty = fields.get(&loop_ident).cloned();
+        let iter = str_to_tk!(&format!("self.{}", loop_ident))?;
+        let sugar_fn = sugar_for_fn_ident(&creditial.iter_ident_as_fn());
+        let virtual_fn = str_to_tk!(&format!("virtual_for_{}", creditial.iter_ident_as_fn()))?;
+        let enumerate = str_to_tk!(&creditial.fmt_enumerate())?;
+        let index = str_to_tk!(&creditial.fmt_index())?;
+        let item_clone = str_to_tk!(&creditial.fmt_item_clone_tk())?;
+        let overscan = *overscan;
+
+        // [set props] 可见的子组件每次绘制前都会重新设置属性 ---------------------------------------------------------
+        let mut set_props = props.iter().fold(TokenStream::new(), |mut tk, (k, v)| {
+            let set_fn = str_to_tk!(&format!("set_{}", v)).unwrap();
+            let v = str_to_tk!(k).unwrap();
+            tk.extend(quote! {
+                widget_target.#set_fn(cx, #v);
+            });
+            tk
+        });
+        if let Some(children) = widget.children.as_ref() {
+            set_props.extend(get_children_sugar_binds(children, Father::new(creditial)));
+        }
+        let let_as_widget = (!set_props.is_empty()).then(|| {
+            quote! {
+                let widget_target = widget_ref.#as_widget();
+            }
+        });
+        let live_id = if let Some(key) = key {
+            item_live_id(id, str_to_tk!(key)?)
+        } else {
+            item_live_id(id, index.clone())
+        };
+        let duplicate_key = duplicate_key_error(id, &index);
+        // [heights] 固定高度或测量高度(未测量的子组件使用已测量的平均高度) ----------------------------------------------
+        let heights = if let Some(height) = height {
+            quote! {
+                let measured = false;
+                let heights = vec![#height; len];
+            }
+        } else {
+            quote! {
+                let mut measured = false;
+                for (offset, (_, row)) in father.children[#origin_pos + 1..#origin_pos + 1 + end - start]
+                    .iter()
+                    .enumerate()
+                {
+                    let height = row.area().rect(cx).size.y;
+                    if let Some(h) = self.#heights_ident.get_mut(start + offset) {
+                        if height > 0.0 && *h != height {
+                            *h = height;
+                            measured = true;
+                        }
+                    }
+                }
+                self.#heights_ident.resize(len, 0.0);
+                let (sum, count) = self
+                    .#heights_ident
+                    .iter()
+                    .filter(|h| **h > 0.0)
+                    .fold((0.0, 0), |(sum, count), h| (sum + h, count + 1));
+                let estimate = if count > 0 { sum / count as f64 } else { #ESTIMATED_HEIGHT };
+                let heights = self
+                    .#heights_ident
+                    .iter()
+                    .map(|h| if *h > 0.0 { *h } else { estimate })
+                    .collect::<Vec<f64>>();
+            }
+        };
+
+        let sugar_fn_item = parse_quote! {
+            fn #sugar_fn(&mut self, cx: &mut Cx, value: &#value_ty) -> () {
+                self.#heights_ident.truncate(value.len());
+                #father.redraw(cx);
+            }
+        };
+        let virtual_fn_item = parse_quote! {
+            fn #virtual_fn(&mut self, cx: &mut Cx2d) -> () {
+                let len = #iter.len();
+                if let Some(mut father) = #father.borrow_mut() {
+                    // [spacers] ---------------------------------------------------------------------
+                    let (start, end) = match self.#range_ident {
+                        Some(range) => range,
+                        None => {
+                            father.children.insert(#origin_pos, (live_id!(#top_id), WidgetRef::new_from_ptr(cx, self.#spacer_ident)));
+                            father.children.insert(#origin_pos + 1, (live_id!(#bottom_id), WidgetRef::new_from_ptr(cx, self.#spacer_ident)));
+                            (0, 0)
+                        }
+                    };
+                    #heights
+                    // [visible range] ---------------------------------------------------------------
+                    let scroll = father.get_scroll_pos().y;
+                    let viewport = father.area().rect(cx).size.y;
+                    let (mut first, mut y) = (0, 0.0);
+                    while first < len && y + heights[first] <= scroll {
+                        y += heights[first];
+                        first += 1;
+                    }
+                    let mut last = first;
+                    while last < len && y < scroll + viewport {
+                        y += heights[last];
+                        last += 1;
+                    }
+                    let (new_start, new_end) = (first.saturating_sub(#overscan), (last + #overscan).min(len));
+                    // [reuse and recycle] -----------------------------------------------------------
+                    let mut rows = father
+                        .children
+                        .drain(#origin_pos + 1..#origin_pos + 1 + end - start)
+                        .collect::<std::collections::HashMap<LiveId, WidgetRef>>();
+                    let mut used_keys = std::collections::HashSet::new();
+                    for #enumerate in #iter.iter().enumerate().take(new_end).skip(new_start) {
+                        #item_clone
+                        let mut key = #live_id;
+                        let reused = if used_keys.insert(key) {
+                            rows.remove(&key)
+                        } else {
+                            #duplicate_key
+                            key = LiveId::unique();
+                            None
+                        };
+                        let widget_ref = reused
+                            .or_else(|| self.#pool_ident.pop())
+                            .unwrap_or_else(|| WidgetRef::new_from_ptr(cx, self.#ptr_ident));
+                        #let_as_widget
+                        #set_props
+                        father.children.insert(#origin_pos + 1 + #index - new_start, (key, widget_ref));
+                    }
+                    self.#pool_ident.extend(rows.into_values());
+                    let top = heights[..new_start].iter().sum::<f64>();
+                    let bottom = heights[new_end..].iter().sum::<f64>();
+                    father.children[#origin_pos].1.apply_over(cx, live!{ height: (top) });
+                    father.children[#origin_pos + 1 + new_end - new_start].1.apply_over(cx, live!{ height: (bottom) });
+                    self.#range_ident = Some((new_start, new_end));
+                    // the items are laid out again by the measured height
+                    if measured {
+                        father.redraw(cx);
+                    }
+                }
+            }
+        };
+
+        Ok((vec![sugar_fn_item, virtual_fn_item], virtual_fn))
     }
 
     /// 循环嵌套生成for语法糖
@@ -115,9 +337,11 @@ impl SugarScript {
             creditial,
             origin_pos,
             props,
+            key,
             children,
             name,
             id,
+            ..
         } = widget_role
         {
             // [ptr_ident] -------------------------------------------------------------------------------------
@@ -132,6 +356,8 @@ impl SugarScript {
                     .into());
                 };
 
+            // `id` is shadowed by the id of parent
+            let list_id = id.as_str();
             // [as_widget] -------------------------------------------------------------------------------------
             let as_widget = str_to_tk!(&format!(
                 "as_{}",
@@ -164,10 +390,25 @@ impl SugarScript {
             } else {
                 quote! {father}
             };
-            let remove_expr = quote! {
-                if #len_ident > 0 && #children_prefix.children.len() > #origin_pos{
-                    for _ in #origin_pos..(#origin_pos + #len_ident) {
-                        #children_prefix.children.remove(#origin_pos);
+            // [keyed] 有`:key`时旧的子组件按key保存, 循环中复用, 循环结束后剩余的子组件被丢弃, 重复的key会报错并创建新的子组件 --
+            let keyed = key.as_ref().map(|key| str_to_tk!(key)).transpose()?;
+            let remove_expr = if keyed.is_some() {
+                quote! {
+                    let mut used_keys = std::collections::HashSet::new();
+                    let mut keyed_children = std::collections::HashMap::new();
+                    if #len_ident > 0 && #children_prefix.children.len() > #origin_pos{
+                        for _ in #origin_pos..(#origin_pos + #len_ident) {
+                            let (key, widget_ref) = #children_prefix.children.remove(#origin_pos);
+                            keyed_children.insert(key, widget_ref);
+                        }
+                    }
+                }
+            } else {
+                quote! {
+                    if #len_ident > 0 && #children_prefix.children.len() > #origin_pos{
+                        for _ in #origin_pos..(#origin_pos + #len_ident) {
+                            #children_prefix.children.remove(#origin_pos);
+                        }
                     }
                 }
             };
@@ -229,14 +470,41 @@ impl SugarScript {
                 None
             };
 
+            let (widget_ref_expr, live_id) = if let Some(key) = keyed {
+                let live_id = item_live_id(list_id, key);
+                let duplicate_key = duplicate_key_error(list_id, &index);
+                (
+                    quote! {
+                        let mut key = #live_id;
+                        let reused = if used_keys.insert(key) {
+                            keyed_children.remove(&key)
+                        } else {
+                            #duplicate_key
+                            key = LiveId::unique();
+                            None
+                        };
+                        let widget_ref = reused
+                            .unwrap_or_else(|| WidgetRef::new_from_ptr(cx, self.#ptr_ident));
+                    },
+                    quote! {key},
+                )
+            } else {
+                (
+                    quote! {
+                        let widget_ref = WidgetRef::new_from_ptr(cx, self.#ptr_ident);
+                    },
+                    quote! {LiveId(#index as u64)},
+                )
+            };
+
             let for_loop_expr = quote! {
                 for #enumerate in #loop_ident.iter().enumerate() {
                     #item_clone
-                    let widget_ref = WidgetRef::new_from_ptr(cx, self.#ptr_ident);
+                    #widget_ref_expr
                     #let_as_widget
                     #nested_expr
                     #set_props
-                    #children_prefix.children.insert(#origin_pos + #index , (LiveId(#index as u64), widget_ref));
+                    #children_prefix.children.insert(#origin_pos + #index , (#live_id, widget_ref));
                 }
             };
 
@@ -275,6 +543,69 @@ pub fn ptr_ident(index: usize) -> TokenStream {
     str_to_tk!(format!("item_ptr{}", index).as_str()).unwrap()
 }
 
+/// ptr of the spacers of `virtual` list, it is derived from the id of the list
+/// (the spacers are inserted as `{id}_top` and `{id}_bottom`), so the lists in the same father do not collide
+pub fn spacer_ident(id: &str) -> TokenStream {
+    str_to_tk!(format!("item_spacer_{}", id).as_str()).unwrap()
+}
+
+/// idents of the fields of `virtual` list: spacer ptr, heights, range and pool, See [`virtual_fields`]
+fn virtual_idents(id: &str) -> [TokenStream; 4] {
+    [
+        spacer_ident(id),
+        str_to_tk!(&format!("virtual_heights_{}", id)).unwrap(),
+        str_to_tk!(&format!("virtual_range_{}", id)).unwrap(),
+        str_to_tk!(&format!("virtual_pool_{}", id)).unwrap(),
+    ]
+}
+
+/// fields of `virtual` list:
+/// - `item_spacer_{id}`: ptr of the spacers
+/// - `virtual_heights_{id}`: measured heights of items, `0.0` means not measured
+/// - `virtual_range_{id}`: range of the instantiated items, `None` before the spacers are inserted
+/// - `virtual_pool_{id}`: recycled items
+fn virtual_fields(id: &str) -> Vec<Field> {
+    let [spacer_ident, heights_ident, range_ident, pool_ident] = virtual_idents(id);
+    vec![
+        ptr_ident_field(&spacer_ident),
+        parse_quote! {
+            #[rust]
+            #heights_ident: Vec<f64>
+        },
+        parse_quote! {
+            #[rust]
+            #range_ident: Option<(usize, usize)>
+        },
+        parse_quote! {
+            #[rust]
+            #pool_ident: Vec<WidgetRef>
+        },
+    ]
+}
+
+/// LiveId of the item in keyed or `virtual` list, hash of the list id and the key (or index),
+/// so the items of different lists in the same father do not collide
+fn item_live_id(id: &str, key: TokenStream) -> TokenStream {
+    quote! {
+        {
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            std::hash::Hash::hash(&(#id, &#key), &mut hasher);
+            LiveId(std::hash::Hasher::finish(&hasher))
+        }
+    }
+}
+
+/// items with the same `:key` can not share a LiveId, the duplicate is reported and created as a new item
+fn duplicate_key_error(id: &str, index: &TokenStream) -> TokenStream {
+    let msg = format!(
+        "duplicate `:key` in `:for` list `{}` at index {{}}, keys should be unique",
+        id
+    );
+    quote! {
+        error!(#msg, #index);
+    }
+}
+
 pub fn sugar_for_fn_ident(ident: &str) -> TokenStream {
     str_to_tk!(format!("sugar_for_{}", ident).as_str()).unwrap()
 }
@@ -521,3 +852,107 @@ impl<'a> Father<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod test_sugar {
+    use gen_analyzer::Model;
+    use gen_utils::compiler::ToRs;
+
+    use crate::{compiler::Context, model::Widget};
+
+    /// generated rust code of the component without whitespace
+    fn compile(input: &str) -> String {
+        let model = input.parse::<Model>().unwrap();
+        let mut context = Context::default();
+        let widget = Widget::new(&mut context, model).unwrap();
+        widget.content().unwrap().to_string().replace(' ', "")
+    }
+
+    #[test]
+    fn virtual_list() {
+        let input = r#"
+<template>
+    <component name="Hello">
+        <view id="box" scroll_bars="{}">
+            <label id="row" :for="item in list" :text="item" virtual="40"></label>
+            <label id="other" :for="item in others" :text="item" virtual="true"></label>
+        </view>
+    </component>
+</template>
+
+<script>
+#[component]
+pub struct Hello {
+    list: Vec<String>,
+    others: Vec<String>,
+}
+</script>
+"#;
+        let code = compile(input);
+        // the spacers and the states are derived from the id of each list
+        for id in ["row", "other"] {
+            for field in [
+                format!("item_spacer_{}:<View>{{width:Fill,height:0}}", id),
+                format!("#[live]item_spacer_{}:Option<LivePtr>", id),
+                format!("#[rust]virtual_range_{}:Option<(usize,usize)>", id),
+                format!("live_id!({}_top)", id),
+                format!("live_id!({}_bottom)", id),
+                format!("std::hash::Hash::hash(&(\"{}\",&index),&muthasher)", id),
+            ] {
+                assert!(code.contains(&field), "{}", field);
+            }
+        }
+        assert!(!code.contains("virtual_top"));
+        // fixed height and measured height
+        assert!(code.contains("letheights=vec![40f64;len];"));
+        assert!(code.contains("self.virtual_heights_other.resize(len,0.0);"));
+        // the visible items are updated before drawing
+        assert!(code.contains(
+            "self.virtual_for_list(cx);self.virtual_for_others(cx);self.deref_widget.draw_walk"
+        ));
+        // setter only redraws the father
+        assert!(code.contains(
+            "fnsugar_for_list(&mutself,cx:&mutCx,value:&Vec<String>)->(){self.virtual_heights_row.truncate(value.len());self.gview(id!(box)).redraw(cx);}"
+        ));
+    }
+
+    #[test]
+    fn keyed_for() {
+        let input = r#"
+<template>
+    <component name="Hello">
+        <view id="box">
+            <label id="row" :for="item in list" :key="item.id" :text="item.name"></label>
+        </view>
+    </component>
+</template>
+
+<script>
+#[component]
+pub struct Hello {
+    list: Vec<Item>,
+}
+</script>
+"#;
+        let code = compile(input);
+        let sugar_fn = &code[code.find("fnsugar_for_list").unwrap()..];
+        // [remove] the old items are saved by key, the items which are not reused are dropped
+        assert!(sugar_fn.contains(
+            "for_in0usize..(0usize+len_list){let(key,widget_ref)=father.children.remove(0usize);keyed_children.insert(key,widget_ref);}"
+        ));
+        // [reuse] by the hash of list id and key
+        assert!(sugar_fn.contains(
+            "letmutkey={letmuthasher=std::collections::hash_map::DefaultHasher::new();std::hash::Hash::hash(&(\"row\",&item.id),&muthasher);LiveId(std::hash::Hasher::finish(&hasher))};"
+        ));
+        assert!(sugar_fn.contains("letreused=ifused_keys.insert(key){keyed_children.remove(&key)}"));
+        // [duplicate] the duplicate key is reported and the item is created with a unique id
+        assert!(sugar_fn.contains(
+            "error!(\"duplicate`:key`in`:for`list`row`atindex{},keysshouldbeunique\",index);key=LiveId::unique();None"
+        ));
+        // [insert] new items are created from the ptr, all items are inserted in the order of the list
+        assert!(sugar_fn.contains(
+            "letwidget_ref=reused.unwrap_or_else(||WidgetRef::new_from_ptr(cx,self.item_ptr0));"
+        ));
+        assert!(sugar_fn.contains("father.children.insert(0usize+index,(key,widget_ref));"));
+    }
+}
@@ -0,0 +1,121 @@
+//! builtin widgets from makepad plugin, use to complete and hover tag names, prop keys and events
+
+use std::str::FromStr;
+
+use gen_analyzer::{BuiltinProps, BUILTIN_PROPS};
+use makepad_gen_plugin::builtin::BuiltinWidgetType;
+
+/// the root tag of a component, it is not a widget
+pub const COMPONENT: &str = "component";
+/// placeholder of slot in a component, it is not a widget
+pub const SLOT: &str = "slot";
+
+/// ## Builtin Widget
+/// information of a builtin widget which is needed by the language server
+#[derive(Debug, Clone)]
+pub struct Widget {
+    /// tag name in `.gen` file
+    pub tag: String,
+    /// widget name in makepad
+    pub name: String,
+    /// (prop key, type name of the prop value), See [`BuiltinWidgetType::prop_types`]
+    pub props: Vec<(String, String)>,
+    /// (event name, event param type)
+    pub events: Vec<(String, String)>,
+}
+
+impl Widget {
+    /// two way binding event of the prop
+    pub fn twb_event(&self, prop: &str) -> Option<String> {
+        self.tag
+            .parse::<BuiltinWidgetType>()
+            .ok()
+            .and_then(|ty| ty.twb_event(prop))
+    }
+}
+
+impl From<BuiltinWidgetType> for Widget {
+    fn from(ty: BuiltinWidgetType) -> Self {
+        let mut events = ty
+            .event_ty_map()
+            .map(|map| map.into_iter().collect::<Vec<_>>())
+            .unwrap_or_default();
+        events.sort();
+
+        Self {
+            tag: ty.tag_name().to_string(),
+            name: ty.name().to_string(),
+            props: ty
+                .prop_types()
+                .into_iter()
+                .map(|(key, ty)| (key.to_string(), type_name(ty)))
+                .collect(),
+            events,
+        }
+    }
+}
+
+/// all builtin widgets
+pub fn widgets() -> Vec<Widget> {
+    BuiltinWidgetType::ALL
+        .into_iter()
+        .map(Widget::from)
+        .collect()
+}
+
+pub fn widget(tag: &str) -> Option<Widget> {
+    tag.parse::<BuiltinWidgetType>().ok().map(Widget::from)
+}
+
+/// ## props handled by GenUI itself, all tags can use them
+/// (key, need bind or not, doc), See [`BuiltinProps`]
+pub fn gen_props() -> Vec<(&'static str, bool, &'static str)> {
+    BUILTIN_PROPS
+        .into_iter()
+        .filter_map(|key| {
+            BuiltinProps::from_str(key)
+                .ok()
+                .map(|prop| (key, prop.is_bind(), prop.doc()))
+        })
+        .collect()
+}
+
+/// ## short type name without module path
+/// `core::option::Option<alloc::string::String>` => `Option<String>`
+fn type_name(ty: &str) -> String {
+    let mut name = String::new();
+    let mut segment = String::new();
+    for c in ty.chars() {
+        if c.is_alphanumeric() || c == '_' {
+            segment.push(c);
+        } else if c == ':' {
+            segment.clear();
+        } else {
+            name.push_str(&segment);
+            name.push(c);
+            segment.clear();
+        }
+    }
+    name.push_str(&segment);
+    name
+}
+
+#[cfg(test)]
+mod test_builtin {
+    use super::{gen_props, type_name, widget};
+
+    #[test]
+    fn props() {
+        assert_eq!(
+            type_name("core::option::Option<alloc::string::String>"),
+            "Option<String>"
+        );
+        let label = widget("label").unwrap();
+        assert!(label
+            .props
+            .iter()
+            .any(|(key, ty)| key == "font_size" && ty == "F64"));
+        let class = gen_props().into_iter().find(|(key, _, _)| *key == "class");
+        assert!(class.is_some_and(|(_, need_bind, doc)| need_bind && !doc.is_empty()));
+    }
+}
@@ -0,0 +1,110 @@
+use lsp_types::{CompletionItem, CompletionItemKind, Documentation};
+
+use crate::{
+    builtin::{self, COMPONENT, SLOT},
+    document::{Cursor, Document},
+    script::{self, SymbolKind},
+};
+
+/// ## completion at `offset`
+/// - tag name: builtin widgets, `component` and `slot`
+/// - prop key: props of the builtin widget and GenUI props, `@` for events
+/// - `:bind` / `@event` value: fields / methods in `<script>`
+pub fn completion(doc: &Document, offset: usize) -> Vec<CompletionItem> {
+    match doc.cursor(offset) {
+        Cursor::TagName { prefix } => {
+            let mut items = vec![
+                item(COMPONENT, CompletionItemKind::CLASS, "GenUI component root"),
+                item(
+                    SLOT,
+                    CompletionItemKind::CLASS,
+                    "slot placeholder of component",
+                ),
+            ];
+            items.extend(builtin::widgets().into_iter().map(|widget| {
+                item(
+                    &widget.tag,
+                    CompletionItemKind::CLASS,
+                    &format!("makepad `{}`", widget.name),
+                )
+            }));
+            filter(items, prefix)
+        }
+        Cursor::PropKey { tag, sign, prefix } => {
+            let widget = builtin::widget(tag);
+            let mut items = vec![];
+            if sign == "@" {
+                if let Some(widget) = widget {
+                    items.extend(widget.events.iter().map(|(event, param)| {
+                        item(
+                            event,
+                            CompletionItemKind::EVENT,
+                            &format!("event of `{}`, param: `{}`", widget.name, param),
+                        )
+                    }));
+                }
+            } else {
+                let is_bind = sign == ":";
+                items.extend(
+                    builtin::gen_props()
+                        .into_iter()
+                        .filter(|(_, need_bind, _)| *need_bind == is_bind)
+                        .map(|(key, _, doc)| item(key, CompletionItemKind::KEYWORD, doc)),
+                );
+                if let Some(widget) = widget {
+                    items.extend(widget.props.iter().map(|(prop, ty)| {
+                        item(
+                            prop,
+                            CompletionItemKind::PROPERTY,
+                            &format!("prop of `{}`, type: `{}`", widget.name, ty),
+                        )
+                    }));
+                }
+            }
+            filter(items, prefix)
+        }
+        Cursor::PropValue { sign, .. } => {
+            let kind = match sign {
+                ":" => SymbolKind::Field,
+                "@" => SymbolKind::Method,
+                _ => return vec![],
+            };
+            let Some((start, end)) = doc.section("script") else {
+                return vec![];
+            };
+            let prefix = doc.word_at(offset).map_or("", |(start, word)| {
+                &word[..(offset - start).min(word.len())]
+            });
+            let items = script::symbols(&doc.text[start..end])
+                .into_iter()
+                .filter(|symbol| symbol.kind == kind)
+                .map(|symbol| match kind {
+                    SymbolKind::Field => {
+                        item(&symbol.name, CompletionItemKind::FIELD, "field in script")
+                    }
+                    SymbolKind::Method => {
+                        item(&symbol.name, CompletionItemKind::METHOD, "method in script")
+                    }
+                })
+                .collect();
+            filter(items, prefix)
+        }
+        Cursor::None => vec![],
+    }
+}
+
+fn item(label: &str, kind: CompletionItemKind, doc: &str) -> CompletionItem {
+    CompletionItem {
+        label: label.to_string(),
+        kind: Some(kind),
+        documentation: (!doc.is_empty()).then(|| Documentation::String(doc.to_string())),
+        ..Default::default()
+    }
+}
+
+fn filter(items: Vec<CompletionItem>, prefix: &str) -> Vec<CompletionItem> {
+    items
+        .into_iter()
+        .filter(|item| item.label.starts_with(prefix))
+        .collect()
+}
@@ -0,0 +1,67 @@
+use lsp_types::{Location, Range};
+
+use crate::{
+    document::{Cursor, Document},
+    position::to_position,
+    script::{self, SymbolKind},
+};
+
+/// ## go to definition at `offset`
+/// from the value of `:bind` / `@event` to the field / method in `<script>`
+pub fn definition(doc: &Document, offset: usize) -> Option<Location> {
+    let kind = match doc.cursor(offset) {
+        Cursor::PropValue { sign: ":", .. } => SymbolKind::Field,
+        Cursor::PropValue { sign: "@", .. } => SymbolKind::Method,
+        _ => return None,
+    };
+    let (_, word) = doc.word_at(offset)?;
+    let (start, end) = doc.section("script")?;
+    let script = &doc.text[start..end];
+    let symbols = script::symbols(script);
+    let symbol = script::find(&symbols, word, kind)?;
+    let symbol_start = start + script::offset(script, symbol);
+
+    Some(Location::new(
+        doc.uri.clone(),
+        Range::new(
+            to_position(&doc.text, symbol_start),
+            to_position(&doc.text, symbol_start + symbol.name.len()),
+        ),
+    ))
+}
+
+#[cfg(test)]
+mod test_definition {
+    use lsp_types::{Position, Url};
+
+    use crate::document::Document;
+
+    use super::definition;
+
+    #[test]
+    fn bind_and_event() {
+        let text = r#"<template>
+  <component name="Hello">
+    <button :text="name" @clicked="click"></button>
+  </component>
+</template>
+
+<script>
+#[component]
+pub struct Hello {
+    name: String,
+}
+
+impl Hello {
+    fn click(&mut self) {}
+}
+</script>
+"#;
+        let doc = Document::new(Url::parse("file:///hello.gen").unwrap(), text.to_string());
+        let bind = definition(&doc, text.find("\"name\"").unwrap() + 2).unwrap();
+        assert_eq!(bind.range.start, Position::new(9, 4));
+        let event = definition(&doc, text.find("\"click\"").unwrap() + 1).unwrap();
+        assert_eq!(event.range.start, Position::new(13, 7));
+        assert!(definition(&doc, text.find("button").unwrap()).is_none());
+    }
+}
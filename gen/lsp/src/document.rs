@@ -0,0 +1,202 @@
+use gen_analyzer::Model;
+use lsp_types::{Diagnostic, DiagnosticSeverity, Url};
+use makepad_gen_plugin::{checker::Checker, compiler::WidgetPoll};
+
+use crate::position::word_range;
+
+/// # Document
+/// an opened `.gen` file, the text is always the latest version from the client
+#[derive(Debug, Clone)]
+pub struct Document {
+    pub uri: Url,
+    pub text: String,
+}
+
+/// # Cursor Context
+/// where the cursor is in the `<template>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Cursor<'a> {
+    /// writing tag name: `<lab|`
+    TagName { prefix: &'a str },
+    /// writing prop key: `<label :te|`, sign is `""`, `":"` or `"@"`
+    PropKey {
+        tag: &'a str,
+        sign: &'a str,
+        prefix: &'a str,
+    },
+    /// writing prop value: `<label :text="he|"`
+    PropValue {
+        tag: &'a str,
+        sign: &'a str,
+        key: &'a str,
+    },
+    /// not in a tag
+    None,
+}
+
+impl Document {
+    pub fn new(uri: Url, text: String) -> Self {
+        Self { uri, text }
+    }
+    /// ## get the content range of section `<name>...</name>`
+    /// return (start, end) byte offset of the content
+    pub fn section(&self, name: &str) -> Option<(usize, usize)> {
+        let start_tag = format!("<{}>", name);
+        let start = self.text.find(&start_tag)? + start_tag.len();
+        let end = self.text[start..]
+            .find(&format!("</{}>", name))
+            .map_or(self.text.len(), |i| start + i);
+        Some((start, end))
+    }
+    /// ## diagnostics of the whole file
+    /// use [`Model::parse_recover`] so that all errors can be reported,
+    /// if the file can be parsed, the model is checked by [`Checker`] (binds, events and slots),
+    /// custom components are not compiled here, so their events and slots are not checked
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        let mut model = Model::default();
+        let mut errors = model.parse_recover(&self.text);
+        if errors.is_empty() {
+            errors = Checker::new(&model, &WidgetPoll::default()).errors();
+        }
+        errors
+            .into_iter()
+            .map(|mut err| {
+                let offset = err.take_span().map_or(0, |span| span.offset);
+                Diagnostic {
+                    range: word_range(&self.text, offset),
+                    severity: Some(DiagnosticSeverity::ERROR),
+                    source: Some("gen".to_string()),
+                    message: err.to_string(),
+                    ..Default::default()
+                }
+            })
+            .collect()
+    }
+    /// ## the identifier at `offset` and its start offset
+    /// identifier is made of alphanumeric and `_`
+    pub fn word_at(&self, offset: usize) -> Option<(usize, &str)> {
+        let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+        let offset = offset.min(self.text.len());
+        let start = self.text[..offset]
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| is_ident(*c))
+            .last()
+            .map_or(offset, |(i, _)| i);
+        let end = self.text[offset..]
+            .char_indices()
+            .find(|(_, c)| !is_ident(*c))
+            .map_or(self.text.len(), |(i, _)| offset + i);
+
+        (start < end).then(|| (start, &self.text[start..end]))
+    }
+    /// ## get the cursor context in `<template>`
+    /// only look back from `offset` to the nearest `<`, so the template does not need to be valid
+    pub fn cursor(&self, offset: usize) -> Cursor<'_> {
+        let Some((start, end)) = self.section("template") else {
+            return Cursor::None;
+        };
+        if offset < start || offset > end {
+            return Cursor::None;
+        }
+        let before = &self.text[start..offset];
+        let Some(lt) = before.rfind('<') else {
+            return Cursor::None;
+        };
+        let segment = &before[lt + 1..];
+        // in a quoted value, `>` may appear, so count the quotes first
+        let in_value = segment.matches('"').count() % 2 == 1;
+        if (!in_value && segment.contains('>')) || segment.starts_with('/') {
+            return Cursor::None;
+        }
+        let tag_end = segment
+            .find(|c: char| c.is_whitespace())
+            .unwrap_or(segment.len());
+        let tag = &segment[..tag_end];
+        if tag_end == segment.len() {
+            return Cursor::TagName { prefix: tag };
+        }
+
+        if in_value {
+            // `key="value`
+            let quote = segment.rfind('"').unwrap_or_default();
+            let key = segment[..quote].trim_end().trim_end_matches('=').trim_end();
+            let key = key
+                .rsplit(|c: char| c.is_whitespace())
+                .next()
+                .unwrap_or_default();
+            let (sign, key) = split_sign(key);
+            return Cursor::PropValue { tag, sign, key };
+        }
+
+        let word = segment
+            .rsplit(|c: char| c.is_whitespace())
+            .next()
+            .unwrap_or_default();
+        let (sign, prefix) = split_sign(word);
+        Cursor::PropKey { tag, sign, prefix }
+    }
+}
+
+/// split `:key` | `@key` | `key` into (sign, key)
+fn split_sign(key: &str) -> (&str, &str) {
+    match key.chars().next() {
+        Some(':') | Some('@') => key.split_at(1),
+        _ => ("", key),
+    }
+}
+
+#[cfg(test)]
+mod test_document {
+    use lsp_types::Url;
+
+    use super::{Cursor, Document};
+
+    fn doc(text: &str) -> Document {
+        Document::new(Url::parse("file:///hello.gen").unwrap(), text.to_string())
+    }
+
+    #[test]
+    fn cursor() {
+        let text = "<template>\n  <component name=\"Hello\">\n    <lab\n    <button :text=\"txt\" @cl\n  </component>\n</template>";
+        let doc = doc(text);
+        let at = |s: &str| text.find(s).unwrap() + s.len();
+
+        assert_eq!(doc.cursor(at("<lab")), Cursor::TagName { prefix: "lab" });
+        assert_eq!(
+            doc.cursor(at("@cl")),
+            Cursor::PropKey {
+                tag: "button",
+                sign: "@",
+                prefix: "cl"
+            }
+        );
+        assert_eq!(
+            doc.cursor(at(":text=\"tx")),
+            Cursor::PropValue {
+                tag: "button",
+                sign: ":",
+                key: "text"
+            }
+        );
+        assert_eq!(doc.cursor(at("</component>")), Cursor::None);
+    }
+
+    #[test]
+    fn diagnostics() {
+        let text = "<template>\n  <component name=\"Hello\">\n    <label text=hello />\n  </component>\n</template>\n";
+        let diagnostics = doc(text).diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range.start.line, 2);
+        assert!(!diagnostics[0].message.contains("-->"));
+    }
+
+    #[test]
+    fn check_diagnostics() {
+        let text = "<template>\n  <component name=\"Hello\">\n    <button id=\"btn\" @click=\"on_click()\"></button>\n  </component>\n</template>\n\n<script>\n#[component]\npub struct Hello {}\n\nimpl Hello {\n    fn on_click(&self) {}\n}\n</script>\n";
+        let diagnostics = doc(text).diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range.start.line, 2);
+        assert!(diagnostics[0].message.contains("`@click`"));
+    }
+}
@@ -0,0 +1,109 @@
+use lsp_types::{Hover, HoverContents, MarkupContent, MarkupKind, Range};
+
+use crate::{
+    builtin::{self, Widget, COMPONENT, SLOT},
+    document::{Cursor, Document},
+    position::to_position,
+};
+
+/// ## hover docs at `offset`
+/// - tag name: makepad widget name, props and events
+/// - prop key: which widget the prop belongs to, two way binding event
+/// - event key: param type of the event
+pub fn hover(doc: &Document, offset: usize) -> Option<Hover> {
+    let (start, word) = doc.word_at(offset)?;
+    let is_close_tag = doc.text[..start].ends_with("</");
+    let value = match doc.cursor(start) {
+        Cursor::TagName { .. } => tag_doc(word)?,
+        Cursor::None if is_close_tag => tag_doc(word)?,
+        Cursor::PropKey { tag, sign, .. } => prop_doc(tag, sign, word)?,
+        _ => return None,
+    };
+
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value,
+        }),
+        range: Some(Range::new(
+            to_position(&doc.text, start),
+            to_position(&doc.text, start + word.len()),
+        )),
+    })
+}
+
+fn tag_doc(tag: &str) -> Option<String> {
+    if tag == COMPONENT {
+        return Some(format!(
+            "**{}**\n\nroot of a GenUI component, props and events of the component are defined in `<script>`",
+            COMPONENT
+        ));
+    }
+    if tag == SLOT {
+        return Some(format!(
+            "**{}**\n\nslot placeholder of a GenUI component, `<{} name=\"header\" />` is filled by `slot=\"header\"` in the caller, `<{} />` is the default slot, bind props (`<{} :title=\"title\" />`) are the data of the scoped slot",
+            SLOT, SLOT, SLOT, SLOT
+        ));
+    }
+    let Widget {
+        tag,
+        name,
+        props,
+        events,
+    } = builtin::widget(tag)?;
+    let mut doc = format!("**{}**\n\nmakepad widget: `{}`", tag, name);
+    if !props.is_empty() {
+        doc.push_str(&format!(
+            "\n\nprops: {}",
+            code_list(props.iter().map(|(prop, _)| prop))
+        ));
+    }
+    if !events.is_empty() {
+        doc.push_str(&format!(
+            "\n\nevents: {}",
+            code_list(events.iter().map(|(event, _)| event))
+        ));
+    }
+    Some(doc)
+}
+
+fn prop_doc(tag: &str, sign: &str, key: &str) -> Option<String> {
+    let widget = builtin::widget(tag);
+    if sign == "@" {
+        let widget = widget?;
+        let (event, param) = widget.events.iter().find(|(event, _)| event == key)?;
+        return Some(format!(
+            "**@{}**\n\nevent of `{}`\n\nparam: `{}`",
+            event, widget.name, param
+        ));
+    }
+
+    if let Some((key, need_bind, doc)) = builtin::gen_props()
+        .into_iter()
+        .find(|(gen_key, _, _)| *gen_key == key)
+    {
+        let sign = if need_bind { ":" } else { "" };
+        return Some(format!("**{}{}**\n\nGenUI prop, {}", sign, key, doc));
+    }
+
+    let widget = widget?;
+    let (_, ty) = widget.props.iter().find(|(prop, _)| prop == key)?;
+    let mut doc = format!(
+        "**{}{}**\n\nprop of `{}`\n\ntype: `{}`",
+        sign, key, widget.name, ty
+    );
+    if let Some(event) = widget.twb_event(key) {
+        doc.push_str(&format!("\n\ntwo way binding event: `{}`", event));
+    }
+    Some(doc)
+}
+
+fn code_list<'a, I>(items: I) -> String
+where
+    I: Iterator<Item = &'a String>,
+{
+    items
+        .map(|item| format!("`{}`", item))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
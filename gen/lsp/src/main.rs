@@ -0,0 +1,31 @@
+//! # GenUI Language Server
+//! language server for `.gen` files, communicate with client by LSP over stdio
+//! - diagnostics: all errors in the file (see `gen_analyzer::Model::parse_recover`)
+//! - completion: tag names, prop keys, events and `<script>` symbols
+//! - hover: docs of builtin widgets, props and events
+//! - go to definition: from `:bind` / `@event` value to the field / method in `<script>`
+mod builtin;
+mod completion;
+mod definition;
+mod document;
+mod hover;
+mod position;
+mod script;
+mod server;
+
+use lsp_server::Connection;
+use server::{capabilities, Server, ServerResult};
+
+fn main() -> ServerResult<()> {
+    // stdout is used by LSP, log to stderr
+    eprintln!("gen-lsp start");
+    let (connection, io_threads) = Connection::stdio();
+    let capabilities = serde_json::to_value(capabilities())?;
+    let _ = connection.initialize(capabilities)?;
+    Server::default().run(&connection)?;
+    // drop connection so that io threads can stop
+    drop(connection);
+    io_threads.join()?;
+    eprintln!("gen-lsp stop");
+    Ok(())
+}
@@ -0,0 +1,76 @@
+//! LSP position is (line, utf-16 character), GenUI span is byte offset, convert between them
+
+use lsp_types::{Position, Range};
+
+/// convert byte offset to lsp position
+pub fn to_position(text: &str, offset: usize) -> Position {
+    let mut offset = offset.min(text.len());
+    while !text.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    Position::new(
+        before.matches('\n').count() as u32,
+        before[line_start..].encode_utf16().count() as u32,
+    )
+}
+
+/// convert lsp position to byte offset, if position is out of the text, return the end of line or text
+pub fn to_offset(text: &str, position: Position) -> usize {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        match text[line_start..].find('\n') {
+            Some(i) => line_start += i + 1,
+            None => return text.len(),
+        }
+    }
+    let line_end = text[line_start..]
+        .find('\n')
+        .map_or(text.len(), |i| line_start + i);
+
+    let mut utf16 = 0;
+    for (index, c) in text[line_start..line_end].char_indices() {
+        if utf16 >= position.character as usize {
+            return line_start + index;
+        }
+        utf16 += c.len_utf16();
+    }
+    line_end
+}
+
+/// range of the word (or the char) which starts at `offset`, use to mark the diagnostic
+pub fn word_range(text: &str, offset: usize) -> Range {
+    let offset = offset.min(text.len());
+    let len = text[offset..]
+        .char_indices()
+        .find(|(_, c)| c.is_whitespace() || matches!(c, '>' | '"' | '='))
+        .map_or(text.len() - offset, |(i, _)| i)
+        .max(text[offset..].chars().next().map_or(0, char::len_utf8));
+
+    Range::new(to_position(text, offset), to_position(text, offset + len))
+}
+
+#[cfg(test)]
+mod test_position {
+    use lsp_types::Position;
+
+    use super::{to_offset, to_position, word_range};
+
+    #[test]
+    fn convert() {
+        let text = "<template>\n  <label text=\"'你好'\" />\n</template>";
+        let offset = text.find("/>").unwrap();
+        let position = to_position(text, offset);
+        assert_eq!(position, Position::new(1, 21));
+        assert_eq!(to_offset(text, position), offset);
+        assert_eq!(to_offset(text, Position::new(9, 0)), text.len());
+    }
+
+    #[test]
+    fn word() {
+        let text = "<label text=hello />";
+        let range = word_range(text, 7);
+        assert_eq!((range.start.character, range.end.character), (7, 11));
+    }
+}
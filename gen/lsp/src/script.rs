@@ -0,0 +1,116 @@
+//! symbols in `<script>`, use to go to the definition of `:bind` and `@event` values
+
+use syn::{visit::Visit, ImplItemFn, ItemFn, ItemStruct};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolKind {
+    /// field of a struct (such as the `#[component]` struct), target of `:bind`
+    Field,
+    /// method in impl block or fn, target of `@event`
+    Method,
+}
+
+/// ## Symbol
+/// a named item in script, `line` (start from 1) and `column` (start from 0, count by char) is in the script content
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Default)]
+struct SymbolVisitor {
+    symbols: Vec<Symbol>,
+}
+
+impl SymbolVisitor {
+    fn push(&mut self, ident: &syn::Ident, kind: SymbolKind) {
+        let start = ident.span().start();
+        self.symbols.push(Symbol {
+            name: ident.to_string(),
+            kind,
+            line: start.line,
+            column: start.column,
+        });
+    }
+}
+
+impl<'ast> Visit<'ast> for SymbolVisitor {
+    fn visit_item_struct(&mut self, i: &'ast ItemStruct) {
+        for field in i.fields.iter() {
+            if let Some(ident) = field.ident.as_ref() {
+                self.push(ident, SymbolKind::Field);
+            }
+        }
+        syn::visit::visit_item_struct(self, i);
+    }
+    fn visit_impl_item_fn(&mut self, i: &'ast ImplItemFn) {
+        self.push(&i.sig.ident, SymbolKind::Method);
+        syn::visit::visit_impl_item_fn(self, i);
+    }
+    fn visit_item_fn(&mut self, i: &'ast ItemFn) {
+        self.push(&i.sig.ident, SymbolKind::Method);
+        syn::visit::visit_item_fn(self, i);
+    }
+}
+
+/// collect symbols from script content, return empty if the script can not be parsed
+pub fn symbols(script: &str) -> Vec<Symbol> {
+    match syn::parse_file(script) {
+        Ok(file) => {
+            let mut visitor = SymbolVisitor::default();
+            visitor.visit_file(&file);
+            visitor.symbols
+        }
+        Err(_) => vec![],
+    }
+}
+
+/// ## find the symbol by name
+/// prefer the symbol which kind is `kind`, if not found, use any symbol with the same name
+pub fn find<'a>(symbols: &'a [Symbol], name: &str, kind: SymbolKind) -> Option<&'a Symbol> {
+    symbols
+        .iter()
+        .find(|symbol| symbol.name == name && symbol.kind == kind)
+        .or_else(|| symbols.iter().find(|symbol| symbol.name == name))
+}
+
+/// convert (line, column) of symbol to byte offset in script
+pub fn offset(script: &str, symbol: &Symbol) -> usize {
+    let line_start = script
+        .split_inclusive('\n')
+        .take(symbol.line.saturating_sub(1))
+        .map(str::len)
+        .sum::<usize>();
+    script[line_start..]
+        .char_indices()
+        .nth(symbol.column)
+        .map_or(script.len(), |(i, _)| line_start + i)
+}
+
+#[cfg(test)]
+mod test_script {
+    use super::{find, offset, symbols, SymbolKind};
+
+    #[test]
+    fn find_symbol() {
+        let script = r#"
+#[component]
+pub struct Hello {
+    name: String,
+}
+
+impl Hello {
+    fn click(&mut self) {}
+}
+"#;
+        let symbols = symbols(script);
+        let name = find(&symbols, "name", SymbolKind::Field).unwrap();
+        assert_eq!(&script[offset(script, name)..][..4], "name");
+        let click = find(&symbols, "click", SymbolKind::Method).unwrap();
+        assert_eq!(&script[offset(script, click)..][..5], "click");
+        assert!(find(&symbols, "other", SymbolKind::Field).is_none());
+    }
+}
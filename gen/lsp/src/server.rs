@@ -0,0 +1,171 @@
+use std::{collections::HashMap, error::Error};
+
+use lsp_server::{Connection, ExtractError, Message, Notification, Request, RequestId, Response};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+        Notification as NotificationTrait, PublishDiagnostics,
+    },
+    request::{Completion, GotoDefinition, HoverRequest, Request as RequestTrait},
+    CompletionOptions, CompletionResponse, GotoDefinitionResponse, HoverProviderCapability, OneOf,
+    PublishDiagnosticsParams, ServerCapabilities, TextDocumentPositionParams,
+    TextDocumentSyncCapability, TextDocumentSyncKind, Url,
+};
+
+use crate::{
+    completion::completion, definition::definition, document::Document, hover::hover,
+    position::to_offset,
+};
+
+pub type ServerResult<T> = Result<T, Box<dyn Error + Sync + Send>>;
+
+/// capabilities of gen-lsp, full text sync is enough since `.gen` files are small
+pub fn capabilities() -> ServerCapabilities {
+    ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec!["<".to_string(), ":".to_string(), "@".to_string()]),
+            ..Default::default()
+        }),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        ..Default::default()
+    }
+}
+
+/// # Server
+/// handle lsp messages from client, keep all opened documents
+#[derive(Debug, Default)]
+pub struct Server {
+    docs: HashMap<Url, Document>,
+}
+
+impl Server {
+    /// main loop, return when client send `shutdown` request or the connection is closed
+    pub fn run(&mut self, connection: &Connection) -> ServerResult<()> {
+        for msg in &connection.receiver {
+            match msg {
+                Message::Request(req) => {
+                    if connection.handle_shutdown(&req)? {
+                        return Ok(());
+                    }
+                    let resp = self.handle_request(req);
+                    connection.sender.send(Message::Response(resp))?;
+                }
+                Message::Notification(not) => {
+                    if let Some(uri) = self.handle_notification(not)? {
+                        self.publish_diagnostics(connection, uri)?;
+                    }
+                }
+                Message::Response(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_request(&self, req: Request) -> Response {
+        let id = req.id.clone();
+        let res = match req.method.as_str() {
+            Completion::METHOD => cast::<Completion>(req).map(|(id, params)| {
+                let items = self
+                    .at(&params.text_document_position)
+                    .map(|(doc, offset)| completion(doc, offset))
+                    .unwrap_or_default();
+                Response::new_ok(id, Some(CompletionResponse::Array(items)))
+            }),
+            HoverRequest::METHOD => cast::<HoverRequest>(req).map(|(id, params)| {
+                let res = self
+                    .at(&params.text_document_position_params)
+                    .and_then(|(doc, offset)| hover(doc, offset));
+                Response::new_ok(id, res)
+            }),
+            GotoDefinition::METHOD => cast::<GotoDefinition>(req).map(|(id, params)| {
+                let res = self
+                    .at(&params.text_document_position_params)
+                    .and_then(|(doc, offset)| definition(doc, offset))
+                    .map(GotoDefinitionResponse::Scalar);
+                Response::new_ok(id, res)
+            }),
+            _ => {
+                return Response::new_err(
+                    id,
+                    lsp_server::ErrorCode::MethodNotFound as i32,
+                    format!("unsupported request: {}", req.method),
+                )
+            }
+        };
+
+        res.unwrap_or_else(|e| {
+            Response::new_err(
+                id,
+                lsp_server::ErrorCode::InvalidParams as i32,
+                e.to_string(),
+            )
+        })
+    }
+
+    /// return the uri of the document which need to publish diagnostics
+    fn handle_notification(&mut self, not: Notification) -> ServerResult<Option<Url>> {
+        match not.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params = not.extract::<<DidOpenTextDocument as NotificationTrait>::Params>(
+                    DidOpenTextDocument::METHOD,
+                )?;
+                let uri = params.text_document.uri;
+                self.docs.insert(
+                    uri.clone(),
+                    Document::new(uri.clone(), params.text_document.text),
+                );
+                Ok(Some(uri))
+            }
+            DidChangeTextDocument::METHOD => {
+                let params = not.extract::<<DidChangeTextDocument as NotificationTrait>::Params>(
+                    DidChangeTextDocument::METHOD,
+                )?;
+                let uri = params.text_document.uri;
+                // full sync, the last change is the whole text
+                if let Some(change) = params.content_changes.into_iter().last() {
+                    self.docs
+                        .insert(uri.clone(), Document::new(uri.clone(), change.text));
+                }
+                Ok(Some(uri))
+            }
+            DidCloseTextDocument::METHOD => {
+                let params = not.extract::<<DidCloseTextDocument as NotificationTrait>::Params>(
+                    DidCloseTextDocument::METHOD,
+                )?;
+                self.docs.remove(&params.text_document.uri);
+                Ok(None)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn publish_diagnostics(&self, connection: &Connection, uri: Url) -> ServerResult<()> {
+        let diagnostics = self
+            .docs
+            .get(&uri)
+            .map(Document::diagnostics)
+            .unwrap_or_default();
+        let params = PublishDiagnosticsParams::new(uri, diagnostics, None);
+        connection
+            .sender
+            .send(Message::Notification(Notification::new(
+                PublishDiagnostics::METHOD.to_string(),
+                params,
+            )))?;
+        Ok(())
+    }
+
+    fn at(&self, params: &TextDocumentPositionParams) -> Option<(&Document, usize)> {
+        let doc = self.docs.get(&params.text_document.uri)?;
+        Some((doc, to_offset(&doc.text, params.position)))
+    }
+}
+
+fn cast<R>(req: Request) -> Result<(RequestId, R::Params), ExtractError<Request>>
+where
+    R: RequestTrait,
+{
+    req.extract(R::METHOD)
+}
@@ -1,27 +1,73 @@
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
+
+use crate::error::Error;
 
 /// # Os Type
 /// Os type is used to determine the current operating system.
 ///
 /// use **Os::current** to get the current operating system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Os {
     Windows,
     Linux,
     Mac,
+    Android,
+    Ios,
+    /// wasm32
+    Web,
     Other,
 }
 
 impl Os {
+    /// desktop operating systems
+    pub const DESKTOP: [Os; 3] = [Os::Windows, Os::Mac, Os::Linux];
+
     /// ## Get Current Operating System
     pub fn current() -> Self {
+        if cfg!(target_arch = "wasm32") {
+            return Os::Web;
+        }
         let os = std::env::consts::OS;
         match os {
             "windows" => Os::Windows,
             "linux" => Os::Linux,
             "macos" => Os::Mac,
+            "android" => Os::Android,
+            "ios" => Os::Ios,
             _ => Os::Other,
         }
     }
+    pub fn is_desktop(&self) -> bool {
+        Os::DESKTOP.contains(self)
+    }
+    /// ## cfg predicate of the os
+    /// such as `target_os = "android"`, web is `target_arch = "wasm32"`, return None if the os is `Other`
+    pub fn cfg(&self) -> Option<String> {
+        match self {
+            Os::Other => None,
+            Os::Web => Some("target_arch = \"wasm32\"".to_string()),
+            _ => Some(format!("target_os = \"{}\"", self)),
+        }
+    }
+}
+
+impl FromStr for Os {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "windows" => Ok(Os::Windows),
+            "linux" => Ok(Os::Linux),
+            "macos" => Ok(Os::Mac),
+            "android" => Ok(Os::Android),
+            "ios" => Ok(Os::Ios),
+            "web" => Ok(Os::Web),
+            _ => Err(Error::from(format!(
+                "unknown os `{}`, expected one of: windows, linux, macos, android, ios, web",
+                s
+            ))),
+        }
+    }
 }
 
 impl Display for Os {
@@ -30,6 +76,9 @@ impl Display for Os {
             Os::Windows => "windows",
             Os::Linux => "linux",
             Os::Mac => "macos",
+            Os::Android => "android",
+            Os::Ios => "ios",
+            Os::Web => "web",
             Os::Other => "other",
         })
     }
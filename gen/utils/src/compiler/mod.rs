@@ -8,7 +8,11 @@ mod rs;
 mod underlayer;
 mod version;
 
-use std::{collections::HashMap, path::PathBuf, process::exit};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    process::exit,
+};
 
 pub use builder::*;
 pub use check::*;
@@ -62,8 +66,50 @@ pub trait CompilerImpl {
     /// ## compile
     /// compile the target file
     fn compile(&mut self, path: PathBuf) -> Result<(), Error>;
+    /// ## files to compile when the compiler is executed
+    /// the compiler without file discovery compiles an empty path, See [`CompilerImpl::execute`]
+    fn sources(&self) -> Vec<PathBuf> {
+        vec![PathBuf::new()]
+    }
+    /// ## compile all files
+    /// compile the files in order, compiler can override this to compile independent files in parallel,
+    /// but the result should be the same as calling [`CompilerImpl::compile`] one by one
+    fn compile_all(&mut self, paths: Vec<PathBuf>) -> Result<(), Error> {
+        for path in paths {
+            self.compile(path)?;
+        }
+        Ok(())
+    }
 
     fn remove(&mut self, path: PathBuf) -> Result<Option<Vec<PathBuf>>, Error>;
+    /// ## files affected by the change of the file
+    /// the file (or files in the dir) and all files depend on it, in compile order.
+    /// compiler without dependency info only returns the file itself
+    fn affected(&self, path: &Path) -> Vec<PathBuf> {
+        vec![path.to_path_buf()]
+    }
+    /// ## compile the changed file and all files depend on it
+    /// files are compiled one by one in the order of [`CompilerImpl::affected`],
+    /// so each file is compiled after the files it depends on
+    fn compile_affected(&mut self, path: PathBuf) -> Result<(), Error> {
+        for path in self.affected(path.as_path()) {
+            self.compile(path)?;
+        }
+        Ok(())
+    }
+    /// ## remove the file and recompile all files depend on it
+    fn remove_affected(&mut self, path: PathBuf) -> Result<Option<Vec<PathBuf>>, Error> {
+        let dependants = self
+            .affected(path.as_path())
+            .into_iter()
+            .filter(|affected| !affected.starts_with(path.as_path()))
+            .collect::<Vec<_>>();
+        let removed = self.remove(path)?;
+        for dependant in dependants {
+            self.compile(dependant)?;
+        }
+        Ok(removed)
+    }
     /// ## update after do compile
     fn update(&mut self) -> Result<(), Error>;
     // /// ## insert node into compiler tree
@@ -74,7 +120,8 @@ pub trait CompilerImpl {
     /// ## execute compiler
     fn execute(&mut self) -> Result<(), Error> {
         // [compile] ------------------------------------------
-        self.compile(PathBuf::new())?;
+        let sources = self.sources();
+        self.compile_all(sources)?;
         // [update] -------------------------------------------
         self.update()
     }
@@ -132,3 +179,88 @@ impl From<Result<(), Error>> for ResultExecutor {
         Self(result)
     }
 }
+
+#[cfg(test)]
+mod test_compiler {
+    use std::{
+        collections::HashMap,
+        path::{Path, PathBuf},
+    };
+
+    use super::{CompilerImpl, Executor};
+    use crate::error::Error;
+
+    /// `b.gen` uses `a.gen`, `d.gen` uses `comps/c.gen`
+    #[derive(Default)]
+    struct MockCompiler {
+        compiled: Vec<PathBuf>,
+        removed: Vec<PathBuf>,
+    }
+
+    impl CompilerImpl for MockCompiler {
+        fn execute_auxiliaries(&mut self, _executor: Executor) {}
+        fn init(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+        fn send_plugins(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+        fn recv_plugins(
+            &mut self,
+            _plugins: Option<&HashMap<String, PathBuf>>,
+        ) -> Result<(), Error> {
+            Ok(())
+        }
+        fn before_compile(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+        fn after_compile(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+        fn compile(&mut self, path: PathBuf) -> Result<(), Error> {
+            self.compiled.push(path);
+            Ok(())
+        }
+        fn remove(&mut self, path: PathBuf) -> Result<Option<Vec<PathBuf>>, Error> {
+            self.removed.push(path.to_path_buf());
+            Ok(Some(vec![path]))
+        }
+        fn affected(&self, path: &Path) -> Vec<PathBuf> {
+            let affected: &[&str] = match path.to_str() {
+                Some("a.gen") => &["a.gen", "b.gen"],
+                Some("comps") => &["comps/c.gen", "d.gen"],
+                _ => &[],
+            };
+            affected.iter().map(PathBuf::from).collect()
+        }
+        fn update(&mut self) -> Result<(), Error> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn compile_affected() {
+        let mut compiler = MockCompiler::default();
+        compiler.compile_affected(PathBuf::from("a.gen")).unwrap();
+        assert_eq!(
+            compiler.compiled,
+            [PathBuf::from("a.gen"), PathBuf::from("b.gen")]
+        );
+        assert!(compiler.removed.is_empty());
+    }
+
+    #[test]
+    fn remove_affected() {
+        // [file] the dependants are recompiled after the file is removed -----------------------
+        let mut compiler = MockCompiler::default();
+        let removed = compiler.remove_affected(PathBuf::from("a.gen")).unwrap();
+        assert_eq!(removed, Some(vec![PathBuf::from("a.gen")]));
+        assert_eq!(compiler.removed, [PathBuf::from("a.gen")]);
+        assert_eq!(compiler.compiled, [PathBuf::from("b.gen")]);
+        // [dir] the files in the dir are not recompiled -------------------------------------------
+        let mut compiler = MockCompiler::default();
+        let _ = compiler.remove_affected(PathBuf::from("comps")).unwrap();
+        assert_eq!(compiler.removed, [PathBuf::from("comps")]);
+        assert_eq!(compiler.compiled, [PathBuf::from("d.gen")]);
+    }
+}
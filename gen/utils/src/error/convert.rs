@@ -1,11 +1,51 @@
 use std::fmt::Display;
 
+use super::Span;
+
 #[derive(Debug, Clone)]
 pub enum ConvertError {
     FromTo { from: String, to: String },
     Serde(String),
     ValueType(String),
     UnSupport(String),
+    /// convert error with the location in the source file
+    Located { err: Box<ConvertError>, span: Box<Span> },
+}
+
+impl ConvertError {
+    pub fn span(&self) -> Option<&Span> {
+        match self {
+            ConvertError::Located { span, .. } => Some(span.as_ref()),
+            _ => None,
+        }
+    }
+    pub fn span_mut(&mut self) -> Option<&mut Span> {
+        match self {
+            ConvertError::Located { span, .. } => Some(span.as_mut()),
+            _ => None,
+        }
+    }
+    /// remove the location and return it, the error becomes the inner error
+    pub fn take_span(&mut self) -> Option<Span> {
+        if let ConvertError::Located { err, span } = self {
+            let span = std::mem::take(span.as_mut());
+            let err = std::mem::replace(err.as_mut(), ConvertError::Serde(String::new()));
+            *self = err;
+            Some(span)
+        } else {
+            None
+        }
+    }
+    /// attach location to the error, if the error already has a location, keep it
+    pub fn located(self, span: Span) -> Self {
+        match self {
+            ConvertError::Located { .. } => self,
+            _ => ConvertError::Located {
+                err: Box::new(self),
+                span: Box::new(span),
+            },
+        }
+    }
 }
 
 impl Display for ConvertError {
@@ -23,6 +63,9 @@ impl Display for ConvertError {
                 "UnSupport Error: value type unsupport to use `{}`",
                 s
             )),
+            ConvertError::Located { err, span } => {
+                f.write_fmt(format_args!("{}\n{}", err, span))
+            }
         }
     }
 }
\ No newline at end of file
@@ -2,6 +2,7 @@ mod convert;
 mod env;
 mod macros;
 mod parse;
+mod span;
 mod strategy;
 mod style;
 mod tag;
@@ -9,6 +10,7 @@ mod tag;
 pub use convert::*;
 pub use env::EnvError;
 pub use parse::*;
+pub use span::Span;
 mod compiler;
 mod fs;
 pub use compiler::CompilerError;
@@ -35,6 +37,51 @@ impl Error {
             _ => false,
         }
     }
+    /// get the location of the error in the source file (only parse and convert error has)
+    pub fn span(&self) -> Option<&Span> {
+        match self {
+            Error::Parse(parse_error) => parse_error.span.as_deref(),
+            Error::Convert(convert_error) => convert_error.span(),
+            _ => None,
+        }
+    }
+    pub fn span_mut(&mut self) -> Option<&mut Span> {
+        match self {
+            Error::Parse(parse_error) => parse_error.span.as_deref_mut(),
+            Error::Convert(convert_error) => convert_error.span_mut(),
+            _ => None,
+        }
+    }
+    /// remove the location of the error and return it, use when the location is shown in other way (such as editor)
+    pub fn take_span(&mut self) -> Option<Span> {
+        match self {
+            Error::Parse(parse_error) => parse_error.span.take().map(|span| *span),
+            Error::Convert(convert_error) => convert_error.take_span(),
+            _ => None,
+        }
+    }
+    /// ## attach location to the error if it has no location yet
+    /// - Parse, Convert: set span
+    /// - FromDynError: convert to a template parse error with span
+    /// - others: not a parse error, keep it
+    pub fn or_span(self, span: Span) -> Self {
+        if self.span().is_some() {
+            return self;
+        }
+        match self {
+            Error::Parse(mut parse_error) => {
+                parse_error.set_span(span);
+                Error::Parse(parse_error)
+            }
+            Error::Convert(convert_error) => Error::Convert(convert_error.located(span)),
+            Error::FromDynError(msg) => {
+                let mut parse_error = ParseError::template(&msg);
+                parse_error.set_span(span);
+                Error::Parse(parse_error)
+            }
+            _ => self,
+        }
+    }
     pub fn to_runtime(self, target: &str) -> Self {
         let target = target.to_string();
         match self {
@@ -1,5 +1,7 @@
 use std::fmt::Display;
 
+use super::Span;
+
 /// # Parse Error
 /// common error for parse, if any you need to parse, but failed, you can use this error
 #[derive(Debug, Clone)]
@@ -7,12 +9,16 @@ pub struct ParseError {
     pub target: String,
     pub other: Option<String>,
     pub ty: ParseType,
+    /// where the error is in the source file (if known)
+    pub span: Option<Box<Span>>,
 }
 
 #[derive(Debug, Clone)]
 pub enum ParseType {
     RustDep,
     Template,
+    Style,
+    Script,
     DSLBind,
     Toml,
     Color(String),
@@ -25,6 +31,7 @@ impl ParseError {
         Self {
             target: target.to_string(),
             other: None,
+            span: None,
             ty,
         }
     }
@@ -32,6 +39,7 @@ impl ParseError {
         Self {
             target: target.to_string(),
             other: None,
+            span: None,
             ty: ParseType::RustDep,
         }
     }
@@ -43,10 +51,15 @@ impl ParseError {
         self.ty = ty;
         self
     }
+    pub fn set_span(&mut self, span: Span) -> &mut Self {
+        self.span.replace(Box::new(span));
+        self
+    }
     pub fn template(target: &str) -> Self {
         Self {
             target: target.to_string(),
             other: None,
+            span: None,
             ty: ParseType::Template,
         }
     }
@@ -54,6 +67,7 @@ impl ParseError {
         Self {
             target: target.to_string(),
             other: None,
+            span: None,
             ty: ParseType::Other(ty.to_string()),
         }
     }
@@ -64,6 +78,8 @@ impl Display for ParseError {
         let ty = match &self.ty {
             ParseType::RustDep => "Rust Dependence".to_string(),
             ParseType::Template => "DSL Template".to_string(),
+            ParseType::Style => "DSL Style".to_string(),
+            ParseType::Script => "DSL Script".to_string(),
             ParseType::Other(s) => s.to_string(),
             ParseType::DSLBind => "DSL Bind Value".to_string(),
             ParseType::Color(s) => format!("Color-{}", s),
@@ -76,6 +92,10 @@ impl Display for ParseError {
             fmt_out.push_str(&format!("\nother msg: {}", other));
         }
 
+        if let Some(span) = self.span.as_ref() {
+            fmt_out.push_str(&format!("\n{}", span));
+        }
+
         f.write_str(&fmt_out)
     }
 }
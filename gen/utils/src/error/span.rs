@@ -0,0 +1,148 @@
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+/// # Span
+/// location of an error in a source file (such as `.gen` file)
+///
+/// parsers only know the byte `offset` of the error in the input they are parsing,
+/// call [`Span::resolve`] with the whole file content to get `line`, `column` and `snippet`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Span {
+    /// file path of the source
+    pub file: Option<PathBuf>,
+    /// byte offset from the start of the input
+    pub offset: usize,
+    /// line number (start from 1), 0 means not resolved
+    pub line: usize,
+    /// column number (start from 1, count by char), 0 means not resolved
+    pub column: usize,
+    /// the source line where the error is, with a `^` under the column
+    pub snippet: Option<String>,
+}
+
+impl Span {
+    pub fn new(offset: usize) -> Self {
+        Self {
+            offset,
+            ..Default::default()
+        }
+    }
+    /// ## get the byte offset of `remain` in `origin`
+    /// nom parsers always return a sub slice of the input, so pointer offset is the exact position,
+    /// if `remain` is not a sub slice of `origin`, fall back to the length difference
+    pub fn offset_in(origin: &str, remain: &str) -> usize {
+        let start = origin.as_ptr() as usize;
+        let current = remain.as_ptr() as usize;
+        if current >= start && current <= start + origin.len() {
+            current - start
+        } else {
+            origin.len().saturating_sub(remain.len())
+        }
+    }
+    /// create span from the remain input of a nom parser
+    pub fn from_remain(origin: &str, remain: &str) -> Self {
+        Self::new(Self::offset_in(origin, remain))
+    }
+    /// move the offset, use when the parsed input is a part of the whole source
+    pub fn shift(&mut self, base: usize) -> &mut Self {
+        self.offset += base;
+        self
+    }
+    pub fn set_file<P>(&mut self, file: P) -> &mut Self
+    where
+        P: AsRef<Path>,
+    {
+        self.file.replace(file.as_ref().to_path_buf());
+        self
+    }
+    pub fn is_resolved(&self) -> bool {
+        self.line != 0
+    }
+    /// ## resolve line, column and snippet from the whole source
+    /// `source` must be the same input which `offset` is counted from
+    pub fn resolve(&mut self, source: &str) -> &mut Self {
+        let mut offset = self.offset.min(source.len());
+        while !source.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let before = &source[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[offset..]
+            .find('\n')
+            .map_or(source.len(), |i| offset + i);
+        let text = source[line_start..line_end].trim_end_matches('\r');
+
+        self.line = before.matches('\n').count() + 1;
+        self.column = source[line_start..offset].chars().count() + 1;
+        self.snippet.replace(format!(
+            "{}\n{}^",
+            text,
+            " ".repeat(self.column - 1)
+        ));
+        self
+    }
+}
+
+impl Display for Span {
+    /// ```text
+    /// --> path/to/file.gen:3:9
+    ///   |
+    /// 3 |         <label :text="" />
+    ///   |         ^
+    /// ```
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let file = self
+            .file
+            .as_ref()
+            .map_or("<input>".to_string(), |file| file.display().to_string());
+
+        if !self.is_resolved() {
+            return write!(f, "--> {} (offset: {})", file, self.offset);
+        }
+
+        write!(f, "--> {}:{}:{}", file, self.line, self.column)?;
+        if let Some(snippet) = self.snippet.as_ref() {
+            let line_no = self.line.to_string();
+            let holder = " ".repeat(line_no.len());
+            let mut lines = snippet.lines();
+            let code = lines.next().unwrap_or_default();
+            let caret = lines.next().unwrap_or_default();
+            write!(
+                f,
+                "\n{holder} |\n{line_no} | {code}\n{holder} | {caret}",
+                holder = holder,
+                line_no = line_no,
+                code = code,
+                caret = caret
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_span {
+    use super::Span;
+
+    #[test]
+    fn resolve() {
+        let source = "<template>\n  <view>\n    <label :text=\"\" />\n";
+        let remain = &source[source.find(":text").unwrap()..];
+        let mut span = Span::from_remain(source, remain);
+        span.resolve(source);
+        assert_eq!((span.line, span.column), (3, 12));
+        assert_eq!(
+            span.snippet.as_deref(),
+            Some("    <label :text=\"\" />\n           ^")
+        );
+    }
+
+    #[test]
+    fn offset_not_sub_slice() {
+        let source = "abcdef";
+        let remain = String::from("ef");
+        assert_eq!(Span::offset_in(source, &remain), 4);
+    }
+}